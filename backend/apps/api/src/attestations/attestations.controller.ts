@@ -35,7 +35,13 @@ export class AttestationsController {
     currency: 'SOL',
     description: 'On-chain attestation creation',
   })
-  @ApiOperation({ summary: 'Create an on-chain attestation' })
+  @ApiOperation({
+    summary: 'Create an on-chain attestation',
+    description:
+      'Covers every wallet of the audit for its tax year. Audits of more than 10 wallets ' +
+      'are refused: the program claims one uniqueness slot per wallet and tax year, at ' +
+      'most 10 per attestation.',
+  })
   @ApiCreatedResponse({
     description: 'Attestation created and submitted to Solana (covers all audit wallets)',
     schema: {
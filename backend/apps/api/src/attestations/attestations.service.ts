@@ -10,6 +10,7 @@ import {
 } from '@auditswarm/queue';
 import { generateAuditHash, JurisdictionCode } from '@auditswarm/common';
 import type { Attestation } from '@prisma/client';
+import { MAX_WALLET_SLOTS } from '../../../../../onchain/sdk/src';
 
 export interface CreateAttestationDto {
   auditId: string;
@@ -43,6 +44,14 @@ export class AttestationsService {
       throw new BadRequestException('Audit has no associated wallets');
     }
 
+    // Each wallet takes a uniqueness slot for the audit's tax year, and the program
+    // claims them all in the creating transaction
+    if (auditWallets.length > MAX_WALLET_SLOTS) {
+      throw new BadRequestException(
+        `An attestation covers at most ${MAX_WALLET_SLOTS} wallets; this audit has ${auditWallets.length}`,
+      );
+    }
+
     const walletAddresses = auditWallets.map((aw) => aw.wallet.address);
     const primaryWalletId = auditWallets[0].walletId;
 
@@ -2,6 +2,7 @@ import { Processor, WorkerHost, OnWorkerEvent } from '@nestjs/bullmq';
 import { Logger } from '@nestjs/common';
 import { ConfigService } from '@nestjs/config';
 import { Job } from 'bullmq';
+import { createHash } from 'crypto';
 import {
   QUEUES,
   JOB_NAMES,
@@ -9,6 +10,7 @@ import {
   RevokeAttestationJobData,
 } from '@auditswarm/queue';
 import { AttestationRepository } from '@auditswarm/database';
+import type { JurisdictionCode } from '@auditswarm/common';
 import {
   Connection,
   PublicKey,
@@ -16,12 +18,25 @@ import {
   TransactionMessage,
   VersionedTransaction,
   SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
 } from '@solana/web3.js';
 import {
+  AuditSwarmSolana,
   getStatePDA,
   getAttestationPDA,
+  getIssuerPDA,
+  getJurisdictionConfigPDA,
+  getSchemaPDA,
+  getWalletLinkPDA,
+  getAttestationSlotPDA,
+  getSlotPeriod,
+  getCoveredTaxYears,
+  fiscalYearPeriod,
   Jurisdiction,
+  ListedWallets,
   AttestationType,
+  AttestationStatus,
+  RevocationReason,
 } from '../../../../../onchain/sdk/src';
 
 // Program ID matching devnet deployment
@@ -64,7 +79,7 @@ export class AttestationProcessor extends WorkerHost {
     const {
       attestationId,
       walletAddresses,
-      jurisdiction,
+      jurisdiction: jurisdictionCode,
       type,
       taxYear,
       hash,
@@ -92,13 +107,64 @@ export class AttestationProcessor extends WorkerHost {
       const walletPubkeys = walletAddresses.map(
         (addr) => new PublicKey(addr),
       );
-      const jurisdictionEnum = this.mapJurisdiction(jurisdiction);
       const attestationTypeEnum = this.mapAttestationType(type);
+      const jurisdiction = this.mapJurisdiction(jurisdictionCode);
+      const sdk = new AuditSwarmSolana(connection, PROGRAM_ID);
+
+      // The attestation is created under the issuer's next sequence number
+      const issuer = await sdk.getIssuer(authority.publicKey);
+      if (!issuer) {
+        await this.setFailed(
+          attestationId,
+          'SOLANA_AUTHORITY_KEY is not a registered issuer',
+        );
+        return;
+      }
+
+      // The program only accepts jurisdiction codes its admin has registered
+      const jurisdictionConfig = await sdk.getJurisdictionConfig(jurisdiction);
+      if (!jurisdictionConfig) {
+        await this.setFailed(
+          attestationId,
+          `Jurisdiction ${jurisdiction} is not registered on-chain`,
+        );
+        return;
+      }
+
+      // API attestations are issued under the base schema of their type,
+      // registered at id = the type's index, version 1
+      const schema = { id: attestationTypeEnum, version: 1 };
+      const schemaData = await sdk.getSchema(schema);
+      if (!schemaData || schemaData.deprecated) {
+        await this.setFailed(
+          attestationId,
+          `No usable base schema registered for ${type}`,
+        );
+        return;
+      }
 
       // Derive PDAs
       const [statePDA] = getStatePDA(PROGRAM_ID);
       const hashBytes = Buffer.from(hash, 'hex').slice(0, 32);
-      const [attestationPDA] = getAttestationPDA(hashBytes, PROGRAM_ID);
+      const [attestationPDA] = getAttestationPDA(
+        authority.publicKey,
+        issuer.nextSequence,
+        PROGRAM_ID,
+      );
+      const [issuerPDA] = getIssuerPDA(authority.publicKey, PROGRAM_ID);
+      const [jurisdictionConfigPDA] = getJurisdictionConfigPDA(
+        jurisdiction,
+        PROGRAM_ID,
+      );
+      const [schemaPDA] = getSchemaPDA(schema.id, schema.version, PROGRAM_ID);
+
+      // Jobs attest for the fiscal year starting in the tax year, on the
+      // jurisdiction's fiscal calendar
+      const period = fiscalYearPeriod(
+        taxYear,
+        jurisdictionConfig.fiscalYearStartMonth,
+        jurisdictionConfig.fiscalYearStartDay,
+      );
 
       // Build instruction data
       const expiresAtTimestamp = BigInt(
@@ -106,17 +172,30 @@ export class AttestationProcessor extends WorkerHost {
       );
 
       const walletsDataSize = 4 + walletPubkeys.length * 32;
-      const data = Buffer.alloc(8 + 1 + 1 + 2 + 32 + 8 + walletsDataSize);
+      const data = Buffer.alloc(
+        8 + 2 + 3 + 1 + 2 + 2 + 1 + 8 + 8 + 32 + 8 + walletsDataSize + 1 + 1,
+      );
       let offset = 0;
 
       DISCRIMINATORS.createAttestation.copy(data, offset);
       offset += 8;
-      data.writeUInt8(jurisdictionEnum, offset);
-      offset += 1;
+      data.write(jurisdiction, offset, 'ascii');
+      offset += 2;
+      // subdivision left zeroed: jobs attest for the whole jurisdiction
+      offset += 3;
       data.writeUInt8(attestationTypeEnum, offset);
       offset += 1;
       data.writeUInt16LE(taxYear, offset);
       offset += 2;
+      // end_tax_year: jobs attest for a single tax year
+      data.writeUInt16LE(taxYear, offset);
+      offset += 2;
+      data.writeUInt8(period.kind, offset);
+      offset += 1;
+      data.writeBigInt64LE(BigInt(period.start), offset);
+      offset += 8;
+      data.writeBigInt64LE(BigInt(period.end), offset);
+      offset += 8;
       hashBytes.copy(data, offset);
       offset += 32;
       data.writeBigInt64LE(expiresAtTimestamp, offset);
@@ -127,13 +206,27 @@ export class AttestationProcessor extends WorkerHost {
         pubkey.toBuffer().copy(data, offset);
         offset += 32;
       }
+      // require_consent = false: API-issued attestations are created Active
+      data.writeUInt8(0, offset);
+      offset += 1;
+      data.writeUInt8(AttestationStatus.Active, offset);
+      offset += 1;
 
-      // Account order per Anchor IDL: state, attestation, authority, system_program
+      // Account order per Anchor IDL: state, attestation, issuer,
+      // jurisdiction_config, schema, authority, system_program, instructions,
+      // then the wallet links and slots
       const ix = {
         programId: PROGRAM_ID,
         keys: [
           { pubkey: statePDA, isSigner: false, isWritable: true },
           { pubkey: attestationPDA, isSigner: false, isWritable: true },
+          { pubkey: issuerPDA, isSigner: false, isWritable: true },
+          {
+            pubkey: jurisdictionConfigPDA,
+            isSigner: false,
+            isWritable: false,
+          },
+          { pubkey: schemaPDA, isSigner: false, isWritable: false },
           {
             pubkey: authority.publicKey,
             isSigner: true,
@@ -144,6 +237,21 @@ export class AttestationProcessor extends WorkerHost {
             isSigner: false,
             isWritable: false,
           },
+          {
+            pubkey: SYSVAR_INSTRUCTIONS_PUBKEY,
+            isSigner: false,
+            isWritable: false,
+          },
+          ...this.walletAccountKeys(attestationPDA, {
+            wallets: walletPubkeys,
+            jurisdiction,
+            subdivision: null,
+            attestationType: attestationTypeEnum,
+            taxYear,
+            endTaxYear: taxYear,
+            periodKind: period.kind,
+            periodStart: BigInt(period.start),
+          }),
         ],
         data,
       };
@@ -185,7 +293,7 @@ export class AttestationProcessor extends WorkerHost {
   private async processRevoke(
     job: Job<RevokeAttestationJobData>,
   ): Promise<void> {
-    const { attestationId, hash, reason } = job.data;
+    const { attestationId, reason } = job.data;
 
     this.logger.log(
       `Revoking on-chain attestation ${attestationId}: ${reason}`,
@@ -201,12 +309,40 @@ export class AttestationProcessor extends WorkerHost {
         return;
       }
 
-      // Derive PDAs
+      // Audit hashes are not unique on-chain; use the account recorded at creation
+      const record = await this.attestationRepository.findById(attestationId);
+      if (!record?.onChainAccount) {
+        this.logger.warn(
+          `Attestation ${attestationId} was never created on-chain — nothing to revoke`,
+        );
+        return;
+      }
+
       const [statePDA] = getStatePDA(PROGRAM_ID);
-      const hashBytes = Buffer.from(hash, 'hex').slice(0, 32);
-      const [attestationPDA] = getAttestationPDA(hashBytes, PROGRAM_ID);
+      const attestationPDA = new PublicKey(record.onChainAccount);
+
+      // Every listed wallet's link records the revocation and its slot is freed
+      const onChain = await new AuditSwarmSolana(
+        connection,
+        PROGRAM_ID,
+      ).getAttestationByAddress(attestationPDA);
+      if (!onChain) {
+        this.logger.warn(
+          `Attestation ${attestationId} not found on-chain — nothing to revoke`,
+        );
+        return;
+      }
+
+      // revoke_attestation(reason: RevocationReason, reason_hash: Option<[u8; 32]>).
+      // Revocations from the API are requested by the client; the free-text
+      // reason stays in Postgres and only its SHA-256 goes on-chain.
+      const reasonHash = createHash('sha256').update(reason).digest();
+      const data = Buffer.concat([
+        DISCRIMINATORS.revokeAttestation,
+        Buffer.from([RevocationReason.ClientRequest, 1]),
+        reasonHash,
+      ]);
 
-      // revoke_attestation takes no arguments — just the 8-byte discriminator
       const ix = {
         programId: PROGRAM_ID,
         keys: [
@@ -217,8 +353,9 @@ export class AttestationProcessor extends WorkerHost {
             isSigner: true,
             isWritable: false,
           },
+          ...this.walletAccountKeys(attestationPDA, onChain),
         ],
-        data: DISCRIMINATORS.revokeAttestation,
+        data,
       };
 
       const signature = await this.sendTransaction(connection, authority, ix);
@@ -236,6 +373,33 @@ export class AttestationProcessor extends WorkerHost {
 
   // ─── Helpers ────────────────────────────────────────────────────────
 
+  /**
+   * Wallet links, then uniqueness slots (one per covered tax year), for every
+   * listed wallet
+   */
+  private walletAccountKeys(attestation: PublicKey, listed: ListedWallets) {
+    const { wallets, jurisdiction, subdivision, attestationType } = listed;
+    return [
+      ...wallets.map(
+        (wallet) => getWalletLinkPDA(wallet, attestation, PROGRAM_ID)[0],
+      ),
+      ...wallets.flatMap((wallet) =>
+        getCoveredTaxYears(listed).map(
+          (taxYear) =>
+            getAttestationSlotPDA(
+              wallet,
+              jurisdiction,
+              subdivision,
+              taxYear,
+              attestationType,
+              getSlotPeriod(listed.periodKind, listed.periodStart),
+              PROGRAM_ID,
+            )[0],
+        ),
+      ),
+    ].map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }));
+  }
+
   private getSolanaContext(): {
     connection: Connection;
     authority: Keypair | null;
@@ -311,28 +475,19 @@ export class AttestationProcessor extends WorkerHost {
     }
   }
 
-  private mapJurisdiction(code: string): Jurisdiction {
+  /**
+   * On-chain jurisdictions are ISO 3166-1 alpha-2 codes. The backend's UK is GB;
+   * EU stays EU, the code ISO exceptionally reserves for the European Union,
+   * registered on-chain for attestations that cover the union as a whole.
+   */
+  private mapJurisdiction(code: JurisdictionCode): Jurisdiction {
     switch (code) {
-      case 'US':
-        return Jurisdiction.US;
-      case 'EU':
-        return Jurisdiction.EU;
-      case 'BR':
-        return Jurisdiction.BR;
       case 'UK':
-        return Jurisdiction.UK;
-      case 'JP':
-        return Jurisdiction.JP;
-      case 'AU':
-        return Jurisdiction.AU;
-      case 'CA':
-        return Jurisdiction.CA;
-      case 'CH':
-        return Jurisdiction.CH;
-      case 'SG':
-        return Jurisdiction.SG;
+        return 'GB';
+      case 'EU':
+        return 'EU';
       default:
-        return Jurisdiction.US;
+        return code;
     }
   }
 
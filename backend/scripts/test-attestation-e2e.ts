@@ -17,18 +17,20 @@ import { PrismaClient } from '@prisma/client';
 import { Queue } from 'bullmq';
 import { Connection, PublicKey } from '@solana/web3.js';
 import { createHash } from 'crypto';
+import {
+  AttestationDerivation,
+  AttestationStatus,
+  getAttestationDerivation,
+  parseAttestationData,
+  parseLegacyAttestationData,
+} from '../../onchain/sdk/src';
 
 // --- Constants ---
 
 const PROGRAM_ID = new PublicKey('52LCg2VXDYgam4yHkXEp2vN2psUmo6Q7rv5efRm7ic8c');
 const STATE_SEED = Buffer.from('state');
-const ATTESTATION_SEED = Buffer.from('attestation');
 const RPC_URL = process.env.SOLANA_RPC_URL || 'https://api.devnet.solana.com';
 
-const JURISDICTION_MAP: Record<string, number> = {
-  US: 0, EU: 1, BR: 2, UK: 3, JP: 4, AU: 5, CA: 6, CH: 7, SG: 8,
-};
-
 const ATTESTATION_TYPE_MAP: Record<string, number> = {
   TAX_COMPLIANCE: 0, AUDIT_COMPLETE: 1, REPORTING_COMPLETE: 2,
   QUARTERLY_REVIEW: 3, ANNUAL_REVIEW: 4,
@@ -40,44 +42,10 @@ function getStatePDA(): [PublicKey, number] {
   return PublicKey.findProgramAddressSync([STATE_SEED], PROGRAM_ID);
 }
 
-function getAttestationPDA(auditHashBytes: Buffer): [PublicKey, number] {
-  return PublicKey.findProgramAddressSync(
-    [ATTESTATION_SEED, auditHashBytes],
-    PROGRAM_ID,
-  );
-}
-
-function parseAttestationAccount(data: Buffer) {
-  let offset = 8; // skip account discriminator
-
-  const bump = data[offset]; offset += 1;
-  const authority = new PublicKey(data.slice(offset, offset + 32)); offset += 32;
-  const jurisdiction = data[offset]; offset += 1;
-  const attestationType = data[offset]; offset += 1;
-  const status = data[offset]; offset += 1;
-  const taxYear = data.readUInt16LE(offset); offset += 2;
-  const auditHash = Buffer.from(data.slice(offset, offset + 32)); offset += 32;
-  const issuedAt = Number(data.readBigInt64LE(offset)); offset += 8;
-  const expiresAt = Number(data.readBigInt64LE(offset)); offset += 8;
-  const revokedAt = Number(data.readBigInt64LE(offset)); offset += 8;
-  const numWallets = data[offset]; offset += 1;
-
-  const vecLen = data.readUInt32LE(offset); offset += 4;
-  const wallets: PublicKey[] = [];
-  for (let i = 0; i < vecLen; i++) {
-    wallets.push(new PublicKey(data.slice(offset, offset + 32)));
-    offset += 32;
-  }
-
-  return {
-    bump, authority, jurisdiction, attestationType, status,
-    taxYear, auditHash, issuedAt, expiresAt, revokedAt,
-    numWallets, wallets,
-  };
-}
+// The backend's UK is registered on-chain under its ISO code
+const ON_CHAIN_JURISDICTIONS: Record<string, string> = { UK: 'GB' };
 
-const STATUS_NAMES = ['Pending', 'Active', 'Expired', 'Revoked'];
-const JURISDICTION_NAMES = ['US', 'EU', 'BR', 'UK', 'JP', 'AU', 'CA', 'CH', 'SG'];
+const STATUS_NAMES = ['Pending', 'Active', 'Expired', 'Revoked', 'Superseded', 'Suspended'];
 
 function sleep(ms: number) {
   return new Promise(r => setTimeout(r, ms));
@@ -301,20 +269,11 @@ async function verifyOnChain(
 ) {
   console.log('\n--- On-Chain Verification ---');
 
-  // Derive PDA from hash
-  const hashBytes = Buffer.from(hash, 'hex').slice(0, 32);
-  const [derivedPDA] = getAttestationPDA(hashBytes);
-  console.log(`  Derived PDA: ${derivedPDA.toBase58()}`);
+  const account = new PublicKey(expectedAccount);
   console.log(`  Expected account: ${expectedAccount}`);
 
-  if (derivedPDA.toBase58() !== expectedAccount) {
-    console.error('  MISMATCH: Derived PDA does not match on-chain account!');
-    process.exit(1);
-  }
-  console.log('  PDA derivation: MATCH');
-
   // Fetch on-chain account
-  const accountInfo = await connection.getAccountInfo(derivedPDA);
+  const accountInfo = await connection.getAccountInfo(account);
   if (!accountInfo) {
     console.error('  ERROR: On-chain account not found!');
     process.exit(1);
@@ -327,15 +286,28 @@ async function verifyOnChain(
   }
   console.log('  Owner: MATCH (attestation program)');
 
-  // Parse on-chain data
-  const parsed = parseAttestationAccount(accountInfo.data as Buffer);
+  // New attestations sit at ["attestation", issuer, sequence]; older ones at
+  // ["attestation", hash]. Either is accepted.
+  const derivation = getAttestationDerivation(account, accountInfo.data as Buffer, PROGRAM_ID);
+  if (!derivation) {
+    console.error('  MISMATCH: Account is not at a known attestation PDA!');
+    process.exit(1);
+  }
+  console.log(`  PDA derivation: MATCH (${derivation})`);
+
+  // Parse on-chain data with the layout of its derivation
+  const data = accountInfo.data as Buffer;
+  const parsed = derivation === AttestationDerivation.Legacy
+    ? parseLegacyAttestationData(data)
+    : parseAttestationData(data);
+  const auditHashHex = Buffer.from(parsed.auditHash).toString('hex');
 
-  console.log(`  On-chain jurisdiction: ${JURISDICTION_NAMES[parsed.jurisdiction] || parsed.jurisdiction}`);
+  console.log(`  On-chain jurisdiction: ${parsed.jurisdiction}`);
   console.log(`  On-chain status: ${STATUS_NAMES[parsed.status] || parsed.status}`);
   console.log(`  On-chain tax year: ${parsed.taxYear}`);
-  console.log(`  On-chain audit hash: ${parsed.auditHash.toString('hex').slice(0, 16)}...`);
-  console.log(`  On-chain issued_at: ${new Date(parsed.issuedAt * 1000).toISOString()}`);
-  console.log(`  On-chain expires_at: ${new Date(parsed.expiresAt * 1000).toISOString()}`);
+  console.log(`  On-chain audit hash: ${auditHashHex.slice(0, 16)}...`);
+  console.log(`  On-chain issued_at: ${new Date(Number(parsed.issuedAt) * 1000).toISOString()}`);
+  console.log(`  On-chain expires_at: ${new Date(Number(parsed.expiresAt) * 1000).toISOString()}`);
   console.log(`  On-chain wallets (${parsed.wallets.length}):`);
   for (const w of parsed.wallets) {
     console.log(`    - ${w.toBase58()}`);
@@ -344,7 +316,7 @@ async function verifyOnChain(
   // Verify fields match
   let allMatch = true;
 
-  const expectedJurisdiction = JURISDICTION_MAP[expected.jurisdiction] ?? -1;
+  const expectedJurisdiction = ON_CHAIN_JURISDICTIONS[expected.jurisdiction] ?? expected.jurisdiction;
   if (parsed.jurisdiction !== expectedJurisdiction) {
     console.error(`  MISMATCH: jurisdiction (on-chain: ${parsed.jurisdiction}, expected: ${expectedJurisdiction})`);
     allMatch = false;
@@ -355,16 +327,15 @@ async function verifyOnChain(
     allMatch = false;
   }
 
-  if (parsed.status !== 1) { // Active = 1
-    console.error(`  MISMATCH: status is not Active (got ${STATUS_NAMES[parsed.status]})`);
+  if (parsed.status !== AttestationStatus.Active) {
+    console.error(`  MISMATCH: status is not Active (got ${STATUS_NAMES[parsed.status] || parsed.status})`);
     allMatch = false;
   }
 
-  const onChainHash = parsed.auditHash.toString('hex');
   const expectedHash = hash.slice(0, 64); // sha256 = 64 hex chars = 32 bytes
-  if (onChainHash !== expectedHash) {
+  if (auditHashHex !== expectedHash) {
     console.error(`  MISMATCH: audit hash`);
-    console.error(`    on-chain: ${onChainHash}`);
+    console.error(`    on-chain: ${auditHashHex}`);
     console.error(`    expected: ${expectedHash}`);
     allMatch = false;
   }
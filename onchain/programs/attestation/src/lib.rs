@@ -1,4 +1,12 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use anchor_lang::system_program;
+use std::ops::RangeInclusive;
+
+pub mod merkle;
 
 declare_id!("52LCg2VXDYgam4yHkXEp2vN2psUmo6Q7rv5efRm7ic8c");
 
@@ -6,12 +14,16 @@ declare_id!("52LCg2VXDYgam4yHkXEp2vN2psUmo6Q7rv5efRm7ic8c");
 pub mod attestation {
     use super::*;
 
-    /// Initialize the program state
+    /// Initialize the program state (restricted to the program's upgrade authority)
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         let state = &mut ctx.accounts.state;
         state.authority = ctx.accounts.authority.key();
         state.attestation_count = 0;
         state.bump = ctx.bumps.state;
+        state.pending_authority = Pubkey::default();
+        state.min_tax_year = DEFAULT_MIN_TAX_YEAR;
+        state.max_tax_year = DEFAULT_MAX_TAX_YEAR;
+        state.retention_period = DEFAULT_RETENTION_PERIOD;
 
         emit!(ProgramInitialized {
             authority: state.authority,
@@ -20,24 +32,83 @@ pub mod attestation {
         Ok(())
     }
 
-    /// Create a new attestation covering multiple wallets
-    pub fn create_attestation(
-        ctx: Context<CreateAttestation>,
-        jurisdiction: Jurisdiction,
+    /// Create a new attestation covering multiple wallets. With `require_consent` it
+    /// stays Pending until every listed wallet has acknowledged, either through
+    /// `acknowledge_attestation` or a signed consent message verified by an
+    /// Ed25519SigVerify instruction earlier in the same transaction. An
+    /// `initial_status` of Pending also holds it until `activate_attestation`.
+    ///
+    /// The account lives at `["attestation", authority, sequence]`, where `sequence`
+    /// is the issuer's `next_sequence`, so audit hashes need not be unique.
+    /// `jurisdiction` is an ISO 3166-1 alpha-2 code registered with `add_jurisdiction`;
+    /// `subdivision`, if not all zeros, one of its ISO 3166-2 subdivision codes.
+    /// The attestation is issued under `schema`, which must be of `attestation_type`;
+    /// an `expires_at` of 0 takes the schema's default validity. The attestation
+    /// covers tax years `tax_year` through `end_tax_year`, each labelled by the
+    /// calendar year its fiscal year starts in. `period_start` and `period_end` bound
+    /// the fiscal period covered (end exclusive), starting in fiscal year `tax_year`
+    /// and ending in `end_tax_year`; annual, quarterly and monthly periods must follow
+    /// the jurisdiction's fiscal calendar, and only annual and custom periods can span
+    /// several years.
+    ///
+    /// `remaining_accounts` are the `WalletAttestationLink` PDAs of `wallets` in wallet
+    /// order, then their `AttestationSlot` PDAs: one per wallet and covered year,
+    /// wallet by wallet, at most `MAX_WALLET_SLOTS` in all. A wallet whose slot for
+    /// any year is held by another attestation is refused; amend that one with
+    /// `supersede_attestation`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_attestation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateAttestation<'info>>,
+        jurisdiction: [u8; 2],
+        subdivision: [u8; 3],
         attestation_type: AttestationType,
         tax_year: u16,
+        end_tax_year: u16,
+        period_kind: PeriodKind,
+        period_start: i64,
+        period_end: i64,
         audit_hash: [u8; 32],
         expires_at: i64,
         wallets: Vec<Pubkey>,
+        require_consent: bool,
+        initial_status: AttestationStatus,
     ) -> Result<()> {
+        let clock = Clock::get()?;
+
         require!(
-            !wallets.is_empty() && wallets.len() <= MAX_WALLETS,
-            AttestationError::InvalidWalletCount
+            matches!(
+                initial_status,
+                AttestationStatus::Pending | AttestationStatus::Active
+            ),
+            AttestationError::InvalidInitialStatus
         );
 
+        let schema = &ctx.accounts.schema;
+        schema.check(jurisdiction, subdivision, require_consent, true)?;
+        let expires_at = schema.resolve_expiry(expires_at, clock.unix_timestamp);
+
+        validate_attestation_args(
+            &ctx.accounts.state,
+            tax_year,
+            end_tax_year,
+            &audit_hash,
+            expires_at,
+            &wallets,
+            clock.unix_timestamp,
+        )?;
+
+        let config = JurisdictionConfig::load(&ctx.accounts.jurisdiction_config)?;
+        config.check_subdivision(subdivision)?;
+        config.check_period(period_kind, period_start, period_end, tax_year, end_tax_year)?;
+
+        let issuer = &mut ctx.accounts.issuer;
+        issuer.check_rights(jurisdiction, attestation_type)?;
+        issuer.attestation_count += 1;
+        let sequence = issuer.next_sequence;
+        issuer.next_sequence += 1;
+
         let attestation_key = ctx.accounts.attestation.key();
         let authority_key = ctx.accounts.authority.key();
-        let clock = Clock::get()?;
 
         let attestation = &mut ctx.accounts.attestation;
         let state = &mut ctx.accounts.state;
@@ -46,34 +117,576 @@ pub mod attestation {
         attestation.authority = authority_key;
         attestation.jurisdiction = jurisdiction;
         attestation.attestation_type = attestation_type;
-        attestation.status = AttestationStatus::Active;
         attestation.tax_year = tax_year;
+        attestation.end_tax_year = end_tax_year;
         attestation.audit_hash = audit_hash;
         attestation.issued_at = clock.unix_timestamp;
         attestation.expires_at = expires_at;
         attestation.revoked_at = 0;
+        attestation.supersedes = Pubkey::default();
+        attestation.superseded_by = Pubkey::default();
+        attestation.renewed_at = 0;
+        attestation.renewal_count = 0;
+        attestation.review_hash = [0u8; 32];
+        attestation.suspended_at = 0;
+        attestation.reinstated_at = 0;
+        attestation.suspension_reason = None;
+        attestation.revocation_reason = None;
+        attestation.revocation_hash = [0u8; 32];
+        attestation.consent_required = require_consent;
+        attestation.activation_pending = initial_status == AttestationStatus::Pending;
+        attestation.acknowledged = 0;
+        attestation.contested_by = 0;
+        attestation.wallet_root = [0u8; 32];
+        attestation.wallet_count = wallets.len() as u32;
+        attestation.sequence = sequence;
+        attestation.subdivision = subdivision;
+        attestation.schema_id = ctx.accounts.schema.id;
+        attestation.schema_version = ctx.accounts.schema.version;
+        attestation.period_kind = period_kind;
+        attestation.period_start = period_start;
+        attestation.period_end = period_end;
         attestation.num_wallets = wallets.len() as u8;
         attestation.wallets = wallets.clone();
 
+        let acknowledged = verified_consents(&ctx.accounts.instructions, attestation)?;
+        attestation.acknowledged = acknowledged;
+        attestation.status = if attestation.ready_to_activate() {
+            AttestationStatus::Active
+        } else {
+            AttestationStatus::Pending
+        };
+
         state.attestation_count += 1;
 
+        let (links, slots) = wallet_accounts(ctx.remaining_accounts, wallets.len());
+        create_wallet_links(
+            links,
+            attestation_key,
+            &wallets,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?;
+        claim_slots(
+            slots,
+            attestation,
+            &wallets,
+            Pubkey::default(),
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?;
+
+        for (i, wallet) in wallets.iter().enumerate() {
+            if acknowledged & (1 << i) != 0 {
+                emit!(AttestationAcknowledged {
+                    attestation: attestation_key,
+                    wallet: *wallet,
+                    acknowledged,
+                });
+            }
+        }
+
         emit!(AttestationCreated {
             attestation: attestation_key,
             wallets,
             jurisdiction,
+            subdivision,
             attestation_type,
+            schema_id: attestation.schema_id,
+            schema_version: attestation.schema_version,
             tax_year,
+            end_tax_year,
+            period_kind,
+            period_start,
+            period_end,
             audit_hash,
             issued_at: attestation.issued_at,
             expires_at,
+            status: attestation.status,
+            wallet_root: attestation.wallet_root,
+            wallet_count: attestation.wallet_count,
         });
 
         Ok(())
     }
 
-    /// Update attestation status
-    pub fn update_status(
-        ctx: Context<UpdateAttestation>,
+    /// Create an attestation whose wallets are committed to as a Merkle root (see
+    /// `merkle`), for wallet sets larger than `MAX_WALLETS`. Coverage of a wallet is
+    /// checked with `verify_wallet_inclusion`; wallet consent, wallet links and
+    /// uniqueness slots do not apply, so one wallet can be covered by overlapping
+    /// Merkle attestations. TaxCompliance attestations are refused here (see
+    /// `AttestationType::allows_merkle_wallets`), as are those under schemas that set
+    /// `SCHEMA_REQUIRES_LISTED_WALLETS`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_merkle_attestation(
+        ctx: Context<CreateMerkleAttestation>,
+        jurisdiction: [u8; 2],
+        subdivision: [u8; 3],
+        attestation_type: AttestationType,
+        tax_year: u16,
+        end_tax_year: u16,
+        period_kind: PeriodKind,
+        period_start: i64,
+        period_end: i64,
+        audit_hash: [u8; 32],
+        expires_at: i64,
+        wallet_root: [u8; 32],
+        wallet_count: u32,
+        initial_status: AttestationStatus,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        require!(
+            matches!(
+                initial_status,
+                AttestationStatus::Pending | AttestationStatus::Active
+            ),
+            AttestationError::InvalidInitialStatus
+        );
+        require!(
+            attestation_type.allows_merkle_wallets(),
+            AttestationError::MerkleWalletsNotAllowed
+        );
+        require!(wallet_count > 0, AttestationError::InvalidWalletCount);
+        require!(
+            wallet_root != [0u8; 32],
+            AttestationError::InvalidWalletRoot
+        );
+
+        let schema = &ctx.accounts.schema;
+        schema.check(jurisdiction, subdivision, false, false)?;
+        let expires_at = schema.resolve_expiry(expires_at, clock.unix_timestamp);
+
+        validate_attestation_terms(
+            &ctx.accounts.state,
+            tax_year,
+            end_tax_year,
+            &audit_hash,
+            expires_at,
+            clock.unix_timestamp,
+        )?;
+
+        let config = JurisdictionConfig::load(&ctx.accounts.jurisdiction_config)?;
+        config.check_subdivision(subdivision)?;
+        config.check_period(period_kind, period_start, period_end, tax_year, end_tax_year)?;
+
+        let issuer = &mut ctx.accounts.issuer;
+        issuer.check_rights(jurisdiction, attestation_type)?;
+        issuer.attestation_count += 1;
+        let sequence = issuer.next_sequence;
+        issuer.next_sequence += 1;
+
+        let attestation_key = ctx.accounts.attestation.key();
+        let authority_key = ctx.accounts.authority.key();
+
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.bump = ctx.bumps.attestation;
+        attestation.authority = authority_key;
+        attestation.jurisdiction = jurisdiction;
+        attestation.attestation_type = attestation_type;
+        attestation.tax_year = tax_year;
+        attestation.end_tax_year = end_tax_year;
+        attestation.audit_hash = audit_hash;
+        attestation.issued_at = clock.unix_timestamp;
+        attestation.expires_at = expires_at;
+        attestation.revoked_at = 0;
+        attestation.supersedes = Pubkey::default();
+        attestation.superseded_by = Pubkey::default();
+        attestation.renewed_at = 0;
+        attestation.renewal_count = 0;
+        attestation.review_hash = [0u8; 32];
+        attestation.suspended_at = 0;
+        attestation.reinstated_at = 0;
+        attestation.suspension_reason = None;
+        attestation.revocation_reason = None;
+        attestation.revocation_hash = [0u8; 32];
+        attestation.consent_required = false;
+        attestation.activation_pending = initial_status == AttestationStatus::Pending;
+        attestation.acknowledged = 0;
+        attestation.contested_by = 0;
+        attestation.wallet_root = wallet_root;
+        attestation.wallet_count = wallet_count;
+        attestation.sequence = sequence;
+        attestation.subdivision = subdivision;
+        attestation.schema_id = ctx.accounts.schema.id;
+        attestation.schema_version = ctx.accounts.schema.version;
+        attestation.period_kind = period_kind;
+        attestation.period_start = period_start;
+        attestation.period_end = period_end;
+        attestation.num_wallets = 0;
+        attestation.wallets = Vec::new();
+        attestation.status = initial_status;
+
+        ctx.accounts.state.attestation_count += 1;
+
+        emit!(AttestationCreated {
+            attestation: attestation_key,
+            wallets: Vec::new(),
+            jurisdiction,
+            subdivision,
+            attestation_type,
+            schema_id: attestation.schema_id,
+            schema_version: attestation.schema_version,
+            tax_year,
+            end_tax_year,
+            period_kind,
+            period_start,
+            period_end,
+            audit_hash,
+            issued_at: attestation.issued_at,
+            expires_at,
+            status: attestation.status,
+            wallet_root,
+            wallet_count,
+        });
+
+        Ok(())
+    }
+
+    /// Report via return data whether `wallet` is one of the attestation's wallets.
+    /// `proof` is only used for Merkle-root attestations. Status is not checked.
+    pub fn verify_wallet_inclusion(
+        ctx: Context<VerifyWalletInclusion>,
+        wallet: Pubkey,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<bool> {
+        Ok(ctx.accounts.attestation.covers(&wallet, &proof))
+    }
+
+    /// Report via return data whether the attestation covers `tax_year`. Status is
+    /// not checked.
+    pub fn covers_tax_year(ctx: Context<CoversTaxYear>, tax_year: u16) -> Result<bool> {
+        Ok(ctx.accounts.attestation.covers_tax_year(tax_year))
+    }
+
+    /// Replace an active attestation with an amended one. The new attestation keeps
+    /// the jurisdiction, subdivision, type, tax years and period of the one it
+    /// supersedes, and is issued under `schema`, a current schema of that type;
+    /// `expires_at` 0 takes the schema's default validity.
+    ///
+    /// `remaining_accounts` are the new attestation's wallet links and then its slots,
+    /// each in wallet order, followed by the slots of the previous attestation's
+    /// wallets that are dropped, in their previous order. Slots held by the previous
+    /// attestation pass to the new one.
+    pub fn supersede_attestation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SupersedeAttestation<'info>>,
+        audit_hash: [u8; 32],
+        expires_at: i64,
+        wallets: Vec<Pubkey>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let previous_key = ctx.accounts.previous.key();
+        let attestation_key = ctx.accounts.attestation.key();
+        let authority_key = ctx.accounts.authority.key();
+
+        let previous = &mut ctx.accounts.previous;
+        require!(
+            previous.status == AttestationStatus::Active,
+            AttestationError::AttestationNotActive
+        );
+
+        // The amendment is issued under the schema and jurisdiction rules in force
+        // now, not those the previous attestation was created under
+        let schema = &ctx.accounts.schema;
+        schema.check(
+            previous.jurisdiction,
+            previous.subdivision,
+            previous.consent_required,
+            true,
+        )?;
+        let expires_at = schema.resolve_expiry(expires_at, clock.unix_timestamp);
+
+        let config = JurisdictionConfig::load(&ctx.accounts.jurisdiction_config)?;
+        config.check_subdivision(previous.subdivision)?;
+        config.check_period(
+            previous.period_kind,
+            previous.period_start,
+            previous.period_end,
+            previous.tax_year,
+            previous.end_tax_year,
+        )?;
+
+        validate_attestation_args(
+            &ctx.accounts.state,
+            previous.tax_year,
+            previous.end_tax_year,
+            &audit_hash,
+            expires_at,
+            &wallets,
+            clock.unix_timestamp,
+        )?;
+
+        let issuer = &mut ctx.accounts.issuer;
+        issuer.check_rights(previous.jurisdiction, previous.attestation_type)?;
+        issuer.attestation_count += 1;
+        let sequence = issuer.next_sequence;
+        issuer.next_sequence += 1;
+
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.bump = ctx.bumps.attestation;
+        attestation.authority = authority_key;
+        attestation.jurisdiction = previous.jurisdiction;
+        attestation.attestation_type = previous.attestation_type;
+        attestation.tax_year = previous.tax_year;
+        attestation.end_tax_year = previous.end_tax_year;
+        attestation.audit_hash = audit_hash;
+        attestation.issued_at = clock.unix_timestamp;
+        attestation.expires_at = expires_at;
+        attestation.revoked_at = 0;
+        attestation.supersedes = previous_key;
+        attestation.superseded_by = Pubkey::default();
+        attestation.renewed_at = 0;
+        attestation.renewal_count = 0;
+        attestation.review_hash = [0u8; 32];
+        attestation.suspended_at = 0;
+        attestation.reinstated_at = 0;
+        attestation.suspension_reason = None;
+        attestation.revocation_reason = None;
+        attestation.revocation_hash = [0u8; 32];
+        attestation.wallet_root = [0u8; 32];
+        attestation.wallet_count = wallets.len() as u32;
+        attestation.sequence = sequence;
+        attestation.subdivision = previous.subdivision;
+        attestation.schema_id = schema.id;
+        attestation.schema_version = schema.version;
+        attestation.period_kind = previous.period_kind;
+        attestation.period_start = previous.period_start;
+        attestation.period_end = previous.period_end;
+        attestation.num_wallets = wallets.len() as u8;
+        attestation.wallets = wallets.clone();
+
+        // Wallets that already consented to the previous attestation keep their
+        // acknowledgement; newly added wallets must still acknowledge.
+        attestation.consent_required = previous.consent_required;
+        attestation.activation_pending = false;
+        attestation.acknowledged = wallets
+            .iter()
+            .enumerate()
+            .filter(|(_, wallet)| previous.has_acknowledged(wallet))
+            .fold(0, |bits, (i, _)| bits | 1 << i);
+        attestation.contested_by = 0;
+        attestation.status = if attestation.ready_to_activate() {
+            AttestationStatus::Active
+        } else {
+            AttestationStatus::Pending
+        };
+
+        previous.status = AttestationStatus::Superseded;
+        previous.superseded_by = attestation_key;
+
+        ctx.accounts.state.attestation_count += 1;
+
+        let (links, rest) = wallet_accounts(ctx.remaining_accounts, wallets.len());
+        let years = ctx.accounts.previous.tax_years().len();
+        let (slots, dropped_slots) = wallet_accounts(rest, wallets.len() * years);
+        create_wallet_links(
+            links,
+            attestation_key,
+            &wallets,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?;
+        claim_slots(
+            slots,
+            &ctx.accounts.attestation,
+            &wallets,
+            previous_key,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?;
+
+        let attestation = &ctx.accounts.attestation;
+        let previous = &ctx.accounts.previous;
+
+        let dropped: Vec<Pubkey> = previous
+            .wallets
+            .iter()
+            .filter(|w| !wallets.contains(w))
+            .copied()
+            .collect();
+        release_slots(dropped_slots, previous, &dropped)?;
+
+        emit!(AttestationCreated {
+            attestation: attestation_key,
+            wallets,
+            jurisdiction: attestation.jurisdiction,
+            subdivision: attestation.subdivision,
+            attestation_type: attestation.attestation_type,
+            schema_id: attestation.schema_id,
+            schema_version: attestation.schema_version,
+            tax_year: attestation.tax_year,
+            end_tax_year: attestation.end_tax_year,
+            period_kind: attestation.period_kind,
+            period_start: attestation.period_start,
+            period_end: attestation.period_end,
+            audit_hash,
+            issued_at: attestation.issued_at,
+            expires_at,
+            status: attestation.status,
+            wallet_root: attestation.wallet_root,
+            wallet_count: attestation.wallet_count,
+        });
+
+        emit!(AttestationSuperseded {
+            previous: previous_key,
+            attestation: attestation_key,
+            previous_audit_hash: previous.audit_hash,
+            audit_hash,
+            superseded_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Extend an active attestation, or bring an expired one back after a re-review.
+    ///
+    /// `remaining_accounts` are the attestation's slots in wallet order, which take
+    /// the new expiry. Renewal fails if another attestation claimed one of them after
+    /// this one expired.
+    pub fn renew_attestation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RenewAttestation<'info>>,
+        new_expires_at: i64,
+        review_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let attestation_key = ctx.accounts.attestation.key();
+        let attestation = &mut ctx.accounts.attestation;
+        let clock = Clock::get()?;
+        let old_status = attestation.status;
+
+        require!(
+            old_status == AttestationStatus::Active || old_status == AttestationStatus::Expired,
+            AttestationError::InvalidStatusTransition
+        );
+        require!(
+            attestation.attestation_type.is_renewable(),
+            AttestationError::AttestationNotRenewable
+        );
+        require!(
+            new_expires_at > clock.unix_timestamp && new_expires_at > attestation.expires_at,
+            AttestationError::InvalidRenewalExpiry
+        );
+        ctx.accounts
+            .issuer
+            .check_rights(attestation.jurisdiction, attestation.attestation_type)?;
+
+        let old_expires_at = attestation.expires_at;
+        attestation.expires_at = new_expires_at;
+        attestation.status = AttestationStatus::Active;
+        attestation.renewed_at = clock.unix_timestamp;
+        attestation.renewal_count = attestation
+            .renewal_count
+            .checked_add(1)
+            .ok_or(AttestationError::RenewalLimitReached)?;
+        if let Some(review_hash) = review_hash {
+            require!(
+                review_hash != [0u8; 32],
+                AttestationError::InvalidAuditHash
+            );
+            attestation.review_hash = review_hash;
+        }
+        extend_slots(ctx.remaining_accounts, attestation)?;
+
+        if old_status != AttestationStatus::Active {
+            emit!(StatusUpdated {
+                attestation: attestation_key,
+                old_status,
+                new_status: AttestationStatus::Active,
+            });
+        }
+
+        emit!(AttestationRenewed {
+            attestation: attestation_key,
+            old_expires_at,
+            new_expires_at,
+            review_hash: attestation.review_hash,
+            renewal_count: attestation.renewal_count,
+            renewed_at: attestation.renewed_at,
+        });
+
+        Ok(())
+    }
+
+    /// Add wallets to a listed attestation, growing the account. On a consent-gated
+    /// attestation the new wallets must acknowledge before it is Active again.
+    ///
+    /// `remaining_accounts` are the wallet links and then the slots of the added
+    /// wallets, each in order.
+    pub fn add_wallets<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddWallets<'info>>,
+        wallets: Vec<Pubkey>,
+    ) -> Result<()> {
+        let attestation = &mut ctx.accounts.attestation;
+        ctx.accounts
+            .issuer
+            .check_rights(attestation.jurisdiction, attestation.attestation_type)?;
+        require!(!wallets.is_empty(), AttestationError::InvalidWalletCount);
+
+        let mut updated = attestation.wallets.clone();
+        updated.extend_from_slice(&wallets);
+        change_wallets(attestation, updated, wallets.clone(), Vec::new())?;
+
+        let (links, slots) = wallet_accounts(ctx.remaining_accounts, wallets.len());
+        create_wallet_links(
+            links,
+            ctx.accounts.attestation.key(),
+            &wallets,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?;
+        claim_slots(
+            slots,
+            &ctx.accounts.attestation,
+            &wallets,
+            Pubkey::default(),
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )
+    }
+
+    /// Remove wallets from a listed attestation, shrinking the account and refunding
+    /// the freed rent to the issuer
+    ///
+    /// `remaining_accounts` are the wallet links and then the slots of the removed
+    /// wallets, each in order; the links are closed and the slots freed.
+    pub fn remove_wallets<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RemoveWallets<'info>>,
+        wallets: Vec<Pubkey>,
+    ) -> Result<()> {
+        let attestation = &mut ctx.accounts.attestation;
+        ctx.accounts
+            .issuer
+            .check_rights(attestation.jurisdiction, attestation.attestation_type)?;
+        require!(!wallets.is_empty(), AttestationError::InvalidWalletCount);
+        validate_wallets(&wallets)?;
+        for wallet in &wallets {
+            require!(
+                attestation.wallets.contains(wallet),
+                AttestationError::WalletNotListed
+            );
+        }
+
+        let updated = attestation
+            .wallets
+            .iter()
+            .filter(|w| !wallets.contains(w))
+            .copied()
+            .collect();
+        change_wallets(attestation, updated, Vec::new(), wallets.clone())?;
+
+        let (links, slots) = wallet_accounts(ctx.remaining_accounts, wallets.len());
+        close_wallet_links(
+            links,
+            ctx.accounts.attestation.key(),
+            &wallets,
+            &ctx.accounts.authority,
+        )?;
+        release_slots(slots, &ctx.accounts.attestation, &wallets)
+    }
+
+    /// Update attestation status. Revoking takes the wallet links and slots as
+    /// `remaining_accounts`, as for `revoke_attestation`.
+    pub fn update_status<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateAttestation<'info>>,
         new_status: AttestationStatus,
     ) -> Result<()> {
         let attestation_key = ctx.accounts.attestation.key();
@@ -84,12 +697,33 @@ pub mod attestation {
             is_valid_status_transition(old_status, new_status),
             AttestationError::InvalidStatusTransition
         );
+        // The authority cannot activate around missing wallet consent
+        if new_status == AttestationStatus::Active && attestation.consent_required {
+            require!(
+                attestation.all_acknowledged(),
+                AttestationError::ConsentIncomplete
+            );
+        }
 
         attestation.status = new_status;
+        if new_status == AttestationStatus::Active {
+            attestation.activation_pending = false;
+        }
 
         if new_status == AttestationStatus::Revoked {
             let clock = Clock::get()?;
             attestation.revoked_at = clock.unix_timestamp;
+            attestation.revocation_reason = Some(RevocationReason::Other);
+            attestation.revocation_hash = [0u8; 32];
+            revoke_wallet_accounts(ctx.remaining_accounts, attestation)?;
+
+            emit!(AttestationRevoked {
+                attestation: attestation_key,
+                wallets: attestation.wallets.clone(),
+                reason: RevocationReason::Other,
+                reason_hash: attestation.revocation_hash,
+                revoked_at: attestation.revoked_at,
+            });
         }
 
         emit!(StatusUpdated {
@@ -101,122 +735,2907 @@ pub mod attestation {
         Ok(())
     }
 
-    /// Revoke an attestation
-    pub fn revoke_attestation(ctx: Context<UpdateAttestation>) -> Result<()> {
+    /// Revoke an attestation, recording why and optionally the supporting document.
+    ///
+    /// `remaining_accounts` are the attestation's wallet links and then its slots, each
+    /// in wallet order. The links get the revocation time and the slots are freed.
+    pub fn revoke_attestation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateAttestation<'info>>,
+        reason: RevocationReason,
+        reason_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
         let attestation_key = ctx.accounts.attestation.key();
         let attestation = &mut ctx.accounts.attestation;
         let clock = Clock::get()?;
 
         require!(
-            attestation.status == AttestationStatus::Active,
+            matches!(
+                attestation.status,
+                AttestationStatus::Pending | AttestationStatus::Active | AttestationStatus::Suspended
+            ),
             AttestationError::AttestationNotActive
         );
 
+        if let Some(reason_hash) = reason_hash {
+            require!(
+                reason_hash != [0u8; 32],
+                AttestationError::InvalidAuditHash
+            );
+        }
+
         attestation.status = AttestationStatus::Revoked;
         attestation.revoked_at = clock.unix_timestamp;
+        attestation.revocation_reason = Some(reason);
+        attestation.revocation_hash = reason_hash.unwrap_or_default();
+        revoke_wallet_accounts(ctx.remaining_accounts, attestation)?;
 
         emit!(AttestationRevoked {
             attestation: attestation_key,
             wallets: attestation.wallets.clone(),
+            reason,
+            reason_hash: attestation.revocation_hash,
             revoked_at: attestation.revoked_at,
         });
 
         Ok(())
     }
-}
 
-/// Max wallets per attestation (10 wallets * 32 bytes = 320 bytes)
-pub const MAX_WALLETS: usize = 10;
+    /// Pause an active attestation while a discrepancy is investigated
+    pub fn suspend_attestation(
+        ctx: Context<UpdateAttestation>,
+        reason: SuspensionReason,
+    ) -> Result<()> {
+        let attestation_key = ctx.accounts.attestation.key();
+        let attestation = &mut ctx.accounts.attestation;
+        let clock = Clock::get()?;
 
-fn is_valid_status_transition(from: AttestationStatus, to: AttestationStatus) -> bool {
-    matches!(
-        (from, to),
-        (AttestationStatus::Pending, AttestationStatus::Active)
-            | (AttestationStatus::Active, AttestationStatus::Expired)
-            | (AttestationStatus::Active, AttestationStatus::Revoked)
-            | (AttestationStatus::Pending, AttestationStatus::Revoked)
-    )
-}
+        require!(
+            attestation.status == AttestationStatus::Active,
+            AttestationError::AttestationNotActive
+        );
 
-// ============================================
-// Accounts
-// ============================================
+        attestation.status = AttestationStatus::Suspended;
+        attestation.suspended_at = clock.unix_timestamp;
+        attestation.suspension_reason = Some(reason);
 
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + ProgramState::INIT_SPACE,
-        seeds = [b"state"],
-        bump
-    )]
-    pub state: Account<'info, ProgramState>,
+        emit!(AttestationSuspended {
+            attestation: attestation_key,
+            reason,
+            suspended_at: attestation.suspended_at,
+        });
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+        Ok(())
+    }
 
-    pub system_program: Program<'info, System>,
-}
+    /// Restore a suspended attestation to Active, or to Expired if `expires_at` passed
+    /// while it was suspended
+    pub fn reinstate_attestation(ctx: Context<UpdateAttestation>) -> Result<()> {
+        let attestation_key = ctx.accounts.attestation.key();
+        let attestation = &mut ctx.accounts.attestation;
+        let clock = Clock::get()?;
 
-#[derive(Accounts)]
-#[instruction(
-    jurisdiction: Jurisdiction,
-    attestation_type: AttestationType,
-    tax_year: u16,
-    audit_hash: [u8; 32],
-)]
-pub struct CreateAttestation<'info> {
-    #[account(
-        mut,
-        seeds = [b"state"],
-        bump = state.bump
-    )]
-    pub state: Account<'info, ProgramState>,
+        require!(
+            attestation.status == AttestationStatus::Suspended,
+            AttestationError::AttestationNotSuspended
+        );
 
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + Attestation::INIT_SPACE,
-        seeds = [
-            b"attestation",
-            audit_hash.as_ref(),
-        ],
-        bump
-    )]
-    pub attestation: Account<'info, Attestation>,
+        attestation.reinstated_at = clock.unix_timestamp;
+        if clock.unix_timestamp >= attestation.expires_at {
+            attestation.status = AttestationStatus::Expired;
+            emit!(StatusUpdated {
+                attestation: attestation_key,
+                old_status: AttestationStatus::Suspended,
+                new_status: AttestationStatus::Expired,
+            });
+        } else {
+            attestation.status = AttestationStatus::Active;
+        }
 
-    #[account(
-        mut,
-        constraint = authority.key() == state.authority @ AttestationError::Unauthorized
-    )]
-    pub authority: Signer<'info>,
+        emit!(AttestationReinstated {
+            attestation: attestation_key,
+            suspended_at: attestation.suspended_at,
+            reinstated_at: attestation.reinstated_at,
+        });
 
-    pub system_program: Program<'info, System>,
-}
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct UpdateAttestation<'info> {
-    #[account(
-        seeds = [b"state"],
-        bump = state.bump
-    )]
-    pub state: Account<'info, ProgramState>,
+    /// Release an attestation created Pending, typically after human review. It turns
+    /// Active at once unless wallet consent is still outstanding.
+    pub fn activate_attestation(ctx: Context<ActivateAttestation>) -> Result<()> {
+        let attestation_key = ctx.accounts.attestation.key();
+        let attestation = &mut ctx.accounts.attestation;
+        let clock = Clock::get()?;
 
-    #[account(
-        mut,
+        require!(
+            attestation.status == AttestationStatus::Pending && attestation.activation_pending,
+            AttestationError::AttestationNotPending
+        );
+        require!(
+            clock.unix_timestamp < attestation.expires_at,
+            AttestationError::AttestationExpired
+        );
+        ctx.accounts
+            .issuer
+            .check_rights(attestation.jurisdiction, attestation.attestation_type)?;
+
+        attestation.activation_pending = false;
+
+        if attestation.ready_to_activate() {
+            attestation.status = AttestationStatus::Active;
+
+            emit!(StatusUpdated {
+                attestation: attestation_key,
+                old_status: AttestationStatus::Pending,
+                new_status: AttestationStatus::Active,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Record a listed wallet's consent. A pending attestation that requires consent
+    /// becomes Active once every wallet has acknowledged it.
+    pub fn acknowledge_attestation(ctx: Context<AcknowledgeAttestation>) -> Result<()> {
+        let attestation_key = ctx.accounts.attestation.key();
+        let wallet = ctx.accounts.wallet.key();
+        let attestation = &mut ctx.accounts.attestation;
+        let clock = Clock::get()?;
+
+        require!(
+            attestation.status == AttestationStatus::Pending
+                || attestation.status == AttestationStatus::Active,
+            AttestationError::InvalidStatusTransition
+        );
+        require!(
+            clock.unix_timestamp < attestation.expires_at,
+            AttestationError::AttestationExpired
+        );
+
+        let index = attestation
+            .wallets
+            .iter()
+            .position(|w| *w == wallet)
+            .ok_or(AttestationError::WalletNotListed)?;
+        require!(
+            attestation.acknowledged & (1 << index) == 0,
+            AttestationError::AlreadyAcknowledged
+        );
+        attestation.acknowledged |= 1 << index;
+
+        emit!(AttestationAcknowledged {
+            attestation: attestation_key,
+            wallet,
+            acknowledged: attestation.acknowledged,
+        });
+
+        if attestation.status == AttestationStatus::Pending && attestation.ready_to_activate() {
+            attestation.status = AttestationStatus::Active;
+
+            emit!(StatusUpdated {
+                attestation: attestation_key,
+                old_status: AttestationStatus::Pending,
+                new_status: AttestationStatus::Active,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Let a listed wallet dispute an attestation. The attestation is flagged as
+    /// contested until the issuer resolves the request, which closes it so the
+    /// wallet may dispute again.
+    pub fn request_removal(ctx: Context<RequestRemoval>, reason_hash: [u8; 32]) -> Result<()> {
+        let attestation_key = ctx.accounts.attestation.key();
+        let request_key = ctx.accounts.removal_request.key();
+        let wallet = ctx.accounts.wallet.key();
+        let attestation = &mut ctx.accounts.attestation;
+        let clock = Clock::get()?;
+
+        require!(
+            matches!(
+                attestation.status,
+                AttestationStatus::Pending | AttestationStatus::Active | AttestationStatus::Suspended
+            ),
+            AttestationError::AttestationNotActive
+        );
+        require!(reason_hash != [0u8; 32], AttestationError::InvalidAuditHash);
+
+        let index = attestation
+            .wallets
+            .iter()
+            .position(|w| *w == wallet)
+            .ok_or(AttestationError::WalletNotListed)?;
+        attestation.contested_by |= 1 << index;
+
+        let request = &mut ctx.accounts.removal_request;
+        request.bump = ctx.bumps.removal_request;
+        request.attestation = attestation_key;
+        request.wallet = wallet;
+        request.reason_hash = reason_hash;
+        request.requested_at = clock.unix_timestamp;
+
+        emit!(RemovalRequested {
+            attestation: attestation_key,
+            request: request_key,
+            wallet,
+            reason_hash,
+            requested_at: request.requested_at,
+        });
+
+        Ok(())
+    }
+
+    /// Resolve an open removal request by revoking the attestation, pointing at the
+    /// amendment that replaced it, or rejecting the request. The request is closed,
+    /// refunding its rent to the wallet; the outcome is kept in `RemovalResolved`.
+    /// Revoking takes the wallet links and slots as `remaining_accounts`, as for
+    /// `revoke_attestation`.
+    pub fn resolve_removal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveRemoval<'info>>,
+        resolution: RemovalResolution,
+        resolution_hash: [u8; 32],
+    ) -> Result<()> {
+        let attestation_key = ctx.accounts.attestation.key();
+        let request_key = ctx.accounts.removal_request.key();
+        let attestation = &mut ctx.accounts.attestation;
+        let request = &ctx.accounts.removal_request;
+        let clock = Clock::get()?;
+
+        require!(
+            resolution_hash != [0u8; 32],
+            AttestationError::InvalidAuditHash
+        );
+
+        match resolution {
+            RemovalResolution::Revoke => {
+                require!(
+                    matches!(
+                        attestation.status,
+                        AttestationStatus::Pending
+                            | AttestationStatus::Active
+                            | AttestationStatus::Suspended
+                    ),
+                    AttestationError::AttestationNotActive
+                );
+                attestation.status = AttestationStatus::Revoked;
+                attestation.revoked_at = clock.unix_timestamp;
+                attestation.revocation_reason = Some(RevocationReason::ClientRequest);
+                attestation.revocation_hash = resolution_hash;
+                revoke_wallet_accounts(ctx.remaining_accounts, attestation)?;
+
+                emit!(AttestationRevoked {
+                    attestation: attestation_key,
+                    wallets: attestation.wallets.clone(),
+                    reason: RevocationReason::ClientRequest,
+                    reason_hash: resolution_hash,
+                    revoked_at: attestation.revoked_at,
+                });
+            }
+            // The issuer amends with `supersede_attestation` first, then resolves
+            RemovalResolution::Amend => {
+                require!(
+                    attestation.status == AttestationStatus::Superseded,
+                    AttestationError::AttestationNotSuperseded
+                );
+            }
+            RemovalResolution::Reject => {}
+        }
+
+        if let Some(index) = attestation.wallets.iter().position(|w| *w == request.wallet) {
+            attestation.contested_by &= !(1 << index);
+        }
+
+        emit!(RemovalResolved {
+            attestation: attestation_key,
+            request: request_key,
+            wallet: request.wallet,
+            resolution,
+            resolution_hash,
+            resolved_at: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Mark an active attestation as expired once `expires_at` has passed (permissionless)
+    pub fn expire_attestation(ctx: Context<ExpireAttestation>) -> Result<()> {
+        let attestation_key = ctx.accounts.attestation.key();
+        let attestation = &mut ctx.accounts.attestation;
+        let clock = Clock::get()?;
+
+        require!(
+            attestation.status == AttestationStatus::Active,
+            AttestationError::AttestationNotActive
+        );
+        require!(
+            clock.unix_timestamp >= attestation.expires_at,
+            AttestationError::AttestationNotExpired
+        );
+
+        attestation.status = AttestationStatus::Expired;
+
+        emit!(StatusUpdated {
+            attestation: attestation_key,
+            old_status: AttestationStatus::Active,
+            new_status: AttestationStatus::Expired,
+        });
+
+        Ok(())
+    }
+
+    /// Expire every eligible attestation passed in `remaining_accounts` (permissionless).
+    /// Attestations that are not active or not yet due are skipped.
+    pub fn expire_attestations<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExpireAttestations>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        for info in ctx.remaining_accounts.iter() {
+            require!(info.is_writable, AttestationError::AccountNotWritable);
+
+            let mut attestation = Account::<Attestation>::try_from(info)?;
+            if attestation.status != AttestationStatus::Active
+                || clock.unix_timestamp < attestation.expires_at
+            {
+                continue;
+            }
+
+            attestation.status = AttestationStatus::Expired;
+            attestation.exit(&crate::ID)?;
+
+            emit!(StatusUpdated {
+                attestation: info.key(),
+                old_status: AttestationStatus::Active,
+                new_status: AttestationStatus::Expired,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Close a revoked or expired attestation once the retention period has passed,
+    /// refunding its rent to the issuer and leaving a tombstone behind.
+    ///
+    /// `remaining_accounts` are the attestation's wallet links and then its slots, each
+    /// in wallet order, followed by each open removal request of a listed wallet and
+    /// that wallet, in wallet order. The links are closed along with it, the slots an
+    /// expired attestation still holds are freed, and the requests are closed with
+    /// their rent refunded to the wallets.
+    pub fn close_attestation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseAttestation<'info>>,
+    ) -> Result<()> {
+        let attestation = &ctx.accounts.attestation;
+        let clock = Clock::get()?;
+
+        let ended_at = match attestation.status {
+            AttestationStatus::Revoked => attestation.revoked_at,
+            AttestationStatus::Expired => attestation.expires_at,
+            _ => return err!(AttestationError::AttestationNotClosable),
+        };
+        require!(
+            clock.unix_timestamp >= ended_at.saturating_add(ctx.accounts.state.retention_period),
+            AttestationError::RetentionPeriodActive
+        );
+
+        let tombstone = &mut ctx.accounts.tombstone;
+        tombstone.bump = ctx.bumps.tombstone;
+        tombstone.audit_hash = attestation.audit_hash;
+        tombstone.final_status = attestation.status;
+        tombstone.closed_at = clock.unix_timestamp;
+
+        let (links, rest) = wallet_accounts(ctx.remaining_accounts, attestation.wallets.len());
+        let slot_count = attestation.wallets.len() * attestation.tax_years().len();
+        let (slots, requests) = wallet_accounts(rest, slot_count);
+        close_wallet_links(
+            links,
+            attestation.key(),
+            &attestation.wallets,
+            &ctx.accounts.authority,
+        )?;
+        release_slots(slots, attestation, &attestation.wallets)?;
+        close_removal_requests(requests, attestation)?;
+
+        emit!(AttestationClosed {
+            attestation: attestation.key(),
+            audit_hash: tombstone.audit_hash,
+            final_status: tombstone.final_status,
+            closed_at: tombstone.closed_at,
+        });
+
+        Ok(())
+    }
+
+    /// Revoke an attestation created before issuer namespacing (see
+    /// `LegacyAttestation`). The account keeps its old layout; only its status and
+    /// revocation time change, so off-chain readers of that layout see the revocation.
+    pub fn revoke_legacy_attestation(
+        ctx: Context<RevokeLegacyAttestation>,
+        reason: RevocationReason,
+    ) -> Result<()> {
+        let info = ctx.accounts.attestation.to_account_info();
+        let mut legacy = LegacyAttestation::load(&info)?;
+        let clock = Clock::get()?;
+
+        require!(
+            matches!(
+                legacy.status,
+                AttestationStatus::Pending | AttestationStatus::Active
+            ),
+            AttestationError::AttestationNotActive
+        );
+
+        legacy.status = AttestationStatus::Revoked;
+        legacy.revoked_at = clock.unix_timestamp;
+        legacy.store(&info)?;
+
+        emit!(AttestationRevoked {
+            attestation: info.key(),
+            wallets: legacy.wallets,
+            reason,
+            reason_hash: [0u8; 32],
+            revoked_at: legacy.revoked_at,
+        });
+
+        Ok(())
+    }
+
+    /// Mark an active legacy attestation as expired once `expires_at` has passed
+    /// (permissionless)
+    pub fn expire_legacy_attestation(ctx: Context<ExpireLegacyAttestation>) -> Result<()> {
+        let info = ctx.accounts.attestation.to_account_info();
+        let mut legacy = LegacyAttestation::load(&info)?;
+        let clock = Clock::get()?;
+
+        require!(
+            legacy.status == AttestationStatus::Active,
+            AttestationError::AttestationNotActive
+        );
+        require!(
+            clock.unix_timestamp >= legacy.expires_at,
+            AttestationError::AttestationNotExpired
+        );
+
+        legacy.status = AttestationStatus::Expired;
+        legacy.store(&info)?;
+
+        emit!(StatusUpdated {
+            attestation: info.key(),
+            old_status: AttestationStatus::Active,
+            new_status: AttestationStatus::Expired,
+        });
+
+        Ok(())
+    }
+
+    /// Close a revoked or expired legacy attestation once the retention period has
+    /// passed, refunding its rent to the authority that created it and leaving a
+    /// tombstone behind, as `close_attestation` does.
+    pub fn close_legacy_attestation(ctx: Context<CloseLegacyAttestation>) -> Result<()> {
+        let info = ctx.accounts.attestation.to_account_info();
+        let legacy = LegacyAttestation::load(&info)?;
+        let clock = Clock::get()?;
+
+        require_keys_eq!(
+            legacy.authority,
+            ctx.accounts.authority.key(),
+            AttestationError::Unauthorized
+        );
+        let ended_at = match legacy.status {
+            AttestationStatus::Revoked => legacy.revoked_at,
+            AttestationStatus::Expired => legacy.expires_at,
+            _ => return err!(AttestationError::AttestationNotClosable),
+        };
+        require!(
+            clock.unix_timestamp >= ended_at.saturating_add(ctx.accounts.state.retention_period),
+            AttestationError::RetentionPeriodActive
+        );
+
+        let tombstone = &mut ctx.accounts.tombstone;
+        tombstone.bump = ctx.bumps.tombstone;
+        tombstone.audit_hash = legacy.audit_hash;
+        tombstone.final_status = legacy.status;
+        tombstone.closed_at = clock.unix_timestamp;
+
+        // Same steps as Anchor's `close` constraint, which needs a typed account
+        let authority = ctx.accounts.authority.to_account_info();
+        let rent = info.lamports();
+        **authority.try_borrow_mut_lamports()? = authority.lamports().saturating_add(rent);
+        **info.try_borrow_mut_lamports()? = 0;
+        info.assign(&System::id());
+        info.realloc(0, false)?;
+
+        emit!(AttestationClosed {
+            attestation: info.key(),
+            audit_hash: tombstone.audit_hash,
+            final_status: tombstone.final_status,
+            closed_at: tombstone.closed_at,
+        });
+
+        Ok(())
+    }
+
+    /// Register an issuer allowed to create attestations for the given jurisdictions and types
+    pub fn add_issuer(
+        ctx: Context<AddIssuer>,
+        issuer_authority: Pubkey,
+        jurisdictions: Vec<[u8; 2]>,
+        attestation_types: Vec<AttestationType>,
+    ) -> Result<()> {
+        validate_issuer_rights(&jurisdictions, &attestation_types)?;
+
+        let issuer_key = ctx.accounts.issuer.key();
+        let clock = Clock::get()?;
+        let issuer = &mut ctx.accounts.issuer;
+
+        issuer.bump = ctx.bumps.issuer;
+        issuer.authority = issuer_authority;
+        issuer.status = IssuerStatus::Active;
+        issuer.jurisdictions = jurisdictions.clone();
+        issuer.attestation_types = attestation_types.clone();
+        issuer.attestation_count = 0;
+        issuer.added_at = clock.unix_timestamp;
+        // Start past every sequence issued so far, so a removed and re-added issuer
+        // never derives the address of one of its earlier attestations
+        issuer.next_sequence = ctx.accounts.state.attestation_count;
+
+        emit!(IssuerAdded {
+            issuer: issuer_key,
+            authority: issuer_authority,
+            jurisdictions,
+            attestation_types,
+        });
+
+        Ok(())
+    }
+
+    /// Replace an issuer's allowed jurisdictions and attestation types
+    pub fn update_issuer(
+        ctx: Context<ManageIssuer>,
+        jurisdictions: Vec<[u8; 2]>,
+        attestation_types: Vec<AttestationType>,
+    ) -> Result<()> {
+        validate_issuer_rights(&jurisdictions, &attestation_types)?;
+
+        let issuer_key = ctx.accounts.issuer.key();
+        let issuer = &mut ctx.accounts.issuer;
+
+        issuer.jurisdictions = jurisdictions.clone();
+        issuer.attestation_types = attestation_types.clone();
+
+        emit!(IssuerUpdated {
+            issuer: issuer_key,
+            authority: issuer.authority,
+            jurisdictions,
+            attestation_types,
+        });
+
+        Ok(())
+    }
+
+    /// Suspend an issuer so it can no longer create attestations
+    pub fn suspend_issuer(ctx: Context<ManageIssuer>) -> Result<()> {
+        set_issuer_status(&mut ctx.accounts.issuer, IssuerStatus::Suspended)
+    }
+
+    /// Reactivate a suspended issuer
+    pub fn reactivate_issuer(ctx: Context<ManageIssuer>) -> Result<()> {
+        set_issuer_status(&mut ctx.accounts.issuer, IssuerStatus::Active)
+    }
+
+    /// Remove an issuer and reclaim its account rent
+    pub fn remove_issuer(ctx: Context<RemoveIssuer>) -> Result<()> {
+        emit!(IssuerRemoved {
+            issuer: ctx.accounts.issuer.key(),
+            authority: ctx.accounts.issuer.authority,
+        });
+
+        Ok(())
+    }
+
+    /// Register an ISO 3166-1 alpha-2 jurisdiction code so attestations can be
+    /// created for it
+    pub fn add_jurisdiction(ctx: Context<AddJurisdiction>, code: [u8; 2]) -> Result<()> {
+        require!(
+            is_valid_jurisdiction_code(&code),
+            AttestationError::InvalidJurisdiction
+        );
+
+        let config = &mut ctx.accounts.jurisdiction_config;
+        config.bump = ctx.bumps.jurisdiction_config;
+        config.code = code;
+        config.registered_at = Clock::get()?.unix_timestamp;
+        config.fiscal_year_start_month = 1;
+        config.fiscal_year_start_day = 1;
+        config.subdivisions = Vec::new();
+
+        emit!(JurisdictionAdded {
+            jurisdiction_config: config.key(),
+            code,
+        });
+
+        Ok(())
+    }
+
+    /// Replace the ISO 3166-2 subdivision codes attestations for a jurisdiction may
+    /// name. Codes are 1-3 uppercase letters or digits, zero-padded (`*b"CA\0"`).
+    pub fn set_jurisdiction_subdivisions(
+        ctx: Context<ManageJurisdiction>,
+        subdivisions: Vec<[u8; 3]>,
+    ) -> Result<()> {
+        require!(
+            subdivisions.len() <= MAX_SUBDIVISIONS
+                && subdivisions.iter().all(is_valid_subdivision_code)
+                && subdivisions
+                    .iter()
+                    .enumerate()
+                    .all(|(i, s)| !subdivisions[..i].contains(s)),
+            AttestationError::InvalidSubdivision
+        );
+
+        let config = &mut ctx.accounts.jurisdiction_config;
+        config.subdivisions = subdivisions.clone();
+
+        emit!(JurisdictionSubdivisionsUpdated {
+            jurisdiction_config: config.key(),
+            code: config.code,
+            subdivisions,
+        });
+
+        Ok(())
+    }
+
+    /// Set the day the jurisdiction's fiscal year starts, e.g. 6 April for the UK or
+    /// 1 July for Australia. Jurisdictions start out on the calendar year. The day
+    /// is capped at 28 so that every month has it.
+    pub fn set_fiscal_calendar(
+        ctx: Context<ManageJurisdiction>,
+        start_month: u8,
+        start_day: u8,
+    ) -> Result<()> {
+        require!(
+            (1..=12).contains(&start_month) && (1..=28).contains(&start_day),
+            AttestationError::InvalidFiscalCalendar
+        );
+
+        let config = &mut ctx.accounts.jurisdiction_config;
+        config.fiscal_year_start_month = start_month;
+        config.fiscal_year_start_day = start_day;
+
+        emit!(FiscalCalendarUpdated {
+            jurisdiction_config: config.key(),
+            code: config.code,
+            start_month,
+            start_day,
+        });
+
+        Ok(())
+    }
+
+    /// Deregister a jurisdiction and reclaim its account rent. Existing attestations
+    /// keep their code, but no new ones can be created for it.
+    pub fn remove_jurisdiction(ctx: Context<RemoveJurisdiction>) -> Result<()> {
+        emit!(JurisdictionRemoved {
+            jurisdiction_config: ctx.accounts.jurisdiction_config.key(),
+            code: ctx.accounts.jurisdiction_config.code,
+        });
+
+        Ok(())
+    }
+
+    /// Register version `version` of attestation schema `id`. `name_hash` is the
+    /// SHA-256 of its human-readable name, `required_fields` a set of
+    /// `SCHEMA_REQUIRES_*` flags and an empty `jurisdictions` allows any.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_schema(
+        ctx: Context<AddSchema>,
+        id: u32,
+        version: u16,
+        name_hash: [u8; 32],
+        attestation_type: AttestationType,
+        required_fields: u16,
+        default_validity: i64,
+        jurisdictions: Vec<[u8; 2]>,
+    ) -> Result<()> {
+        require!(
+            version > 0
+                && name_hash != [0u8; 32]
+                && required_fields & !SCHEMA_REQUIRES_ALL == 0
+                && default_validity >= 0
+                && jurisdictions.len() <= MAX_SCHEMA_JURISDICTIONS
+                && jurisdictions.iter().all(is_valid_jurisdiction_code)
+                && jurisdictions
+                    .iter()
+                    .enumerate()
+                    .all(|(i, j)| !jurisdictions[..i].contains(j)),
+            AttestationError::InvalidSchema
+        );
+
+        let schema = &mut ctx.accounts.schema;
+        schema.bump = ctx.bumps.schema;
+        schema.id = id;
+        schema.version = version;
+        schema.name_hash = name_hash;
+        schema.attestation_type = attestation_type;
+        schema.required_fields = required_fields;
+        schema.default_validity = default_validity;
+        schema.jurisdictions = jurisdictions;
+        schema.deprecated = false;
+        schema.created_at = Clock::get()?.unix_timestamp;
+
+        emit!(SchemaAdded {
+            schema: schema.key(),
+            id,
+            version,
+            name_hash,
+            attestation_type,
+        });
+
+        Ok(())
+    }
+
+    /// Deprecate a schema so no new attestations are issued under it. Existing
+    /// attestations are unaffected.
+    pub fn deprecate_schema(ctx: Context<DeprecateSchema>) -> Result<()> {
+        let schema = &mut ctx.accounts.schema;
+        require!(!schema.deprecated, AttestationError::SchemaDeprecated);
+        schema.deprecated = true;
+
+        emit!(SchemaDeprecated {
+            schema: schema.key(),
+            id: schema.id,
+            version: schema.version,
+        });
+
+        Ok(())
+    }
+
+    /// Set the inclusive range of tax years accepted by `create_attestation`
+    pub fn set_tax_year_window(
+        ctx: Context<UpdateState>,
+        min_tax_year: u16,
+        max_tax_year: u16,
+    ) -> Result<()> {
+        require!(
+            min_tax_year > 0 && min_tax_year <= max_tax_year,
+            AttestationError::InvalidTaxYearWindow
+        );
+
+        let state = &mut ctx.accounts.state;
+        state.min_tax_year = min_tax_year;
+        state.max_tax_year = max_tax_year;
+
+        emit!(TaxYearWindowUpdated {
+            min_tax_year,
+            max_tax_year,
+        });
+
+        Ok(())
+    }
+
+    /// Set how long revoked or expired attestations must be kept before they can be closed
+    pub fn set_retention_period(ctx: Context<UpdateState>, retention_period: i64) -> Result<()> {
+        require!(
+            retention_period >= 0,
+            AttestationError::InvalidRetentionPeriod
+        );
+
+        ctx.accounts.state.retention_period = retention_period;
+
+        emit!(RetentionPeriodUpdated { retention_period });
+
+        Ok(())
+    }
+
+    /// Propose a new program authority; it takes effect once accepted
+    pub fn propose_authority(
+        ctx: Context<UpdateState>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+
+        require!(
+            new_authority != Pubkey::default() && new_authority != state.authority,
+            AttestationError::InvalidAuthority
+        );
+
+        state.pending_authority = new_authority;
+
+        emit!(AuthorityTransferProposed {
+            authority: state.authority,
+            pending_authority: new_authority,
+        });
+
+        Ok(())
+    }
+
+    /// Accept a pending authority transfer (signed by the proposed authority)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        let old_authority = state.authority;
+
+        state.authority = state.pending_authority;
+        state.pending_authority = Pubkey::default();
+
+        emit!(AuthorityTransferAccepted {
+            old_authority,
+            new_authority: state.authority,
+        });
+
+        Ok(())
+    }
+
+    /// Cancel a pending authority transfer
+    pub fn cancel_authority_transfer(ctx: Context<UpdateState>) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+
+        require!(
+            state.pending_authority != Pubkey::default(),
+            AttestationError::NoPendingAuthority
+        );
+
+        let pending_authority = state.pending_authority;
+        state.pending_authority = Pubkey::default();
+
+        emit!(AuthorityTransferCancelled {
+            authority: state.authority,
+            pending_authority,
+        });
+
+        Ok(())
+    }
+
+    /// Hand the program authority over to an M-of-N multisig vault. A pending
+    /// authority transfer is cancelled, so it cannot take the program back.
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        threshold: u8,
+        signers: Vec<Pubkey>,
+    ) -> Result<()> {
+        validate_multisig_config(threshold, &signers)?;
+
+        let multisig_key = ctx.accounts.multisig.key();
+        let vault_key = ctx.accounts.vault.key();
+        let multisig = &mut ctx.accounts.multisig;
+        let state = &mut ctx.accounts.state;
+
+        multisig.threshold = threshold;
+        multisig.signers = signers.clone();
+        multisig.proposal_count = 0;
+        multisig.vault_bump = ctx.bumps.vault;
+        multisig.bump = ctx.bumps.multisig;
+
+        // From here on every privileged instruction must be signed by the vault,
+        // which only happens through `execute_transaction`.
+        state.authority = vault_key;
+
+        if state.pending_authority != Pubkey::default() {
+            emit!(AuthorityTransferCancelled {
+                authority: vault_key,
+                pending_authority: state.pending_authority,
+            });
+            state.pending_authority = Pubkey::default();
+        }
+
+        emit!(MultisigCreated {
+            multisig: multisig_key,
+            vault: vault_key,
+            threshold,
+            signers,
+        });
+
+        Ok(())
+    }
+
+    /// Replace the multisig signer set and threshold (executed via a proposal)
+    pub fn update_multisig(
+        ctx: Context<UpdateMultisig>,
+        threshold: u8,
+        signers: Vec<Pubkey>,
+    ) -> Result<()> {
+        validate_multisig_config(threshold, &signers)?;
+
+        let multisig_key = ctx.accounts.multisig.key();
+        let multisig = &mut ctx.accounts.multisig;
+
+        multisig.threshold = threshold;
+        multisig.signers = signers.clone();
+
+        emit!(MultisigUpdated {
+            multisig: multisig_key,
+            threshold,
+            signers,
+        });
+
+        Ok(())
+    }
+
+    /// Propose a program instruction to be executed with the vault as signer
+    pub fn propose_transaction(
+        ctx: Context<ProposeTransaction>,
+        accounts: Vec<ProposalAccount>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        require!(
+            accounts.len() <= MAX_PROPOSAL_ACCOUNTS && data.len() <= MAX_PROPOSAL_DATA_LEN,
+            AttestationError::ProposalTooLarge
+        );
+
+        let proposal_key = ctx.accounts.proposal.key();
+        let proposer_key = ctx.accounts.proposer.key();
+        let clock = Clock::get()?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        let multisig = &mut ctx.accounts.multisig;
+
+        proposal.bump = ctx.bumps.proposal;
+        proposal.index = multisig.proposal_count;
+        proposal.proposer = proposer_key;
+        proposal.accounts = accounts;
+        proposal.data = data;
+        proposal.approvals = vec![proposer_key];
+        proposal.executed = false;
+        proposal.created_at = clock.unix_timestamp;
+        proposal.executed_at = 0;
+
+        multisig.proposal_count += 1;
+
+        emit!(ProposalCreated {
+            proposal: proposal_key,
+            index: proposal.index,
+            proposer: proposer_key,
+        });
+
+        Ok(())
+    }
+
+    /// Approve a pending proposal. Approvals from signers removed since the proposal
+    /// was created are dropped, so they cannot fill up the approval list.
+    pub fn approve_transaction(ctx: Context<ApproveTransaction>) -> Result<()> {
+        let proposal_key = ctx.accounts.proposal.key();
+        let signer_key = ctx.accounts.signer.key();
+        let multisig = &ctx.accounts.multisig;
+        let proposal = &mut ctx.accounts.proposal;
+
+        require!(!proposal.executed, AttestationError::ProposalAlreadyExecuted);
+        require!(
+            !proposal.is_expired(Clock::get()?.unix_timestamp),
+            AttestationError::ProposalExpired
+        );
+        proposal
+            .approvals
+            .retain(|key| multisig.signers.contains(key));
+        require!(
+            !proposal.approvals.contains(&signer_key),
+            AttestationError::AlreadyApproved
+        );
+
+        proposal.approvals.push(signer_key);
+
+        emit!(ProposalApproved {
+            proposal: proposal_key,
+            signer: signer_key,
+            approvals: proposal.approvals.len() as u8,
+        });
+
+        Ok(())
+    }
+
+    /// Execute a proposal once enough current signers have approved it
+    pub fn execute_transaction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteTransaction<'info>>,
+    ) -> Result<()> {
+        let proposal_key = ctx.accounts.proposal.key();
+        let multisig = &ctx.accounts.multisig;
+        let proposal = &mut ctx.accounts.proposal;
+
+        require!(!proposal.executed, AttestationError::ProposalAlreadyExecuted);
+        require!(
+            !proposal.is_expired(Clock::get()?.unix_timestamp),
+            AttestationError::ProposalExpired
+        );
+
+        // Approvals from signers removed since the proposal was created no longer count
+        let approvals = proposal
+            .approvals
+            .iter()
+            .filter(|key| multisig.signers.contains(key))
+            .count();
+        require!(
+            approvals >= multisig.threshold as usize,
+            AttestationError::InsufficientApprovals
+        );
+
+        let remaining = ctx.remaining_accounts;
+        require!(
+            remaining.len() == proposal.accounts.len()
+                && remaining
+                    .iter()
+                    .zip(proposal.accounts.iter())
+                    .all(|(info, meta)| info.key() == meta.pubkey),
+            AttestationError::ProposalAccountMismatch
+        );
+
+        let ix = Instruction {
+            program_id: crate::ID,
+            accounts: proposal
+                .accounts
+                .iter()
+                .map(|meta| anchor_lang::solana_program::instruction::AccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: proposal.data.clone(),
+        };
+
+        proposal.executed = true;
+        proposal.executed_at = Clock::get()?.unix_timestamp;
+
+        let mut account_infos = remaining.to_vec();
+        account_infos.push(ctx.accounts.vault.to_account_info());
+        account_infos.push(ctx.accounts.attestation_program.to_account_info());
+
+        invoke_signed(
+            &ix,
+            &account_infos,
+            &[&[b"multisig_vault", &[multisig.vault_bump]]],
+        )?;
+
+        emit!(ProposalExecuted {
+            proposal: proposal_key,
+            index: proposal.index,
+            executor: ctx.accounts.executor.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Cancel an unexecuted proposal, returning its rent to the proposer. The
+    /// proposer can cancel at any time, other signers once it has expired.
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+
+        require!(!proposal.executed, AttestationError::ProposalAlreadyExecuted);
+        require!(
+            ctx.accounts.signer.key() == proposal.proposer
+                || proposal.is_expired(Clock::get()?.unix_timestamp),
+            AttestationError::ProposalNotExpired
+        );
+
+        emit!(ProposalCancelled {
+            proposal: proposal.key(),
+            index: proposal.index,
+            cancelled_by: ctx.accounts.signer.key(),
+        });
+
+        Ok(())
+    }
+}
+
+/// Max wallets per attestation (10 wallets * 32 bytes = 320 bytes)
+pub const MAX_WALLETS: usize = 10;
+
+/// Default inclusive tax-year window, adjustable via `set_tax_year_window`
+pub const DEFAULT_MIN_TAX_YEAR: u16 = 2009;
+pub const DEFAULT_MAX_TAX_YEAR: u16 = 2100;
+
+/// Default time (seconds) a revoked or expired attestation is kept before it can be
+/// closed: seven years, the longest common tax-record retention requirement
+pub const DEFAULT_RETENTION_PERIOD: i64 = 7 * 365 * 24 * 60 * 60;
+
+/// Domain separator for off-chain wallet consent messages
+pub const CONSENT_MESSAGE_PREFIX: &[u8] = b"auditswarm:consent:v1";
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Max tax years one attestation can cover: ten, the longest common statute of
+/// limitations
+pub const MAX_TAX_YEARS: u16 = 10;
+
+/// Max uniqueness slots a listed attestation holds, one per wallet and covered tax
+/// year: as many as a single-year attestation with `MAX_WALLETS` wallets, so its
+/// wallet links and slots still fit in one transaction
+pub const MAX_WALLET_SLOTS: usize = MAX_WALLETS;
+
+/// Max jurisdictions an issuer can be allowed to attest for
+pub const MAX_ISSUER_JURISDICTIONS: usize = 16;
+
+/// Max ISO 3166-2 subdivisions registered per jurisdiction (fits the US states,
+/// districts and outlying areas)
+pub const MAX_SUBDIVISIONS: usize = 64;
+
+/// Max jurisdictions a schema can be restricted to
+pub const MAX_SCHEMA_JURISDICTIONS: usize = 16;
+
+/// `AttestationSchema::required_fields` flags: the attestation must name a
+/// subdivision, require wallet consent, or list its wallets (not a Merkle root).
+/// Only listed wallets get uniqueness slots.
+pub const SCHEMA_REQUIRES_SUBDIVISION: u16 = 1 << 0;
+pub const SCHEMA_REQUIRES_WALLET_CONSENT: u16 = 1 << 1;
+pub const SCHEMA_REQUIRES_LISTED_WALLETS: u16 = 1 << 2;
+const SCHEMA_REQUIRES_ALL: u16 =
+    SCHEMA_REQUIRES_SUBDIVISION | SCHEMA_REQUIRES_WALLET_CONSENT | SCHEMA_REQUIRES_LISTED_WALLETS;
+
+/// Max members of the authority multisig
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+
+/// Max accounts referenced by a proposed instruction (fits any create_attestation
+/// with its wallet links and up to `MAX_WALLET_SLOTS` slots)
+pub const MAX_PROPOSAL_ACCOUNTS: usize = 28;
+
+/// Max instruction data stored in a proposal (fits a 10-wallet create_attestation)
+pub const MAX_PROPOSAL_DATA_LEN: usize = 512;
+
+/// Time (seconds) a proposal stays open for approval and execution: 14 days
+pub const PROPOSAL_LIFETIME: i64 = 14 * 24 * 60 * 60;
+
+fn is_valid_status_transition(from: AttestationStatus, to: AttestationStatus) -> bool {
+    matches!(
+        (from, to),
+        (AttestationStatus::Pending, AttestationStatus::Active)
+            | (AttestationStatus::Active, AttestationStatus::Expired)
+            | (AttestationStatus::Active, AttestationStatus::Revoked)
+            | (AttestationStatus::Pending, AttestationStatus::Revoked)
+            | (AttestationStatus::Suspended, AttestationStatus::Revoked)
+    )
+}
+
+fn validate_attestation_args(
+    state: &ProgramState,
+    tax_year: u16,
+    end_tax_year: u16,
+    audit_hash: &[u8; 32],
+    expires_at: i64,
+    wallets: &[Pubkey],
+    now: i64,
+) -> Result<()> {
+    require!(
+        !wallets.is_empty() && wallets.len() <= MAX_WALLETS,
+        AttestationError::InvalidWalletCount
+    );
+    validate_wallets(wallets)?;
+    validate_attestation_terms(state, tax_year, end_tax_year, audit_hash, expires_at, now)?;
+    check_slot_count(wallets.len(), tax_year, end_tax_year)
+}
+
+/// `wallets` listed for tax years `tax_year` through `end_tax_year` must not need
+/// more than `MAX_WALLET_SLOTS` slots
+fn check_slot_count(wallets: usize, tax_year: u16, end_tax_year: u16) -> Result<()> {
+    let years = usize::from(end_tax_year - tax_year) + 1;
+    require!(
+        wallets * years <= MAX_WALLET_SLOTS,
+        AttestationError::TooManySlots
+    );
+    Ok(())
+}
+
+fn validate_attestation_terms(
+    state: &ProgramState,
+    tax_year: u16,
+    end_tax_year: u16,
+    audit_hash: &[u8; 32],
+    expires_at: i64,
+    now: i64,
+) -> Result<()> {
+    require!(
+        *audit_hash != [0u8; 32],
+        AttestationError::InvalidAuditHash
+    );
+    require!(expires_at > now, AttestationError::AttestationExpired);
+    require!(
+        (state.min_tax_year..=state.max_tax_year).contains(&tax_year)
+            && (state.min_tax_year..=state.max_tax_year).contains(&end_tax_year),
+        AttestationError::InvalidTaxYear
+    );
+    require!(
+        tax_year <= end_tax_year && end_tax_year - tax_year < MAX_TAX_YEARS,
+        AttestationError::InvalidTaxYearRange
+    );
+    Ok(())
+}
+
+/// Replace the wallet list of a listed attestation, carrying acknowledgement and
+/// contest bits over by wallet, and move a consent-gated attestation between
+/// Pending and Active as the wallet set requires
+fn change_wallets(
+    attestation: &mut Account<Attestation>,
+    wallets: Vec<Pubkey>,
+    added: Vec<Pubkey>,
+    removed: Vec<Pubkey>,
+) -> Result<()> {
+    require!(
+        !attestation.is_merkle(),
+        AttestationError::MerkleWalletsImmutable
+    );
+    require!(
+        matches!(
+            attestation.status,
+            AttestationStatus::Pending | AttestationStatus::Active | AttestationStatus::Suspended
+        ),
+        AttestationError::AttestationNotActive
+    );
+    require!(
+        !wallets.is_empty() && wallets.len() <= MAX_WALLETS,
+        AttestationError::InvalidWalletCount
+    );
+    validate_wallets(&wallets)?;
+    check_slot_count(wallets.len(), attestation.tax_year, attestation.end_tax_year)?;
+
+    let carry = |bits: u16| {
+        wallets.iter().enumerate().fold(0u16, |acc, (i, wallet)| {
+            match attestation.wallets.iter().position(|w| w == wallet) {
+                Some(old) if bits & (1 << old) != 0 => acc | 1 << i,
+                _ => acc,
+            }
+        })
+    };
+    let acknowledged = carry(attestation.acknowledged);
+    let contested_by = carry(attestation.contested_by);
+
+    attestation.acknowledged = acknowledged;
+    attestation.contested_by = contested_by;
+    attestation.num_wallets = wallets.len() as u8;
+    attestation.wallet_count = wallets.len() as u32;
+    attestation.wallets = wallets;
+
+    let old_status = attestation.status;
+    let ready = attestation.ready_to_activate();
+    attestation.status = match old_status {
+        AttestationStatus::Active if !ready => AttestationStatus::Pending,
+        AttestationStatus::Pending if ready => AttestationStatus::Active,
+        status => status,
+    };
+
+    emit!(WalletsChanged {
+        attestation: attestation.key(),
+        added,
+        removed,
+        num_wallets: attestation.num_wallets,
+    });
+
+    if attestation.status != old_status {
+        emit!(StatusUpdated {
+            attestation: attestation.key(),
+            old_status,
+            new_status: attestation.status,
+        });
+    }
+
+    Ok(())
+}
+
+/// Split `remaining_accounts` after the accounts of `count` wallets
+fn wallet_accounts<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+    count: usize,
+) -> (&'a [AccountInfo<'info>], &'a [AccountInfo<'info>]) {
+    accounts.split_at(count.min(accounts.len()))
+}
+
+/// Create a program-owned PDA with the same steps as Anchor's `init`, so a
+/// pre-funded address cannot block creation
+fn create_pda_account<'info>(
+    info: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    space: usize,
+    payer: &Signer<'info>,
+    system: &Program<'info, System>,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let system = system.to_account_info();
+
+    if info.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system,
+                system_program::CreateAccount {
+                    from: payer.to_account_info(),
+                    to: info.clone(),
+                },
+                &[seeds],
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let top_up = rent.saturating_sub(info.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system.clone(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: info.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system.clone(),
+            system_program::Allocate {
+                account_to_allocate: info.clone(),
+            },
+            &[seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system,
+            system_program::Assign {
+                account_to_assign: info.clone(),
+            },
+            &[seeds],
+        ),
+        &crate::ID,
+    )
+}
+
+/// Create a `WalletAttestationLink` for each of `wallets` from the matching entry of
+/// `links`, paid for by `payer`
+fn create_wallet_links<'info>(
+    links: &[AccountInfo<'info>],
+    attestation: Pubkey,
+    wallets: &[Pubkey],
+    payer: &Signer<'info>,
+    system: &Program<'info, System>,
+) -> Result<()> {
+    require!(
+        links.len() == wallets.len(),
+        AttestationError::WalletLinkMismatch
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+
+    for (info, wallet) in links.iter().zip(wallets) {
+        let (address, bump) = Pubkey::find_program_address(
+            &[b"wallet_link", wallet.as_ref(), attestation.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(info.key(), address, AttestationError::WalletLinkMismatch);
+
+        create_pda_account(
+            info,
+            &[b"wallet_link", wallet.as_ref(), attestation.as_ref(), &[bump]],
+            8 + WalletAttestationLink::INIT_SPACE,
+            payer,
+            system,
+        )?;
+
+        let link = WalletAttestationLink {
+            bump,
+            wallet: *wallet,
+            attestation,
+            linked_at: now,
+            revoked_at: 0,
+        };
+        link.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    }
+
+    Ok(())
+}
+
+/// Revocation bookkeeping for the listed wallets: stamp their links and free their
+/// slots, passed as for `revoke_attestation`
+fn revoke_wallet_accounts<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    attestation: &Account<Attestation>,
+) -> Result<()> {
+    let (links, slots) = wallet_accounts(accounts, attestation.wallets.len());
+    revoke_wallet_links(links, attestation, attestation.revoked_at)?;
+    release_slots(slots, attestation, &attestation.wallets)
+}
+
+/// Record the revocation time on every wallet link of `attestation`
+fn revoke_wallet_links<'info>(
+    links: &'info [AccountInfo<'info>],
+    attestation: &Account<Attestation>,
+    revoked_at: i64,
+) -> Result<()> {
+    require!(
+        links.len() == attestation.wallets.len(),
+        AttestationError::WalletLinkMismatch
+    );
+
+    for (info, wallet) in links.iter().zip(&attestation.wallets) {
+        let mut link = load_wallet_link(info, wallet, attestation.key())?;
+        link.revoked_at = revoked_at;
+        link.exit(&crate::ID)?;
+    }
+
+    Ok(())
+}
+
+/// Close the wallet links of `wallets`, refunding their rent to `destination`
+fn close_wallet_links<'info>(
+    links: &'info [AccountInfo<'info>],
+    attestation: Pubkey,
+    wallets: &[Pubkey],
+    destination: &Signer<'info>,
+) -> Result<()> {
+    require!(
+        links.len() == wallets.len(),
+        AttestationError::WalletLinkMismatch
+    );
+
+    for (info, wallet) in links.iter().zip(wallets) {
+        load_wallet_link(info, wallet, attestation)?.close(destination.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+/// Close the open removal requests of `attestation`'s contested wallets, passed in
+/// wallet order as (request, wallet) pairs, refunding each request's rent to its wallet
+fn close_removal_requests<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    attestation: &Account<Attestation>,
+) -> Result<()> {
+    let contested: Vec<&Pubkey> = attestation
+        .wallets
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| attestation.contested_by & (1 << i) != 0)
+        .map(|(_, wallet)| wallet)
+        .collect();
+    require!(
+        accounts.len() == contested.len() * 2,
+        AttestationError::RemovalRequestMismatch
+    );
+
+    for (pair, wallet) in accounts.chunks_exact(2).zip(contested) {
+        let (info, wallet_info) = (&pair[0], &pair[1]);
+        require!(
+            info.is_writable && wallet_info.is_writable,
+            AttestationError::AccountNotWritable
+        );
+        let request = Account::<RemovalRequest>::try_from(info)?;
+        require!(
+            request.attestation == attestation.key()
+                && request.wallet == *wallet
+                && wallet_info.key() == *wallet,
+            AttestationError::RemovalRequestMismatch
+        );
+        request.close(wallet_info.clone())?;
+    }
+
+    Ok(())
+}
+
+fn load_wallet_link<'info>(
+    info: &'info AccountInfo<'info>,
+    wallet: &Pubkey,
+    attestation: Pubkey,
+) -> Result<Account<'info, WalletAttestationLink>> {
+    require!(info.is_writable, AttestationError::AccountNotWritable);
+    let link = Account::<WalletAttestationLink>::try_from(info)?;
+    require!(
+        link.wallet == *wallet && link.attestation == attestation,
+        AttestationError::WalletLinkMismatch
+    );
+    Ok(link)
+}
+
+/// Claim the uniqueness slots of each of `wallets` for `attestation`, one per
+/// covered tax year and passed wallet by wallet, creating them on first use. A slot
+/// held by another attestation is only handed over from `predecessor`, the
+/// attestation being superseded, or once the holder is past its `expires_at`.
+fn claim_slots<'info>(
+    slots: &'info [AccountInfo<'info>],
+    attestation: &Account<Attestation>,
+    wallets: &[Pubkey],
+    predecessor: Pubkey,
+    payer: &Signer<'info>,
+    system: &Program<'info, System>,
+) -> Result<()> {
+    let years = attestation.tax_years().len();
+    require!(
+        slots.len() == wallets.len() * years,
+        AttestationError::SlotMismatch
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let jurisdiction = attestation.jurisdiction;
+    let subdivision = attestation.subdivision;
+    let attestation_type = [attestation.attestation_type as u8];
+    let period = attestation.slot_period().to_le_bytes();
+
+    let slot_years = wallets
+        .iter()
+        .flat_map(|wallet| attestation.tax_years().map(move |year| (wallet, year)));
+    for (info, (wallet, year)) in slots.iter().zip(slot_years) {
+        let tax_year = year.to_le_bytes();
+        let (address, bump) = Pubkey::find_program_address(
+            &[
+                b"slot",
+                wallet.as_ref(),
+                &jurisdiction,
+                &subdivision,
+                &tax_year,
+                &attestation_type,
+                &period,
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(info.key(), address, AttestationError::SlotMismatch);
+
+        if info.data_is_empty() {
+            create_pda_account(
+                info,
+                &[
+                    b"slot",
+                    wallet.as_ref(),
+                    &jurisdiction,
+                    &subdivision,
+                    &tax_year,
+                    &attestation_type,
+                    &period,
+                    &[bump],
+                ],
+                8 + AttestationSlot::INIT_SPACE,
+                payer,
+                system,
+            )?;
+
+            let slot = AttestationSlot {
+                bump,
+                wallet: *wallet,
+                jurisdiction: attestation.jurisdiction,
+                subdivision: attestation.subdivision,
+                attestation_type: attestation.attestation_type,
+                tax_year: year,
+                period_start: attestation.slot_period(),
+                attestation: attestation.key(),
+                claimed_at: now,
+                expires_at: attestation.expires_at,
+            };
+            slot.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+            continue;
+        }
+
+        let mut slot = Account::<AttestationSlot>::try_from(info)?;
+        require!(
+            slot.attestation == Pubkey::default()
+                || slot.attestation == predecessor
+                || now >= slot.expires_at,
+            AttestationError::SlotOccupied
+        );
+        slot.attestation = attestation.key();
+        slot.claimed_at = now;
+        slot.expires_at = attestation.expires_at;
+        slot.exit(&crate::ID)?;
+    }
+
+    Ok(())
+}
+
+/// Free the slots of `wallets` that `attestation` still holds, passed as for
+/// `claim_slots`
+fn release_slots<'info>(
+    slots: &'info [AccountInfo<'info>],
+    attestation: &Account<Attestation>,
+    wallets: &[Pubkey],
+) -> Result<()> {
+    require!(
+        slots.len() == wallets.len() * attestation.tax_years().len(),
+        AttestationError::SlotMismatch
+    );
+
+    let slot_years = wallets
+        .iter()
+        .flat_map(|wallet| attestation.tax_years().map(move |year| (wallet, year)));
+    for (info, (wallet, year)) in slots.iter().zip(slot_years) {
+        let mut slot = load_slot(info, attestation, wallet, year)?;
+        if slot.attestation == attestation.key() {
+            slot.attestation = Pubkey::default();
+            slot.exit(&crate::ID)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Carry the new `expires_at` of `attestation` to every slot of its listed wallets,
+/// passed as for `claim_slots`. Fails if a slot was claimed by another attestation
+/// while this one was past its expiry.
+fn extend_slots<'info>(
+    slots: &'info [AccountInfo<'info>],
+    attestation: &Account<Attestation>,
+) -> Result<()> {
+    require!(
+        slots.len() == attestation.wallets.len() * attestation.tax_years().len(),
+        AttestationError::SlotMismatch
+    );
+
+    let slot_years = attestation
+        .wallets
+        .iter()
+        .flat_map(|wallet| attestation.tax_years().map(move |year| (wallet, year)));
+    for (info, (wallet, year)) in slots.iter().zip(slot_years) {
+        let mut slot = load_slot(info, attestation, wallet, year)?;
+        require_keys_eq!(
+            slot.attestation,
+            attestation.key(),
+            AttestationError::SlotOccupied
+        );
+        slot.expires_at = attestation.expires_at;
+        slot.exit(&crate::ID)?;
+    }
+
+    Ok(())
+}
+
+fn load_slot<'info>(
+    info: &'info AccountInfo<'info>,
+    attestation: &Account<Attestation>,
+    wallet: &Pubkey,
+    year: u16,
+) -> Result<Account<'info, AttestationSlot>> {
+    require!(info.is_writable, AttestationError::AccountNotWritable);
+    let slot = Account::<AttestationSlot>::try_from(info)?;
+    require!(
+        slot.wallet == *wallet
+            && slot.jurisdiction == attestation.jurisdiction
+            && slot.subdivision == attestation.subdivision
+            && slot.attestation_type == attestation.attestation_type
+            && slot.tax_year == year
+            && slot.period_start == attestation.slot_period(),
+        AttestationError::SlotMismatch
+    );
+    Ok(slot)
+}
+
+/// Consent message a wallet signs off-chain:
+/// prefix || wallet (32) || attestation (32) || audit_hash (32) || attestation_type (1)
+/// || jurisdiction (2) || tax_year (2, LE) || end_tax_year (2, LE)
+///
+/// `attestation` is the PDA being created, which pins the issuer authority and
+/// sequence, so a signed consent cannot be replayed for another attestation.
+pub fn consent_message(
+    wallet: &Pubkey,
+    attestation: &Pubkey,
+    audit_hash: &[u8; 32],
+    attestation_type: AttestationType,
+    jurisdiction: [u8; 2],
+    tax_year: u16,
+    end_tax_year: u16,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(CONSENT_MESSAGE_PREFIX.len() + 103);
+    message.extend_from_slice(CONSENT_MESSAGE_PREFIX);
+    message.extend_from_slice(wallet.as_ref());
+    message.extend_from_slice(attestation.as_ref());
+    message.extend_from_slice(audit_hash);
+    message.push(attestation_type as u8);
+    message.extend_from_slice(&jurisdiction);
+    message.extend_from_slice(&tax_year.to_le_bytes());
+    message.extend_from_slice(&end_tax_year.to_le_bytes());
+    message
+}
+
+/// Bitmap over the wallets of `attestation` of the consent messages verified by
+/// Ed25519SigVerify instructions that precede the current one in this transaction
+fn verified_consents(
+    instructions: &AccountInfo,
+    attestation: &Account<Attestation>,
+) -> Result<u16> {
+    let wallets = &attestation.wallets;
+    let current = instructions_sysvar::load_current_index_checked(instructions)?;
+    let mut acknowledged = 0u16;
+
+    for index in 0..current {
+        let ix = instructions_sysvar::load_instruction_at_checked(index as usize, instructions)?;
+        if ix.program_id != ed25519_program::ID {
+            continue;
+        }
+
+        let data = &ix.data;
+        let count = data.first().copied().unwrap_or(0) as usize;
+        for entry in 0..count {
+            let Some(offsets) = data.get(2 + entry * 14..2 + (entry + 1) * 14) else {
+                break;
+            };
+            let read = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+
+            // Only trust entries whose key and message live in the verify instruction
+            // itself; the precompile has then checked exactly the bytes read here.
+            if read(2) != u16::MAX || read(6) != u16::MAX || read(12) != u16::MAX {
+                continue;
+            }
+            let (key_at, message_at, message_len) =
+                (read(4) as usize, read(8) as usize, read(10) as usize);
+            let (Some(key), Some(message)) = (
+                data.get(key_at..key_at + 32),
+                data.get(message_at..message_at + message_len),
+            ) else {
+                continue;
+            };
+
+            if let Some(i) = wallets.iter().position(|w| w.as_ref() == key) {
+                let expected = consent_message(
+                    &wallets[i],
+                    &attestation.key(),
+                    &attestation.audit_hash,
+                    attestation.attestation_type,
+                    attestation.jurisdiction,
+                    attestation.tax_year,
+                    attestation.end_tax_year,
+                );
+                if message == expected {
+                    acknowledged |= 1 << i;
+                }
+            }
+        }
+    }
+
+    Ok(acknowledged)
+}
+
+/// Wallets must be real keys and listed at most once
+fn validate_wallets(wallets: &[Pubkey]) -> Result<()> {
+    for (i, wallet) in wallets.iter().enumerate() {
+        require!(
+            *wallet != Pubkey::default(),
+            AttestationError::InvalidWallet
+        );
+        require!(
+            !wallets[..i].contains(wallet),
+            AttestationError::DuplicateWallet
+        );
+    }
+    Ok(())
+}
+
+fn validate_issuer_rights(
+    jurisdictions: &[[u8; 2]],
+    attestation_types: &[AttestationType],
+) -> Result<()> {
+    require!(
+        jurisdictions.iter().all(is_valid_jurisdiction_code),
+        AttestationError::InvalidJurisdiction
+    );
+    require!(
+        !jurisdictions.is_empty()
+            && jurisdictions.len() <= MAX_ISSUER_JURISDICTIONS
+            && !attestation_types.is_empty()
+            && jurisdictions
+                .iter()
+                .enumerate()
+                .all(|(i, j)| !jurisdictions[..i].contains(j))
+            && attestation_types
+                .iter()
+                .enumerate()
+                .all(|(i, t)| !attestation_types[..i].contains(t)),
+        AttestationError::InvalidIssuerRights
+    );
+    Ok(())
+}
+
+/// ISO 3166-1 alpha-2 codes are two uppercase ASCII letters. This is only a shape
+/// check: which codes are in use is up to the registry, which also holds `EU`, the
+/// code ISO exceptionally reserves for the European Union.
+fn is_valid_jurisdiction_code(code: &[u8; 2]) -> bool {
+    code.iter().all(u8::is_ascii_uppercase)
+}
+
+/// ISO 3166-2 subdivision codes are 1-3 uppercase letters or digits, stored
+/// left-aligned and zero-padded
+fn is_valid_subdivision_code(code: &[u8; 3]) -> bool {
+    let len = code.iter().position(|b| *b == 0).unwrap_or(code.len());
+    len > 0
+        && code[..len]
+            .iter()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+        && code[len..].iter().all(|b| *b == 0)
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date (Hinnant's `days_from_civil`)
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Inverse of `days_from_civil`: (year, month, day)
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn set_issuer_status(issuer: &mut Account<Issuer>, new_status: IssuerStatus) -> Result<()> {
+    let old_status = issuer.status;
+    require!(
+        old_status != new_status,
+        AttestationError::InvalidStatusTransition
+    );
+
+    issuer.status = new_status;
+
+    emit!(IssuerStatusChanged {
+        issuer: issuer.key(),
+        authority: issuer.authority,
+        old_status,
+        new_status,
+    });
+
+    Ok(())
+}
+
+fn validate_multisig_config(threshold: u8, signers: &[Pubkey]) -> Result<()> {
+    require!(
+        !signers.is_empty() && signers.len() <= MAX_MULTISIG_SIGNERS,
+        AttestationError::InvalidSignerCount
+    );
+    require!(
+        threshold > 0 && threshold as usize <= signers.len(),
+        AttestationError::InvalidThreshold
+    );
+    for (i, signer) in signers.iter().enumerate() {
+        require!(
+            !signers[..i].contains(signer),
+            AttestationError::DuplicateSigner
+        );
+    }
+    Ok(())
+}
+
+// ============================================
+// Accounts
+// ============================================
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ProgramState::INIT_SPACE,
+        seeds = [b"state"],
+        bump
+    )]
+    pub state: Account<'info, ProgramState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key())
+    )]
+    pub program: Program<'info, crate::program::Attestation>,
+
+    // Without this check whoever front-runs the deployment owns the program
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key())
+            @ AttestationError::NotUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    jurisdiction: [u8; 2],
+    subdivision: [u8; 3],
+    attestation_type: AttestationType,
+    tax_year: u16,
+    end_tax_year: u16,
+    period_kind: PeriodKind,
+    period_start: i64,
+    period_end: i64,
+    audit_hash: [u8; 32],
+    expires_at: i64,
+    wallets: Vec<Pubkey>,
+)]
+pub struct CreateAttestation<'info> {
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Attestation::space(wallets.len()),
+        seeds = [
+            b"attestation",
+            authority.key().as_ref(),
+            issuer.next_sequence.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"issuer",
+            authority.key().as_ref(),
+        ],
+        bump = issuer.bump,
+        constraint = issuer.status == IssuerStatus::Active @ AttestationError::IssuerSuspended
+    )]
+    pub issuer: Account<'info, Issuer>,
+
+    /// CHECK: owned by this program only once `add_jurisdiction` has registered the code
+    #[account(
+        seeds = [b"jurisdiction", jurisdiction.as_ref()],
+        bump,
+        constraint = jurisdiction_config.owner == &crate::ID @ AttestationError::InvalidJurisdiction
+    )]
+    pub jurisdiction_config: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            b"schema",
+            schema.id.to_le_bytes().as_ref(),
+            schema.version.to_le_bytes().as_ref(),
+        ],
+        bump = schema.bump,
+        constraint = !schema.deprecated @ AttestationError::SchemaDeprecated,
+        constraint = schema.attestation_type == attestation_type
+            @ AttestationError::SchemaTypeMismatch
+    )]
+    pub schema: Account<'info, AttestationSchema>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: the instructions sysvar, read for Ed25519SigVerify consent signatures
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(jurisdiction: [u8; 2], subdivision: [u8; 3], attestation_type: AttestationType)]
+pub struct CreateMerkleAttestation<'info> {
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Attestation::space(0),
+        seeds = [
+            b"attestation",
+            authority.key().as_ref(),
+            issuer.next_sequence.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"issuer",
+            authority.key().as_ref(),
+        ],
+        bump = issuer.bump,
+        constraint = issuer.status == IssuerStatus::Active @ AttestationError::IssuerSuspended
+    )]
+    pub issuer: Account<'info, Issuer>,
+
+    /// CHECK: owned by this program only once `add_jurisdiction` has registered the code
+    #[account(
+        seeds = [b"jurisdiction", jurisdiction.as_ref()],
+        bump,
+        constraint = jurisdiction_config.owner == &crate::ID @ AttestationError::InvalidJurisdiction
+    )]
+    pub jurisdiction_config: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            b"schema",
+            schema.id.to_le_bytes().as_ref(),
+            schema.version.to_le_bytes().as_ref(),
+        ],
+        bump = schema.bump,
+        constraint = !schema.deprecated @ AttestationError::SchemaDeprecated,
+        constraint = schema.attestation_type == attestation_type
+            @ AttestationError::SchemaTypeMismatch
+    )]
+    pub schema: Account<'info, AttestationSchema>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(audit_hash: [u8; 32], expires_at: i64, wallets: Vec<Pubkey>)]
+pub struct SupersedeAttestation<'info> {
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"attestation",
+            previous.authority.as_ref(),
+            previous.sequence.to_le_bytes().as_ref(),
+        ],
+        bump = previous.bump
+    )]
+    pub previous: Account<'info, Attestation>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Attestation::space(wallets.len()),
+        seeds = [
+            b"attestation",
+            authority.key().as_ref(),
+            issuer.next_sequence.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"issuer",
+            authority.key().as_ref(),
+        ],
+        bump = issuer.bump,
+        constraint = issuer.status == IssuerStatus::Active @ AttestationError::IssuerSuspended
+    )]
+    pub issuer: Account<'info, Issuer>,
+
+    /// CHECK: owned by this program only while the jurisdiction is registered
+    #[account(
+        seeds = [b"jurisdiction", previous.jurisdiction.as_ref()],
+        bump,
+        constraint = jurisdiction_config.owner == &crate::ID @ AttestationError::InvalidJurisdiction
+    )]
+    pub jurisdiction_config: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            b"schema",
+            schema.id.to_le_bytes().as_ref(),
+            schema.version.to_le_bytes().as_ref(),
+        ],
+        bump = schema.bump,
+        constraint = !schema.deprecated @ AttestationError::SchemaDeprecated,
+        constraint = schema.attestation_type == previous.attestation_type
+            @ AttestationError::SchemaTypeMismatch
+    )]
+    pub schema: Account<'info, AttestationSchema>,
+
+    // Only the original issuer may amend its attestation
+    #[account(
+        mut,
+        constraint = previous.authority == authority.key() @ AttestationError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RenewAttestation<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"attestation",
+            attestation.authority.as_ref(),
+            attestation.sequence.to_le_bytes().as_ref(),
+        ],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(
+        seeds = [
+            b"issuer",
+            authority.key().as_ref(),
+        ],
+        bump = issuer.bump,
+        constraint = issuer.status == IssuerStatus::Active @ AttestationError::IssuerSuspended
+    )]
+    pub issuer: Account<'info, Issuer>,
+
+    #[account(
+        constraint = attestation.authority == authority.key() @ AttestationError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallets: Vec<Pubkey>)]
+pub struct AddWallets<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"attestation",
+            attestation.authority.as_ref(),
+            attestation.sequence.to_le_bytes().as_ref(),
+        ],
+        bump = attestation.bump,
+        realloc = Attestation::space(attestation.wallets.len() + wallets.len()),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(
+        seeds = [
+            b"issuer",
+            authority.key().as_ref(),
+        ],
+        bump = issuer.bump,
+        constraint = issuer.status == IssuerStatus::Active @ AttestationError::IssuerSuspended
+    )]
+    pub issuer: Account<'info, Issuer>,
+
+    #[account(
+        mut,
+        constraint = attestation.authority == authority.key() @ AttestationError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallets: Vec<Pubkey>)]
+pub struct RemoveWallets<'info> {
+    // Shrinking refunds the freed rent to the issuer
+    #[account(
+        mut,
+        seeds = [
+            b"attestation",
+            attestation.authority.as_ref(),
+            attestation.sequence.to_le_bytes().as_ref(),
+        ],
+        bump = attestation.bump,
+        realloc = Attestation::space(attestation.wallets.len().saturating_sub(wallets.len())),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(
+        seeds = [
+            b"issuer",
+            authority.key().as_ref(),
+        ],
+        bump = issuer.bump,
+        constraint = issuer.status == IssuerStatus::Active @ AttestationError::IssuerSuspended
+    )]
+    pub issuer: Account<'info, Issuer>,
+
+    #[account(
+        mut,
+        constraint = attestation.authority == authority.key() @ AttestationError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAttestation<'info> {
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"attestation",
+            attestation.authority.as_ref(),
+            attestation.sequence.to_le_bytes().as_ref(),
+        ],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(
+        constraint = authority.key() == state.authority @ AttestationError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyWalletInclusion<'info> {
+    #[account(
+        seeds = [
+            b"attestation",
+            attestation.authority.as_ref(),
+            attestation.sequence.to_le_bytes().as_ref(),
+        ],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+}
+
+#[derive(Accounts)]
+pub struct CoversTaxYear<'info> {
+    #[account(
+        seeds = [
+            b"attestation",
+            attestation.authority.as_ref(),
+            attestation.sequence.to_le_bytes().as_ref(),
+        ],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+}
+
+#[derive(Accounts)]
+pub struct ActivateAttestation<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"attestation",
+            attestation.authority.as_ref(),
+            attestation.sequence.to_le_bytes().as_ref(),
+        ],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(
+        seeds = [
+            b"issuer",
+            authority.key().as_ref(),
+        ],
+        bump = issuer.bump,
+        constraint = issuer.status == IssuerStatus::Active @ AttestationError::IssuerSuspended
+    )]
+    pub issuer: Account<'info, Issuer>,
+
+    #[account(
+        constraint = attestation.authority == authority.key() @ AttestationError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcknowledgeAttestation<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"attestation",
+            attestation.authority.as_ref(),
+            attestation.sequence.to_le_bytes().as_ref(),
+        ],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    pub wallet: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RequestRemoval<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"attestation",
+            attestation.authority.as_ref(),
+            attestation.sequence.to_le_bytes().as_ref(),
+        ],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(
+        init,
+        payer = wallet,
+        space = 8 + RemovalRequest::INIT_SPACE,
+        seeds = [
+            b"removal",
+            attestation.key().as_ref(),
+            wallet.key().as_ref(),
+        ],
+        bump
+    )]
+    pub removal_request: Account<'info, RemovalRequest>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveRemoval<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"attestation",
+            attestation.authority.as_ref(),
+            attestation.sequence.to_le_bytes().as_ref(),
+        ],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(
+        mut,
+        close = wallet,
+        seeds = [
+            b"removal",
+            attestation.key().as_ref(),
+            removal_request.wallet.as_ref(),
+        ],
+        bump = removal_request.bump
+    )]
+    pub removal_request: Account<'info, RemovalRequest>,
+
+    /// CHECK: the disputing wallet, refunded the request's rent
+    #[account(mut, address = removal_request.wallet)]
+    pub wallet: UncheckedAccount<'info>,
+
+    // Removal requests are answered by the issuer that made the claim
+    #[account(
+        constraint = attestation.authority == authority.key() @ AttestationError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireAttestation<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"attestation",
+            attestation.authority.as_ref(),
+            attestation.sequence.to_le_bytes().as_ref(),
+        ],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireAttestations {}
+
+#[derive(Accounts)]
+pub struct CloseAttestation<'info> {
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            b"attestation",
+            attestation.authority.as_ref(),
+            attestation.sequence.to_le_bytes().as_ref(),
+        ],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Tombstone::INIT_SPACE,
+        seeds = [
+            b"tombstone",
+            attestation.key().as_ref(),
+        ],
+        bump
+    )]
+    pub tombstone: Account<'info, Tombstone>,
+
+    // Rent goes back to the issuer that paid for the attestation
+    #[account(
+        mut,
+        constraint = attestation.authority == authority.key() @ AttestationError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeLegacyAttestation<'info> {
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+
+    /// CHECK: old `Attestation` layout, checked by `LegacyAttestation::load`
+    #[account(mut)]
+    pub attestation: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = authority.key() == state.authority @ AttestationError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireLegacyAttestation<'info> {
+    /// CHECK: old `Attestation` layout, checked by `LegacyAttestation::load`
+    #[account(mut)]
+    pub attestation: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseLegacyAttestation<'info> {
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+
+    /// CHECK: old `Attestation` layout, checked by `LegacyAttestation::load`
+    #[account(mut)]
+    pub attestation: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Tombstone::INIT_SPACE,
+        seeds = [
+            b"tombstone",
+            attestation.key().as_ref(),
+        ],
+        bump
+    )]
+    pub tombstone: Account<'info, Tombstone>,
+
+    // Must be the attestation's authority, checked by the handler
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(issuer_authority: Pubkey)]
+pub struct AddIssuer<'info> {
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Issuer::INIT_SPACE,
+        seeds = [
+            b"issuer",
+            issuer_authority.as_ref(),
+        ],
+        bump
+    )]
+    pub issuer: Account<'info, Issuer>,
+
+    #[account(
+        mut,
+        constraint = authority.key() == state.authority @ AttestationError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageIssuer<'info> {
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"issuer",
+            issuer.authority.as_ref(),
+        ],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Issuer>,
+
+    #[account(
+        constraint = authority.key() == state.authority @ AttestationError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveIssuer<'info> {
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            b"issuer",
+            issuer.authority.as_ref(),
+        ],
+        bump = issuer.bump
+    )]
+    pub issuer: Account<'info, Issuer>,
+
+    #[account(
+        mut,
+        constraint = authority.key() == state.authority @ AttestationError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(code: [u8; 2])]
+pub struct AddJurisdiction<'info> {
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + JurisdictionConfig::INIT_SPACE,
+        seeds = [
+            b"jurisdiction",
+            code.as_ref(),
+        ],
+        bump
+    )]
+    pub jurisdiction_config: Account<'info, JurisdictionConfig>,
+
+    #[account(
+        mut,
+        constraint = authority.key() == state.authority @ AttestationError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageJurisdiction<'info> {
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"jurisdiction",
+            jurisdiction_config.code.as_ref(),
+        ],
+        bump = jurisdiction_config.bump
+    )]
+    pub jurisdiction_config: Account<'info, JurisdictionConfig>,
+
+    #[account(
+        constraint = authority.key() == state.authority @ AttestationError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveJurisdiction<'info> {
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            b"jurisdiction",
+            jurisdiction_config.code.as_ref(),
+        ],
+        bump = jurisdiction_config.bump
+    )]
+    pub jurisdiction_config: Account<'info, JurisdictionConfig>,
+
+    #[account(
+        mut,
+        constraint = authority.key() == state.authority @ AttestationError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(id: u32, version: u16)]
+pub struct AddSchema<'info> {
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AttestationSchema::INIT_SPACE,
+        seeds = [
+            b"schema",
+            id.to_le_bytes().as_ref(),
+            version.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub schema: Account<'info, AttestationSchema>,
+
+    #[account(
+        mut,
+        constraint = authority.key() == state.authority @ AttestationError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeprecateSchema<'info> {
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+
+    #[account(
+        mut,
         seeds = [
-            b"attestation",
-            attestation.audit_hash.as_ref(),
+            b"schema",
+            schema.id.to_le_bytes().as_ref(),
+            schema.version.to_le_bytes().as_ref(),
         ],
-        bump = attestation.bump
+        bump = schema.bump
     )]
-    pub attestation: Account<'info, Attestation>,
+    pub schema: Account<'info, AttestationSchema>,
+
+    #[account(
+        constraint = authority.key() == state.authority @ AttestationError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateState<'info> {
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+
+    #[account(
+        constraint = authority.key() == state.authority @ AttestationError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump,
+        constraint = state.pending_authority != Pubkey::default() @ AttestationError::NoPendingAuthority
+    )]
+    pub state: Account<'info, ProgramState>,
 
     #[account(
+        constraint = pending_authority.key() == state.pending_authority @ AttestationError::Unauthorized
+    )]
+    pub pending_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Multisig::INIT_SPACE,
+        seeds = [b"multisig"],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        seeds = [b"multisig_vault"],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
         constraint = authority.key() == state.authority @ AttestationError::Unauthorized
     )]
     pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMultisig<'info> {
+    #[account(
+        mut,
+        seeds = [b"multisig"],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        seeds = [b"multisig_vault"],
+        bump = multisig.vault_bump
+    )]
+    pub vault: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeTransaction<'info> {
+    #[account(
+        mut,
+        seeds = [b"multisig"],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [
+            b"proposal",
+            multisig.proposal_count.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        constraint = multisig.signers.contains(&proposer.key()) @ AttestationError::NotMultisigSigner
+    )]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveTransaction<'info> {
+    #[account(
+        seeds = [b"multisig"],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            proposal.index.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        constraint = multisig.signers.contains(&signer.key()) @ AttestationError::NotMultisigSigner
+    )]
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    #[account(
+        seeds = [b"multisig"],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [
+            b"proposal",
+            proposal.index.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: receives the proposal's rent; must be the account that paid it
+    #[account(mut, address = proposal.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = multisig.signers.contains(&signer.key()) @ AttestationError::NotMultisigSigner
+    )]
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteTransaction<'info> {
+    // Not `mut`: the proposed instruction may itself rewrite the multisig
+    // (e.g. `update_multisig`), and writing it back here would clobber that.
+    #[account(
+        seeds = [b"multisig"],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            proposal.index.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [b"multisig_vault"],
+        bump = multisig.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        constraint = multisig.signers.contains(&executor.key()) @ AttestationError::NotMultisigSigner
+    )]
+    pub executor: Signer<'info>,
+
+    pub attestation_program: Program<'info, crate::program::Attestation>,
 }
 
 // ============================================
@@ -229,15 +3648,427 @@ pub struct ProgramState {
     pub authority: Pubkey,
     pub attestation_count: u64,
     pub bump: u8,
+    /// Proposed authority awaiting `accept_authority` (default when none)
+    pub pending_authority: Pubkey,
+    pub min_tax_year: u16,
+    pub max_tax_year: u16,
+    /// Seconds a revoked or expired attestation is kept before it can be closed
+    pub retention_period: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Issuer {
+    pub bump: u8,
+    pub authority: Pubkey,
+    pub status: IssuerStatus,
+    #[max_len(MAX_ISSUER_JURISDICTIONS)]
+    pub jurisdictions: Vec<[u8; 2]>,
+    #[max_len(5)]
+    pub attestation_types: Vec<AttestationType>,
+    pub attestation_count: u64,
+    pub added_at: i64,
+    /// Sequence number the issuer's next attestation is created under
+    pub next_sequence: u64,
+}
+
+impl Issuer {
+    fn check_rights(
+        &self,
+        jurisdiction: [u8; 2],
+        attestation_type: AttestationType,
+    ) -> Result<()> {
+        require!(
+            self.jurisdictions.contains(&jurisdiction),
+            AttestationError::JurisdictionNotAllowed
+        );
+        require!(
+            self.attestation_types.contains(&attestation_type),
+            AttestationError::AttestationTypeNotAllowed
+        );
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Multisig {
+    pub threshold: u8,
+    #[max_len(MAX_MULTISIG_SIGNERS)]
+    pub signers: Vec<Pubkey>,
+    pub proposal_count: u64,
+    pub vault_bump: u8,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub bump: u8,
+    pub index: u64,
+    pub proposer: Pubkey,
+    #[max_len(MAX_PROPOSAL_ACCOUNTS)]
+    pub accounts: Vec<ProposalAccount>,
+    #[max_len(MAX_PROPOSAL_DATA_LEN)]
+    pub data: Vec<u8>,
+    #[max_len(MAX_MULTISIG_SIGNERS)]
+    pub approvals: Vec<Pubkey>,
+    pub executed: bool,
+    pub created_at: i64,
+    pub executed_at: i64,
+}
+
+impl Proposal {
+    /// Proposals can no longer be approved or executed `PROPOSAL_LIFETIME` after
+    /// creation
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.created_at + PROPOSAL_LIFETIME
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct ProposalAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Attestation {
+    pub bump: u8,
+    pub authority: Pubkey,
+    pub jurisdiction: [u8; 2],
+    pub attestation_type: AttestationType,
+    pub status: AttestationStatus,
+    /// First tax year covered, labelled by the calendar year its fiscal year starts in
+    pub tax_year: u16,
+    pub audit_hash: [u8; 32],
+    pub issued_at: i64,
+    pub expires_at: i64,
+    pub revoked_at: i64,
+    /// Attestation this one amends (default when original)
+    pub supersedes: Pubkey,
+    /// Amendment that replaced this one (default while current)
+    pub superseded_by: Pubkey,
+    pub renewed_at: i64,
+    pub renewal_count: u16,
+    /// Hash of the latest re-review document (zero until renewed with one)
+    pub review_hash: [u8; 32],
+    pub suspended_at: i64,
+    pub reinstated_at: i64,
+    /// Reason for the most recent suspension
+    pub suspension_reason: Option<SuspensionReason>,
+    /// Why the attestation was revoked (None while not revoked)
+    pub revocation_reason: Option<RevocationReason>,
+    /// Hash of the document backing the revocation (zero when none given)
+    pub revocation_hash: [u8; 32],
+    /// Whether every listed wallet must acknowledge before the attestation is Active
+    pub consent_required: bool,
+    /// Held Pending by the issuer until `activate_attestation`
+    pub activation_pending: bool,
+    /// Bit i is set once `wallets[i]` has acknowledged
+    pub acknowledged: u16,
+    /// Bit i is set while `wallets[i]` has an open removal request
+    pub contested_by: u16,
+    /// Merkle root of the covered wallets (zero when they are listed in `wallets`)
+    pub wallet_root: [u8; 32],
+    /// Number of covered wallets, listed or under `wallet_root`
+    pub wallet_count: u32,
+    /// Issuer's sequence number for this attestation, part of its PDA seeds
+    pub sequence: u64,
+    /// ISO 3166-2 subdivision code within `jurisdiction`, e.g. `*b"CA\0"` for US-CA;
+    /// all zeros when the attestation covers the whole jurisdiction
+    pub subdivision: [u8; 3],
+    /// `AttestationSchema` the attestation was issued under
+    pub schema_id: u32,
+    pub schema_version: u16,
+    /// Fiscal period covered, `[period_start, period_end)` in Unix time
+    pub period_kind: PeriodKind,
+    pub period_start: i64,
+    pub period_end: i64,
+    /// Last tax year covered; equal to `tax_year` for a single-year attestation
+    pub end_tax_year: u16,
+    pub num_wallets: u8,
+    #[max_len(10)]
+    pub wallets: Vec<Pubkey>,
+}
+
+impl Attestation {
+    /// Account size (with discriminator) for an attestation listing `num_wallets`
+    pub fn space(num_wallets: usize) -> usize {
+        8 + Attestation::INIT_SPACE - (MAX_WALLETS - num_wallets.min(MAX_WALLETS)) * 32
+    }
+
+    /// Tax years covered, `tax_year` through `end_tax_year`
+    pub fn tax_years(&self) -> RangeInclusive<u16> {
+        self.tax_year..=self.end_tax_year
+    }
+
+    pub fn covers_tax_year(&self, tax_year: u16) -> bool {
+        self.tax_years().contains(&tax_year)
+    }
+
+    /// Period part of the uniqueness slot seeds: quarters and months of a tax year
+    /// each get their own slots, annual and custom periods share one
+    pub fn slot_period(&self) -> i64 {
+        match self.period_kind {
+            PeriodKind::Quarterly | PeriodKind::Monthly => self.period_start,
+            PeriodKind::Annual | PeriodKind::Custom => 0,
+        }
+    }
+
+    pub fn is_merkle(&self) -> bool {
+        self.wallet_root != [0u8; 32]
+    }
+
+    /// Whether `wallet` is covered; `proof` is only consulted for Merkle attestations
+    pub fn covers(&self, wallet: &Pubkey, proof: &[[u8; 32]]) -> bool {
+        if self.is_merkle() {
+            merkle::verify(&self.wallet_root, wallet, proof)
+        } else {
+            self.wallets.contains(wallet)
+        }
+    }
+
+    pub fn has_acknowledged(&self, wallet: &Pubkey) -> bool {
+        self.wallets
+            .iter()
+            .position(|w| w == wallet)
+            .is_some_and(|i| self.acknowledged & (1 << i) != 0)
+    }
+
+    pub fn is_contested(&self) -> bool {
+        self.contested_by != 0
+    }
+
+    pub fn all_acknowledged(&self) -> bool {
+        let mask = (1u16 << self.wallets.len()) - 1;
+        self.acknowledged & mask == mask
+    }
+
+    /// Neither issuer review nor wallet consent is outstanding
+    pub fn ready_to_activate(&self) -> bool {
+        !self.activation_pending && (!self.consent_required || self.all_acknowledged())
+    }
+}
+
+/// A listed wallet's open dispute of an attestation, closed once resolved or when the
+/// attestation is closed
+#[account]
+#[derive(InitSpace)]
+pub struct RemovalRequest {
+    pub bump: u8,
+    pub attestation: Pubkey,
+    pub wallet: Pubkey,
+    /// Hash of the wallet owner's statement
+    pub reason_hash: [u8; 32],
+    pub requested_at: i64,
+}
+
+/// Reverse index from a listed wallet to an attestation covering it. Links sit at
+/// `[b"wallet_link", wallet, attestation]`, and `wallet` is at a fixed offset so all
+/// links of a wallet can be fetched with a single memcmp filter. They follow the
+/// wallet list and are closed with the attestation; other status changes are not
+/// mirrored, so read the attestation for its current status.
+#[account]
+#[derive(InitSpace)]
+pub struct WalletAttestationLink {
+    pub bump: u8,
+    pub wallet: Pubkey,
+    pub attestation: Pubkey,
+    pub linked_at: i64,
+    /// When the attestation was revoked (0 if it has not been)
+    pub revoked_at: i64,
+}
+
+/// Uniqueness guard: at most one attestation per wallet, jurisdiction, subdivision,
+/// tax year, type and (for quarters and months) period; a multi-year attestation
+/// holds one slot per year. Lives at
+/// `[b"slot", wallet, jurisdiction, subdivision, tax_year (LE), attestation_type,
+/// period_start (LE)]` and stays allocated once created; `attestation` is cleared
+/// when the holder is revoked, closed or drops the wallet. Once the holder's
+/// `expires_at` has passed, expired or not, another attestation may claim the slot.
+#[account]
+#[derive(InitSpace)]
+pub struct AttestationSlot {
+    pub bump: u8,
+    pub wallet: Pubkey,
+    pub jurisdiction: [u8; 2],
+    pub subdivision: [u8; 3],
+    pub attestation_type: AttestationType,
+    pub tax_year: u16,
+    /// Start of the quarter or month; 0 for annual and custom periods
+    pub period_start: i64,
+    /// Attestation holding the slot (default when free)
+    pub attestation: Pubkey,
+    pub claimed_at: i64,
+    /// Holder's `expires_at`, kept in step by `renew_attestation`
+    pub expires_at: i64,
+}
+
+/// Registry entry at `["jurisdiction", code]` for an ISO 3166-1 alpha-2 code;
+/// attestations can only be created for registered codes
+#[account]
+#[derive(InitSpace)]
+pub struct JurisdictionConfig {
+    pub bump: u8,
+    pub code: [u8; 2],
+    pub registered_at: i64,
+    /// Month (1-12) and day (1-28) the fiscal year starts on, 1 January by default
+    pub fiscal_year_start_month: u8,
+    pub fiscal_year_start_day: u8,
+    /// ISO 3166-2 subdivision codes attestations may name, zero-padded to 3 bytes
+    #[max_len(MAX_SUBDIVISIONS)]
+    pub subdivisions: Vec<[u8; 3]>,
+}
+
+impl JurisdictionConfig {
+    /// Read the config behind an instruction's `jurisdiction_config` account
+    fn load(info: &AccountInfo) -> Result<Self> {
+        Self::try_deserialize(&mut &info.try_borrow_data()?[..])
+    }
+
+    /// A non-zero `subdivision` must be listed by the config
+    fn check_subdivision(&self, subdivision: [u8; 3]) -> Result<()> {
+        require!(
+            subdivision == [0u8; 3] || self.subdivisions.contains(&subdivision),
+            AttestationError::InvalidSubdivision
+        );
+        Ok(())
+    }
+
+    /// Midnight UTC on the day fiscal year `year` starts. A tax year is labelled by
+    /// the calendar year its fiscal year starts in, so with a 1 July start tax year
+    /// 2024 runs from 1 July 2024 to 30 June 2025.
+    fn fiscal_year_start(&self, year: i64) -> i64 {
+        days_from_civil(
+            year,
+            i64::from(self.fiscal_year_start_month),
+            i64::from(self.fiscal_year_start_day),
+        ) * SECONDS_PER_DAY
+    }
+
+    /// Every period starts within fiscal year `tax_year` and ends within fiscal year
+    /// `end_tax_year` (end exclusive). Annual, quarterly and monthly periods also
+    /// start at midnight UTC on the fiscal year's start day, in a month a whole
+    /// number of periods into the fiscal year, and run exactly one period, or every
+    /// covered fiscal year for annual periods.
+    fn check_period(
+        &self,
+        kind: PeriodKind,
+        start: i64,
+        end: i64,
+        tax_year: u16,
+        end_tax_year: u16,
+    ) -> Result<()> {
+        let (first, last) = (i64::from(tax_year), i64::from(end_tax_year));
+        require!(start < end, AttestationError::InvalidPeriod);
+        require!(
+            (self.fiscal_year_start(first)..self.fiscal_year_start(first + 1)).contains(&start)
+                && end > self.fiscal_year_start(last)
+                && end <= self.fiscal_year_start(last + 1),
+            AttestationError::InvalidPeriod
+        );
+        let Some(months) = kind.months() else {
+            return Ok(());
+        };
+        let years = end_tax_year - tax_year + 1;
+        let length = match kind {
+            PeriodKind::Annual => months * i64::from(years),
+            _ => {
+                require!(years == 1, AttestationError::InvalidPeriod);
+                months
+            }
+        };
+
+        require!(
+            start.rem_euclid(SECONDS_PER_DAY) == 0,
+            AttestationError::InvalidPeriod
+        );
+        let (year, month, day) = civil_from_days(start.div_euclid(SECONDS_PER_DAY));
+        require!(
+            day == i64::from(self.fiscal_year_start_day)
+                && (month - i64::from(self.fiscal_year_start_month)).rem_euclid(months) == 0,
+            AttestationError::InvalidPeriod
+        );
+
+        let end_month = year * 12 + month - 1 + length;
+        let expected_end =
+            days_from_civil(end_month.div_euclid(12), end_month.rem_euclid(12) + 1, day)
+                * SECONDS_PER_DAY;
+        require!(end == expected_end, AttestationError::InvalidPeriod);
+        Ok(())
+    }
 }
 
+/// Registered attestation kind at `["schema", id (LE), version (LE)]`. Every
+/// attestation is issued under a schema of its `attestation_type`.
 #[account]
 #[derive(InitSpace)]
-pub struct Attestation {
+pub struct AttestationSchema {
+    pub bump: u8,
+    pub id: u32,
+    pub version: u16,
+    /// SHA-256 of the schema's human-readable name, e.g. "DAC8 reporting"
+    pub name_hash: [u8; 32],
+    /// Category that governs issuer rights, uniqueness and renewal
+    pub attestation_type: AttestationType,
+    /// `SCHEMA_REQUIRES_*` flags
+    pub required_fields: u16,
+    /// Seconds an attestation created with `expires_at` 0 stays valid (0 for none)
+    pub default_validity: i64,
+    /// Jurisdictions the schema may be used for; empty for any
+    #[max_len(MAX_SCHEMA_JURISDICTIONS)]
+    pub jurisdictions: Vec<[u8; 2]>,
+    pub deprecated: bool,
+    pub created_at: i64,
+}
+
+impl AttestationSchema {
+    fn check(
+        &self,
+        jurisdiction: [u8; 2],
+        subdivision: [u8; 3],
+        require_consent: bool,
+        listed: bool,
+    ) -> Result<()> {
+        require!(
+            self.jurisdictions.is_empty() || self.jurisdictions.contains(&jurisdiction),
+            AttestationError::SchemaJurisdictionNotAllowed
+        );
+        let requires = |flag: u16| self.required_fields & flag != 0;
+        require!(
+            (!requires(SCHEMA_REQUIRES_SUBDIVISION) || subdivision != [0u8; 3])
+                && (!requires(SCHEMA_REQUIRES_WALLET_CONSENT) || require_consent)
+                && (!requires(SCHEMA_REQUIRES_LISTED_WALLETS) || listed),
+            AttestationError::SchemaFieldMissing
+        );
+        Ok(())
+    }
+
+    /// `expires_at`, or `now` plus the default validity when it is 0
+    fn resolve_expiry(&self, expires_at: i64, now: i64) -> i64 {
+        if expires_at == 0 && self.default_validity > 0 {
+            now.saturating_add(self.default_validity)
+        } else {
+            expires_at
+        }
+    }
+}
+
+/// Attestation created before issuer namespacing, at `["attestation", audit_hash]`.
+/// It shares `Attestation`'s discriminator but keeps the original layout, with the
+/// jurisdiction as an index into the old `Jurisdiction` enum, and no wallet links or
+/// slots. Only the `*_legacy_attestation` instructions read or write it.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyAttestation {
     pub bump: u8,
     pub authority: Pubkey,
-    pub jurisdiction: Jurisdiction,
+    /// US, EU, BR, UK, JP, AU, CA, CH, SG from 0
+    pub jurisdiction: u8,
     pub attestation_type: AttestationType,
+    /// Pending, Active, Expired or Revoked, with today's `AttestationStatus` values
     pub status: AttestationStatus,
     pub tax_year: u16,
     pub audit_hash: [u8; 32],
@@ -245,27 +4076,53 @@ pub struct Attestation {
     pub expires_at: i64,
     pub revoked_at: i64,
     pub num_wallets: u8,
-    #[max_len(10)]
     pub wallets: Vec<Pubkey>,
 }
 
+impl LegacyAttestation {
+    /// Read a legacy attestation, checking it lives at its legacy address; accounts
+    /// in the current layout never do
+    fn load(info: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(*info.owner, crate::ID, AttestationError::NotLegacyAttestation);
+        let data = info.try_borrow_data()?;
+        require!(
+            data.get(..8) == Some(Attestation::DISCRIMINATOR),
+            AttestationError::NotLegacyAttestation
+        );
+        let legacy = Self::deserialize(&mut &data[8..])
+            .map_err(|_| error!(AttestationError::NotLegacyAttestation))?;
+
+        let address = Pubkey::create_program_address(
+            &[b"attestation", &legacy.audit_hash, &[legacy.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| error!(AttestationError::NotLegacyAttestation))?;
+        require_keys_eq!(info.key(), address, AttestationError::NotLegacyAttestation);
+        Ok(legacy)
+    }
+
+    /// Write back in place; statuses and timestamps keep their encoded size
+    fn store(&self, info: &AccountInfo) -> Result<()> {
+        self.serialize(&mut &mut info.try_borrow_mut_data()?[8..])?;
+        Ok(())
+    }
+}
+
+/// Left behind by `close_attestation` at `["tombstone", attestation]` as a record of
+/// the closed attestation
+#[account]
+#[derive(InitSpace)]
+pub struct Tombstone {
+    pub bump: u8,
+    pub audit_hash: [u8; 32],
+    pub final_status: AttestationStatus,
+    pub closed_at: i64,
+}
+
 // ============================================
 // Enums
 // ============================================
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
-pub enum Jurisdiction {
-    US = 0,
-    EU = 1,
-    BR = 2,
-    UK = 3,
-    JP = 4,
-    AU = 5,
-    CA = 6,
-    CH = 7,
-    SG = 8,
-}
-
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 pub enum AttestationType {
     TaxCompliance = 0,
@@ -275,12 +4132,86 @@ pub enum AttestationType {
     AnnualReview = 4,
 }
 
+/// Length of the fiscal period an attestation covers
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PeriodKind {
+    Annual = 0,
+    Quarterly = 1,
+    Monthly = 2,
+    /// Any non-empty range, e.g. a partial year after a change of residence
+    Custom = 3,
+}
+
+impl PeriodKind {
+    /// Period length in months; `None` for custom periods
+    pub fn months(self) -> Option<i64> {
+        match self {
+            PeriodKind::Annual => Some(12),
+            PeriodKind::Quarterly => Some(3),
+            PeriodKind::Monthly => Some(1),
+            PeriodKind::Custom => None,
+        }
+    }
+}
+
+impl AttestationType {
+    /// Ongoing statuses can be renewed; attestations about a single completed
+    /// audit, report or quarter cannot.
+    pub fn is_renewable(self) -> bool {
+        matches!(
+            self,
+            AttestationType::TaxCompliance | AttestationType::AnnualReview
+        )
+    }
+
+    /// Tax compliance is attested wallet by wallet and must stay unique per slot, so
+    /// it always lists its wallets; the other types may cover a fund or treasury
+    /// through a Merkle root.
+    pub fn allows_merkle_wallets(self) -> bool {
+        self != AttestationType::TaxCompliance
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 pub enum AttestationStatus {
     Pending = 0,
     Active = 1,
     Expired = 2,
     Revoked = 3,
+    Superseded = 4,
+    Suspended = 5,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum SuspensionReason {
+    DiscrepancyUnderReview = 0,
+    PendingDocumentation = 1,
+    ClientDispute = 2,
+    RegulatoryInquiry = 3,
+    Other = 4,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum RevocationReason {
+    IssuerError = 0,
+    ClientRequest = 1,
+    Fraud = 2,
+    Superseded = 3,
+    RegulatoryOrder = 4,
+    Other = 5,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum RemovalResolution {
+    Revoke = 0,
+    Amend = 1,
+    Reject = 2,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum IssuerStatus {
+    Active = 0,
+    Suspended = 1,
 }
 
 // ============================================
@@ -296,12 +4227,73 @@ pub struct ProgramInitialized {
 pub struct AttestationCreated {
     pub attestation: Pubkey,
     pub wallets: Vec<Pubkey>,
-    pub jurisdiction: Jurisdiction,
+    pub jurisdiction: [u8; 2],
+    /// ISO 3166-2 subdivision code, all zeros for the whole jurisdiction
+    pub subdivision: [u8; 3],
     pub attestation_type: AttestationType,
+    pub schema_id: u32,
+    pub schema_version: u16,
     pub tax_year: u16,
+    pub end_tax_year: u16,
+    pub period_kind: PeriodKind,
+    pub period_start: i64,
+    pub period_end: i64,
     pub audit_hash: [u8; 32],
     pub issued_at: i64,
     pub expires_at: i64,
+    /// Pending when wallet consent or activation is still outstanding
+    pub status: AttestationStatus,
+    /// Set instead of `wallets` for Merkle-root attestations
+    pub wallet_root: [u8; 32],
+    pub wallet_count: u32,
+}
+
+#[event]
+pub struct WalletsChanged {
+    pub attestation: Pubkey,
+    pub added: Vec<Pubkey>,
+    pub removed: Vec<Pubkey>,
+    pub num_wallets: u8,
+}
+
+#[event]
+pub struct AttestationAcknowledged {
+    pub attestation: Pubkey,
+    pub wallet: Pubkey,
+    pub acknowledged: u16,
+}
+
+#[event]
+pub struct AttestationSuperseded {
+    pub previous: Pubkey,
+    pub attestation: Pubkey,
+    pub previous_audit_hash: [u8; 32],
+    pub audit_hash: [u8; 32],
+    pub superseded_at: i64,
+}
+
+#[event]
+pub struct AttestationRenewed {
+    pub attestation: Pubkey,
+    pub old_expires_at: i64,
+    pub new_expires_at: i64,
+    pub review_hash: [u8; 32],
+    pub renewal_count: u16,
+    pub renewed_at: i64,
+}
+
+#[event]
+pub struct AttestationSuspended {
+    pub attestation: Pubkey,
+    pub reason: SuspensionReason,
+    pub suspended_at: i64,
+}
+
+#[event]
+pub struct AttestationReinstated {
+    pub attestation: Pubkey,
+    pub suspended_at: i64,
+    pub reinstated_at: i64,
 }
 
 #[event]
@@ -315,9 +4307,183 @@ pub struct StatusUpdated {
 pub struct AttestationRevoked {
     pub attestation: Pubkey,
     pub wallets: Vec<Pubkey>,
+    pub reason: RevocationReason,
+    pub reason_hash: [u8; 32],
     pub revoked_at: i64,
 }
 
+#[event]
+pub struct RemovalRequested {
+    pub attestation: Pubkey,
+    pub request: Pubkey,
+    pub wallet: Pubkey,
+    pub reason_hash: [u8; 32],
+    pub requested_at: i64,
+}
+
+#[event]
+pub struct RemovalResolved {
+    pub attestation: Pubkey,
+    pub request: Pubkey,
+    pub wallet: Pubkey,
+    pub resolution: RemovalResolution,
+    pub resolution_hash: [u8; 32],
+    pub resolved_at: i64,
+}
+
+#[event]
+pub struct AttestationClosed {
+    pub attestation: Pubkey,
+    pub audit_hash: [u8; 32],
+    pub final_status: AttestationStatus,
+    pub closed_at: i64,
+}
+
+#[event]
+pub struct SchemaAdded {
+    pub schema: Pubkey,
+    pub id: u32,
+    pub version: u16,
+    pub name_hash: [u8; 32],
+    pub attestation_type: AttestationType,
+}
+
+#[event]
+pub struct SchemaDeprecated {
+    pub schema: Pubkey,
+    pub id: u32,
+    pub version: u16,
+}
+
+#[event]
+pub struct TaxYearWindowUpdated {
+    pub min_tax_year: u16,
+    pub max_tax_year: u16,
+}
+
+#[event]
+pub struct RetentionPeriodUpdated {
+    pub retention_period: i64,
+}
+
+#[event]
+pub struct IssuerAdded {
+    pub issuer: Pubkey,
+    pub authority: Pubkey,
+    pub jurisdictions: Vec<[u8; 2]>,
+    pub attestation_types: Vec<AttestationType>,
+}
+
+#[event]
+pub struct IssuerUpdated {
+    pub issuer: Pubkey,
+    pub authority: Pubkey,
+    pub jurisdictions: Vec<[u8; 2]>,
+    pub attestation_types: Vec<AttestationType>,
+}
+
+#[event]
+pub struct IssuerStatusChanged {
+    pub issuer: Pubkey,
+    pub authority: Pubkey,
+    pub old_status: IssuerStatus,
+    pub new_status: IssuerStatus,
+}
+
+#[event]
+pub struct IssuerRemoved {
+    pub issuer: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct JurisdictionAdded {
+    pub jurisdiction_config: Pubkey,
+    pub code: [u8; 2],
+}
+
+#[event]
+pub struct JurisdictionSubdivisionsUpdated {
+    pub jurisdiction_config: Pubkey,
+    pub code: [u8; 2],
+    pub subdivisions: Vec<[u8; 3]>,
+}
+
+#[event]
+pub struct FiscalCalendarUpdated {
+    pub jurisdiction_config: Pubkey,
+    pub code: [u8; 2],
+    pub start_month: u8,
+    pub start_day: u8,
+}
+
+#[event]
+pub struct JurisdictionRemoved {
+    pub jurisdiction_config: Pubkey,
+    pub code: [u8; 2],
+}
+
+#[event]
+pub struct AuthorityTransferProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferAccepted {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferCancelled {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct MultisigCreated {
+    pub multisig: Pubkey,
+    pub vault: Pubkey,
+    pub threshold: u8,
+    pub signers: Vec<Pubkey>,
+}
+
+#[event]
+pub struct MultisigUpdated {
+    pub multisig: Pubkey,
+    pub threshold: u8,
+    pub signers: Vec<Pubkey>,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+}
+
+#[event]
+pub struct ProposalApproved {
+    pub proposal: Pubkey,
+    pub signer: Pubkey,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal: Pubkey,
+    pub index: u64,
+    pub executor: Pubkey,
+}
+
+#[event]
+pub struct ProposalCancelled {
+    pub proposal: Pubkey,
+    pub index: u64,
+    pub cancelled_by: Pubkey,
+}
+
 // ============================================
 // Errors
 // ============================================
@@ -344,4 +4510,331 @@ pub enum AttestationError {
 
     #[msg("Invalid wallet count: must be 1-10 wallets")]
     InvalidWalletCount,
+
+    #[msg("Wallet must not be the default pubkey")]
+    InvalidWallet,
+
+    #[msg("Wallet is listed more than once")]
+    DuplicateWallet,
+
+    #[msg("Audit hash must not be all zeroes")]
+    InvalidAuditHash,
+
+    #[msg("Tax year is outside the accepted window")]
+    InvalidTaxYear,
+
+    #[msg("Invalid tax year window")]
+    InvalidTaxYearWindow,
+
+    #[msg("Attestation has not reached its expiry time")]
+    AttestationNotExpired,
+
+    #[msg("Attestation is not suspended")]
+    AttestationNotSuspended,
+
+    #[msg("Attestation type cannot be renewed")]
+    AttestationNotRenewable,
+
+    #[msg("Renewal must move expires_at past both now and the current expiry")]
+    InvalidRenewalExpiry,
+
+    #[msg("Attestation has reached the maximum number of renewals")]
+    RenewalLimitReached,
+
+    #[msg("Account must be writable")]
+    AccountNotWritable,
+
+    #[msg("Issuer rights must list at least one jurisdiction and type, without duplicates")]
+    InvalidIssuerRights,
+
+    #[msg("Issuer is suspended")]
+    IssuerSuspended,
+
+    #[msg("Issuer is not allowed to attest for this jurisdiction")]
+    JurisdictionNotAllowed,
+
+    #[msg("Issuer is not allowed to issue this attestation type")]
+    AttestationTypeNotAllowed,
+
+    #[msg("Signer is not the program's upgrade authority")]
+    NotUpgradeAuthority,
+
+    #[msg("Invalid authority")]
+    InvalidAuthority,
+
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+
+    #[msg("Invalid signer count: must be 1-10 signers")]
+    InvalidSignerCount,
+
+    #[msg("Invalid threshold: must be between 1 and the number of signers")]
+    InvalidThreshold,
+
+    #[msg("Duplicate multisig signer")]
+    DuplicateSigner,
+
+    #[msg("Signer is not a member of the multisig")]
+    NotMultisigSigner,
+
+    #[msg("Proposal exceeds the maximum accounts or data length")]
+    ProposalTooLarge,
+
+    #[msg("Proposal already approved by this signer")]
+    AlreadyApproved,
+
+    #[msg("Proposal has already been executed")]
+    ProposalAlreadyExecuted,
+
+    #[msg("Proposal does not have enough approvals")]
+    InsufficientApprovals,
+
+    #[msg("Remaining accounts do not match the proposal")]
+    ProposalAccountMismatch,
+
+    #[msg("Only revoked or expired attestations can be closed")]
+    AttestationNotClosable,
+
+    #[msg("Attestation is still within its retention period")]
+    RetentionPeriodActive,
+
+    #[msg("Retention period must not be negative")]
+    InvalidRetentionPeriod,
+
+    #[msg("Wallet is not listed on this attestation")]
+    WalletNotListed,
+
+    #[msg("Wallet has already acknowledged this attestation")]
+    AlreadyAcknowledged,
+
+    #[msg("Not every listed wallet has acknowledged this attestation")]
+    ConsentIncomplete,
+
+    #[msg("Attestation has not been superseded by an amendment")]
+    AttestationNotSuperseded,
+
+    #[msg("Attestations can only be created Pending or Active")]
+    InvalidInitialStatus,
+
+    #[msg("Attestation is not awaiting activation")]
+    AttestationNotPending,
+
+    #[msg("Wallet Merkle root must not be all zeroes")]
+    InvalidWalletRoot,
+
+    #[msg("Wallets of a Merkle-root attestation cannot be changed")]
+    MerkleWalletsImmutable,
+
+    #[msg("Wallet link accounts do not match the attestation's wallets")]
+    WalletLinkMismatch,
+
+    #[msg("Slot accounts do not match the attestation's wallets")]
+    SlotMismatch,
+
+    #[msg("Wallet already holds an attestation for this jurisdiction, tax year and type")]
+    SlotOccupied,
+
+    #[msg("Subdivision is not a registered ISO 3166-2 code of the jurisdiction")]
+    InvalidSubdivision,
+
+    #[msg("Invalid attestation schema definition")]
+    InvalidSchema,
+
+    #[msg("Attestation schema is deprecated")]
+    SchemaDeprecated,
+
+    #[msg("Attestation schema is for a different attestation type")]
+    SchemaTypeMismatch,
+
+    #[msg("Attestation schema does not allow this jurisdiction")]
+    SchemaJurisdictionNotAllowed,
+
+    #[msg("Attestation is missing a field its schema requires")]
+    SchemaFieldMissing,
+
+    #[msg("Invalid fiscal calendar")]
+    InvalidFiscalCalendar,
+
+    #[msg("Period does not match the jurisdiction's fiscal calendar")]
+    InvalidPeriod,
+
+    #[msg("Tax year range is reversed or longer than MAX_TAX_YEARS")]
+    InvalidTaxYearRange,
+
+    #[msg("Proposal has expired")]
+    ProposalExpired,
+
+    #[msg("Only the proposer can cancel a proposal before it expires")]
+    ProposalNotExpired,
+
+    #[msg("Wallets times covered tax years exceeds MAX_WALLET_SLOTS")]
+    TooManySlots,
+
+    #[msg("Attestation type must list its wallets")]
+    MerkleWalletsNotAllowed,
+
+    #[msg("Account is not an attestation in the legacy layout at its legacy address")]
+    NotLegacyAttestation,
+
+    #[msg("Removal requests do not match the attestation's contested wallets")]
+    RemovalRequestMismatch,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+    use anchor_lang::{Bumps, InstructionData};
+    use std::collections::BTreeSet;
+
+    /// Decode the `#[instruction]` args of `T` from `data` (discriminator included)
+    /// with no accounts passed: past the args, account loading runs out of keys
+    fn decode_args<'info, T>(data: &[u8]) -> Error
+    where
+        T: Accounts<'info, <T as Bumps>::Bumps> + Bumps,
+        <T as Bumps>::Bumps: Default,
+    {
+        let mut accounts: &'info [AccountInfo<'info>] = &[];
+        let mut bumps = Default::default();
+        match T::try_accounts(
+            &crate::ID,
+            &mut accounts,
+            &data[8..],
+            &mut bumps,
+            &mut BTreeSet::new(),
+        ) {
+            Ok(_) => panic!("accounts loaded without any keys"),
+            Err(err) => err,
+        }
+    }
+
+    #[test]
+    fn create_attestation_args_match_the_handler() {
+        let data = crate::instruction::CreateAttestation {
+            jurisdiction: *b"US",
+            subdivision: [0; 3],
+            attestation_type: AttestationType::TaxCompliance,
+            tax_year: 2025,
+            end_tax_year: 2025,
+            period_kind: PeriodKind::Annual,
+            period_start: 1_735_689_600,
+            period_end: 1_767_225_600,
+            audit_hash: hash(b"audit").to_bytes(),
+            expires_at: 1_800_000_000,
+            wallets: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+            require_consent: false,
+            initial_status: AttestationStatus::Active,
+        }
+        .data();
+
+        assert_eq!(
+            decode_args::<CreateAttestation>(&data),
+            ErrorCode::AccountNotEnoughKeys.into()
+        );
+    }
+
+    #[test]
+    fn create_merkle_attestation_args_match_the_handler() {
+        let data = crate::instruction::CreateMerkleAttestation {
+            jurisdiction: *b"US",
+            subdivision: [0; 3],
+            attestation_type: AttestationType::AnnualReview,
+            tax_year: 2025,
+            end_tax_year: 2025,
+            period_kind: PeriodKind::Annual,
+            period_start: 1_735_689_600,
+            period_end: 1_767_225_600,
+            audit_hash: hash(b"audit").to_bytes(),
+            expires_at: 1_800_000_000,
+            wallet_root: hash(b"root").to_bytes(),
+            wallet_count: 100,
+            initial_status: AttestationStatus::Active,
+        }
+        .data();
+
+        assert_eq!(
+            decode_args::<CreateMerkleAttestation>(&data),
+            ErrorCode::AccountNotEnoughKeys.into()
+        );
+    }
+
+    /// An attestation as the first release of the program wrote it, field by field
+    fn legacy_account_data(audit_hash: [u8; 32], bump: u8, wallets: &[Pubkey]) -> Vec<u8> {
+        let mut data = Attestation::DISCRIMINATOR.to_vec();
+        data.push(bump);
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.push(3); // Jurisdiction::UK
+        data.push(AttestationType::TaxCompliance as u8);
+        data.push(AttestationStatus::Active as u8);
+        data.extend_from_slice(&2024u16.to_le_bytes());
+        data.extend_from_slice(&audit_hash);
+        data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        data.extend_from_slice(&1_800_000_000i64.to_le_bytes());
+        data.extend_from_slice(&0i64.to_le_bytes());
+        data.push(wallets.len() as u8);
+        data.extend_from_slice(&(wallets.len() as u32).to_le_bytes());
+        for wallet in wallets {
+            data.extend_from_slice(wallet.as_ref());
+        }
+        data
+    }
+
+    #[test]
+    fn legacy_attestation_round_trips_at_its_legacy_address() {
+        let audit_hash = hash(b"legacy").to_bytes();
+        let (key, bump) = Pubkey::find_program_address(&[b"attestation", &audit_hash], &crate::ID);
+        let wallets = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut data = legacy_account_data(audit_hash, bump, &wallets);
+        let original = data.clone();
+        let mut lamports = 0;
+        let info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+
+        let mut legacy = LegacyAttestation::load(&info).unwrap();
+        assert_eq!(legacy.jurisdiction, 3);
+        assert_eq!(legacy.tax_year, 2024);
+        assert_eq!(legacy.wallets, wallets);
+
+        legacy.store(&info).unwrap();
+        assert_eq!(*info.data.borrow(), &original[..]);
+
+        legacy.status = AttestationStatus::Revoked;
+        legacy.revoked_at = 1_750_000_000;
+        legacy.store(&info).unwrap();
+        let reloaded = LegacyAttestation::load(&info).unwrap();
+        assert!(reloaded.status == AttestationStatus::Revoked);
+        assert_eq!(reloaded.revoked_at, 1_750_000_000);
+    }
+
+    #[test]
+    fn legacy_attestation_refuses_other_addresses() {
+        let audit_hash = hash(b"legacy").to_bytes();
+        let (_, bump) = Pubkey::find_program_address(&[b"attestation", &audit_hash], &crate::ID);
+        let mut data = legacy_account_data(audit_hash, bump, &[Pubkey::new_unique()]);
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+
+        assert_eq!(
+            LegacyAttestation::load(&info).err(),
+            Some(AttestationError::NotLegacyAttestation.into())
+        );
+    }
 }
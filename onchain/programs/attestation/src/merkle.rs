@@ -0,0 +1,157 @@
+//! Merkle tree over wallet addresses, for attestations that cover more wallets
+//! than fit in `Attestation.wallets`.
+//!
+//! Leaves are `sha256(0x00 || wallet)` and parents are `sha256(0x01 || lo || hi)`
+//! where `lo <= hi`, so proofs carry no left/right flags. An odd node at the end
+//! of a level is carried up unchanged.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::hashv;
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub fn leaf_hash(wallet: &Pubkey) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, wallet.as_ref()]).to_bytes()
+}
+
+pub fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, lo, hi]).to_bytes()
+}
+
+/// Check that `proof` leads from `wallet` to `root`
+pub fn verify(root: &[u8; 32], wallet: &Pubkey, proof: &[[u8; 32]]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf_hash(wallet), |node, sibling| node_hash(&node, sibling));
+    computed == *root
+}
+
+/// Off-chain helper that builds the tree and its inclusion proofs
+pub struct WalletTree {
+    /// levels[0] are the leaves in wallet order; the last level holds the root
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl WalletTree {
+    pub fn new(wallets: &[Pubkey]) -> Self {
+        let mut levels = vec![wallets.iter().map(leaf_hash).collect::<Vec<_>>()];
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => node_hash(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        Self { levels }
+    }
+
+    /// Root to store on-chain (all zeroes for an empty tree)
+    pub fn root(&self) -> [u8; 32] {
+        self.levels
+            .last()
+            .and_then(|level| level.first())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn leaf_count(&self) -> u32 {
+        self.levels[0].len() as u32
+    }
+
+    /// Proof for the wallet at `index`, or None if out of range
+    pub fn proof(&self, index: usize) -> Option<Vec<[u8; 32]>> {
+        if index >= self.levels[0].len() {
+            return None;
+        }
+
+        let mut proof = Vec::new();
+        let mut i = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(i ^ 1) {
+                proof.push(*sibling);
+            }
+            i /= 2;
+        }
+        Some(proof)
+    }
+
+    /// Proof for `wallet`, or None if it is not in the tree
+    pub fn proof_for(&self, wallet: &Pubkey) -> Option<Vec<[u8; 32]>> {
+        let leaf = leaf_hash(wallet);
+        let index = self.levels[0].iter().position(|l| *l == leaf)?;
+        self.proof(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wallets(n: usize) -> Vec<Pubkey> {
+        (0..n).map(|_| Pubkey::new_unique()).collect()
+    }
+
+    fn assert_round_trip(n: usize) {
+        let wallets = wallets(n);
+        let tree = WalletTree::new(&wallets);
+        let root = tree.root();
+
+        assert_eq!(tree.leaf_count(), n as u32);
+        for (index, wallet) in wallets.iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+            assert!(verify(&root, wallet, &proof));
+            assert_eq!(tree.proof_for(wallet), Some(proof));
+        }
+        assert_eq!(tree.proof(n), None);
+    }
+
+    #[test]
+    fn single_leaf_is_the_root() {
+        let wallets = wallets(1);
+        let tree = WalletTree::new(&wallets);
+
+        assert_eq!(tree.root(), leaf_hash(&wallets[0]));
+        assert_eq!(tree.proof(0), Some(Vec::new()));
+        assert_round_trip(1);
+    }
+
+    #[test]
+    fn two_leaves_round_trip() {
+        assert_round_trip(2);
+    }
+
+    #[test]
+    fn odd_leaf_counts_round_trip() {
+        for n in [3, 5, 7, 11] {
+            assert_round_trip(n);
+        }
+    }
+
+    #[test]
+    fn non_member_has_no_proof() {
+        let tree = WalletTree::new(&wallets(5));
+
+        assert_eq!(tree.proof_for(&Pubkey::new_unique()), None);
+    }
+
+    #[test]
+    fn non_member_fails_verify() {
+        let wallets = wallets(5);
+        let tree = WalletTree::new(&wallets);
+        let root = tree.root();
+        let outsider = Pubkey::new_unique();
+
+        for index in 0..wallets.len() {
+            assert!(!verify(&root, &outsider, &tree.proof(index).unwrap()));
+        }
+        assert!(!verify(&root, &outsider, &[]));
+    }
+}
@@ -11,6 +11,7 @@ import * as fs from 'fs';
 import * as path from 'path';
 
 const PROGRAM_ID = new PublicKey('52LCg2VXDYgam4yHkXEp2vN2psUmo6Q7rv5efRm7ic8c');
+const BPF_LOADER_UPGRADEABLE_ID = new PublicKey('BPFLoaderUpgradeab1e11111111111111111111111');
 const INITIALIZE_DISCRIMINATOR = Buffer.from([175, 175, 109, 31, 13, 152, 155, 237]);
 
 async function main() {
@@ -45,15 +46,24 @@ async function main() {
     console.log('Program state already initialized!');
     console.log(`  Owner: ${stateAccount.owner.toBase58()}`);
     console.log(`  Data length: ${stateAccount.data.length} bytes`);
+    console.log('Register jurisdictions, schemas and the issuer with setup-program.ts');
     return;
   }
 
+  // initialize must be signed by the upgrade authority recorded in ProgramData
+  const [programDataPda] = PublicKey.findProgramAddressSync(
+    [PROGRAM_ID.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_ID,
+  );
+
   // Build initialize instruction
   const ix = new TransactionInstruction({
     programId: PROGRAM_ID,
     keys: [
       { pubkey: statePda, isSigner: false, isWritable: true },
       { pubkey: authority.publicKey, isSigner: true, isWritable: true },
+      { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: programDataPda, isSigner: false, isWritable: false },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
     ],
     data: INITIALIZE_DISCRIMINATOR,
@@ -94,6 +104,7 @@ async function main() {
   if (verifyAccount) {
     console.log(`Verified: State account exists (${verifyAccount.data.length} bytes)`);
   }
+  console.log('Next, register jurisdictions, schemas and the issuer with setup-program.ts');
 }
 
 main().catch((err) => {
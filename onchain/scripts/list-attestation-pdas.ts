@@ -0,0 +1,82 @@
+import { Connection, PublicKey } from '@solana/web3.js';
+import {
+  AttestationDerivation,
+  AttestationStatus,
+  LegacyAttestationData,
+  getAttestationDerivation,
+  parseLegacyAttestationData,
+} from '../sdk/src';
+
+const PROGRAM_ID = new PublicKey('52LCg2VXDYgam4yHkXEp2vN2psUmo6Q7rv5efRm7ic8c');
+const ATTESTATION_DISCRIMINATOR = Buffer.from([152, 125, 183, 86, 36, 146, 121, 73]);
+
+// Lists every attestation account by the seed scheme its address was derived
+// with: legacy ["attestation", auditHash] or namespaced ["attestation", issuer,
+// sequence], and the next migration step for each legacy account:
+//   1. the issuer reissues a live attestation under the namespaced scheme
+//   2. the program authority revokes the legacy one (revokeLegacyAttestation,
+//      reason Superseded); past its expiry anyone may expireLegacyAttestation
+//   3. after the retention period the issuer closes it (closeLegacyAttestation)
+async function main() {
+  const rpcUrl = process.env.SOLANA_RPC_URL || 'https://api.devnet.solana.com';
+  const connection = new Connection(rpcUrl, 'confirmed');
+  console.log(`Connected to: ${rpcUrl}`);
+
+  const accounts = await connection.getProgramAccounts(PROGRAM_ID, {
+    filters: [
+      {
+        memcmp: {
+          offset: 0,
+          bytes: ATTESTATION_DISCRIMINATOR.toString('base64'),
+          encoding: 'base64' as any,
+        },
+      },
+    ],
+  });
+  console.log(`Attestation accounts: ${accounts.length}`);
+
+  const now = BigInt(Math.floor(Date.now() / 1000));
+  const counts = { [AttestationDerivation.Legacy]: 0, [AttestationDerivation.Namespaced]: 0, unknown: 0 };
+
+  for (const { pubkey, account } of accounts) {
+    const data = account.data as Buffer;
+    const derivation = getAttestationDerivation(pubkey, data, PROGRAM_ID);
+    counts[derivation ?? 'unknown'] += 1;
+
+    if (derivation === AttestationDerivation.Namespaced) continue;
+
+    console.log(`  ${derivation ?? 'unknown'}: ${pubkey.toBase58()}`);
+    if (derivation === null) continue;
+
+    const legacy = parseLegacyAttestationData(data);
+    console.log(`    issuer: ${legacy.authority.toBase58()}`);
+    console.log(`    audit hash: ${Buffer.from(legacy.auditHash).toString('hex')}`);
+    console.log(`    ${legacy.jurisdiction} ${legacy.taxYear}, ${legacy.numWallets} wallet(s)`);
+    console.log(`    status: ${AttestationStatus[legacy.status]}`);
+    console.log(`    next step: ${nextStep(legacy, now)}`);
+  }
+
+  console.log(`Namespaced: ${counts[AttestationDerivation.Namespaced]}`);
+  console.log(`Legacy: ${counts[AttestationDerivation.Legacy]}`);
+  console.log(`Unrecognised: ${counts.unknown}`);
+}
+
+function nextStep(legacy: LegacyAttestationData, now: bigint): string {
+  switch (legacy.status) {
+    case AttestationStatus.Active:
+      if (now >= legacy.expiresAt) return 'expireLegacyAttestation';
+      return 'reissue, then revokeLegacyAttestation (Superseded)';
+    case AttestationStatus.Pending:
+      return 'reissue, then revokeLegacyAttestation (Superseded)';
+    case AttestationStatus.Expired:
+    case AttestationStatus.Revoked:
+      return 'closeLegacyAttestation once the retention period has passed';
+    default:
+      return 'none';
+  }
+}
+
+main().catch((err) => {
+  console.error('Error:', err);
+  process.exit(1);
+});
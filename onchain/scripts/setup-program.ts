@@ -0,0 +1,148 @@
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+} from '@solana/web3.js';
+import { createHash } from 'crypto';
+import * as fs from 'fs';
+import * as path from 'path';
+import { AttestationType, AuditSwarmSolana, Jurisdiction } from '../sdk/src';
+
+const PROGRAM_ID = new PublicKey('52LCg2VXDYgam4yHkXEp2vN2psUmo6Q7rv5efRm7ic8c');
+
+// The backend's jurisdictions as the attestation processor maps them (UK is
+// registered as GB)
+const JURISDICTIONS: Jurisdiction[] = ['US', 'EU', 'BR', 'GB', 'JP', 'AU', 'CA', 'CH', 'SG'];
+
+// Fiscal years that do not start on 1 January, the on-chain default, as in
+// backend/libs/common/src/constants/jurisdictions.ts
+const FISCAL_CALENDARS: Partial<Record<Jurisdiction, { month: number; day: number }>> = {
+  GB: { month: 4, day: 6 },
+  AU: { month: 7, day: 1 },
+};
+
+const ATTESTATION_TYPES = [
+  AttestationType.TaxCompliance,
+  AttestationType.AuditComplete,
+  AttestationType.ReportingComplete,
+  AttestationType.QuarterlyReview,
+  AttestationType.AnnualReview,
+];
+
+// Registers what the workflow service needs before it can create attestations:
+// the jurisdictions it issues for, a base schema per attestation type (id = the
+// type's index, version 1) and its key as an issuer for all of them. Run after
+// init-program.ts, with the program authority's keypair. Safe to re-run: anything
+// already registered is left as it is, except issuer rights, which are widened.
+//
+// The issuer is ISSUER_PUBKEY, or else the public key of SOLANA_AUTHORITY_KEY,
+// the workflow service's keypair.
+async function main() {
+  const rpcUrl = process.env.SOLANA_RPC_URL || 'https://api.devnet.solana.com';
+  const connection = new Connection(rpcUrl, 'confirmed');
+  console.log(`Connected to: ${rpcUrl}`);
+
+  const keypairPath = process.env.SOLANA_KEYPAIR || path.join(
+    process.env.HOME || '~',
+    '.config/solana/id.json',
+  );
+  const keypairData = JSON.parse(fs.readFileSync(keypairPath, 'utf-8'));
+  const authority = Keypair.fromSecretKey(Uint8Array.from(keypairData));
+  console.log(`Authority: ${authority.publicKey.toBase58()}`);
+
+  const issuer = issuerKey();
+  console.log(`Issuer: ${issuer.toBase58()}`);
+
+  const sdk = new AuditSwarmSolana(connection, PROGRAM_ID);
+  const send = async (label: string, ix: TransactionInstruction) => {
+    const signature = await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(ix),
+      [authority],
+    );
+    console.log(`  ${label}: ${signature}`);
+  };
+
+  console.log('Jurisdictions:');
+  for (const code of JURISDICTIONS) {
+    const config = await sdk.getJurisdictionConfig(code);
+    if (!config) {
+      await send(`add ${code}`, sdk.buildAddJurisdictionInstruction(authority.publicKey, code));
+    }
+
+    const calendar = FISCAL_CALENDARS[code];
+    if (
+      calendar &&
+      (config?.fiscalYearStartMonth !== calendar.month ||
+        config?.fiscalYearStartDay !== calendar.day)
+    ) {
+      await send(
+        `fiscal year of ${code} from ${calendar.day}/${calendar.month}`,
+        sdk.buildSetFiscalCalendarInstruction(authority.publicKey, code, calendar.month, calendar.day),
+      );
+    }
+  }
+
+  console.log('Base schemas:');
+  for (const attestationType of ATTESTATION_TYPES) {
+    const schema = { id: attestationType, version: 1 };
+    if (await sdk.getSchema(schema)) continue;
+
+    const name = `${AttestationType[attestationType]} base schema`;
+    await send(
+      `add ${name}`,
+      sdk.buildAddSchemaInstruction(authority.publicKey, {
+        ...schema,
+        nameHash: createHash('sha256').update(name).digest(),
+        attestationType,
+      }),
+    );
+  }
+
+  console.log('Issuer:');
+  const existing = await sdk.getIssuer(issuer);
+  if (!existing) {
+    await send(
+      'add issuer',
+      sdk.buildAddIssuerInstruction(authority.publicKey, issuer, JURISDICTIONS, ATTESTATION_TYPES),
+    );
+  } else {
+    const jurisdictions = [...new Set([...existing.jurisdictions, ...JURISDICTIONS])];
+    const attestationTypes = [...new Set([...existing.attestationTypes, ...ATTESTATION_TYPES])];
+    if (
+      jurisdictions.length !== existing.jurisdictions.length ||
+      attestationTypes.length !== existing.attestationTypes.length
+    ) {
+      await send(
+        'widen issuer rights',
+        sdk.buildUpdateIssuerInstruction(
+          authority.publicKey,
+          issuer,
+          jurisdictions,
+          attestationTypes,
+        ),
+      );
+    }
+  }
+
+  console.log('Program registry is set up.');
+}
+
+function issuerKey(): PublicKey {
+  if (process.env.ISSUER_PUBKEY) {
+    return new PublicKey(process.env.ISSUER_PUBKEY);
+  }
+  if (process.env.SOLANA_AUTHORITY_KEY) {
+    const secret = Uint8Array.from(JSON.parse(process.env.SOLANA_AUTHORITY_KEY));
+    return Keypair.fromSecretKey(secret).publicKey;
+  }
+  throw new Error('Set ISSUER_PUBKEY or SOLANA_AUTHORITY_KEY to the workflow service key');
+}
+
+main().catch((err) => {
+  console.error('Error:', err);
+  process.exit(1);
+});
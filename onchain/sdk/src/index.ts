@@ -2,7 +2,10 @@ import {
   PublicKey,
   Connection,
   TransactionInstruction,
+  AccountMeta,
   SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  Ed25519Program,
   Keypair,
   Transaction,
   sendAndConfirmTransaction,
@@ -13,9 +16,36 @@ import {
 // Program ID
 export const PROGRAM_ID = new PublicKey('52LCg2VXDYgam4yHkXEp2vN2psUmo6Q7rv5efRm7ic8c');
 
+// Upgradeable BPF loader (owner of the program's ProgramData account)
+export const BPF_LOADER_UPGRADEABLE_ID = new PublicKey('BPFLoaderUpgradeab1e11111111111111111111111');
+
 // Seeds
 export const STATE_SEED = Buffer.from('state');
 export const ATTESTATION_SEED = Buffer.from('attestation');
+export const ISSUER_SEED = Buffer.from('issuer');
+export const MULTISIG_SEED = Buffer.from('multisig');
+export const MULTISIG_VAULT_SEED = Buffer.from('multisig_vault');
+export const PROPOSAL_SEED = Buffer.from('proposal');
+export const TOMBSTONE_SEED = Buffer.from('tombstone');
+export const REMOVAL_SEED = Buffer.from('removal');
+export const WALLET_LINK_SEED = Buffer.from('wallet_link');
+export const SLOT_SEED = Buffer.from('slot');
+export const JURISDICTION_SEED = Buffer.from('jurisdiction');
+export const SCHEMA_SEED = Buffer.from('schema');
+
+// Domain separator for off-chain wallet consent messages
+export const CONSENT_MESSAGE_PREFIX = Buffer.from('auditswarm:consent:v1');
+
+/** Max wallets an attestation can list */
+export const MAX_WALLETS = 10;
+
+/**
+ * Max uniqueness slots a listed attestation holds, one per wallet and covered tax
+ * year, so that its wallet links and slots fit in one transaction. A single-year
+ * attestation lists up to 10 wallets, a three-year one up to 3. For more, issue
+ * one attestation per tax year, or a Merkle attestation where the type allows it.
+ */
+export const MAX_WALLET_SLOTS = 10;
 
 // Instruction discriminators (from IDL)
 const DISCRIMINATORS = {
@@ -23,26 +53,84 @@ const DISCRIMINATORS = {
   createAttestation: Buffer.from([49, 24, 67, 80, 12, 249, 96, 239]),
   updateStatus: Buffer.from([147, 215, 74, 174, 55, 191, 42, 0]),
   revokeAttestation: Buffer.from([12, 156, 103, 161, 194, 246, 211, 179]),
+  createMultisig: Buffer.from([148, 146, 240, 10, 226, 215, 167, 174]),
+  updateMultisig: Buffer.from([152, 192, 112, 152, 120, 184, 150, 59]),
+  proposeTransaction: Buffer.from([35, 204, 169, 240, 74, 70, 31, 236]),
+  approveTransaction: Buffer.from([224, 39, 88, 181, 36, 59, 155, 122]),
+  executeTransaction: Buffer.from([231, 173, 49, 91, 235, 24, 68, 19]),
+  cancelProposal: Buffer.from([106, 74, 128, 146, 19, 65, 39, 23]),
+  proposeAuthority: Buffer.from([20, 148, 236, 198, 76, 119, 99, 142]),
+  acceptAuthority: Buffer.from([107, 86, 198, 91, 33, 12, 107, 160]),
+  cancelAuthorityTransfer: Buffer.from([94, 131, 125, 184, 183, 24, 125, 229]),
+  addIssuer: Buffer.from([252, 97, 3, 221, 65, 162, 177, 32]),
+  updateIssuer: Buffer.from([9, 100, 234, 30, 84, 43, 30, 29]),
+  suspendIssuer: Buffer.from([121, 249, 30, 244, 208, 80, 95, 150]),
+  reactivateIssuer: Buffer.from([174, 162, 75, 94, 50, 88, 97, 99]),
+  removeIssuer: Buffer.from([0, 75, 88, 225, 4, 159, 167, 119]),
+  expireAttestation: Buffer.from([203, 224, 7, 22, 27, 65, 33, 215]),
+  expireAttestations: Buffer.from([174, 156, 94, 34, 205, 182, 74, 205]),
+  setTaxYearWindow: Buffer.from([121, 67, 247, 213, 144, 224, 183, 99]),
+  supersedeAttestation: Buffer.from([23, 86, 80, 121, 45, 176, 139, 25]),
+  renewAttestation: Buffer.from([209, 173, 109, 25, 255, 94, 203, 222]),
+  suspendAttestation: Buffer.from([86, 7, 1, 233, 212, 127, 136, 160]),
+  reinstateAttestation: Buffer.from([174, 166, 63, 168, 164, 157, 225, 5]),
+  acknowledgeAttestation: Buffer.from([86, 68, 186, 44, 69, 25, 78, 49]),
+  activateAttestation: Buffer.from([134, 55, 159, 106, 146, 208, 62, 170]),
+  createMerkleAttestation: Buffer.from([95, 18, 173, 61, 92, 17, 220, 83]),
+  verifyWalletInclusion: Buffer.from([230, 32, 1, 215, 140, 83, 111, 229]),
+  coversTaxYear: Buffer.from([135, 250, 224, 195, 49, 19, 147, 236]),
+  addWallets: Buffer.from([49, 183, 121, 147, 232, 114, 93, 217]),
+  removeWallets: Buffer.from([30, 185, 226, 136, 42, 236, 225, 242]),
+  requestRemoval: Buffer.from([161, 111, 142, 117, 54, 0, 100, 239]),
+  resolveRemoval: Buffer.from([139, 70, 3, 122, 223, 62, 34, 128]),
+  closeAttestation: Buffer.from([249, 84, 133, 23, 48, 175, 252, 221]),
+  setRetentionPeriod: Buffer.from([163, 157, 127, 21, 233, 129, 157, 31]),
+  addJurisdiction: Buffer.from([238, 100, 145, 62, 7, 134, 30, 128]),
+  removeJurisdiction: Buffer.from([201, 152, 185, 58, 199, 157, 247, 159]),
+  setJurisdictionSubdivisions: Buffer.from([253, 152, 83, 47, 19, 170, 153, 249]),
+  setFiscalCalendar: Buffer.from([178, 163, 146, 244, 176, 134, 194, 87]),
+  addSchema: Buffer.from([133, 191, 60, 139, 221, 213, 46, 170]),
+  deprecateSchema: Buffer.from([17, 211, 95, 93, 93, 28, 134, 203]),
+  revokeLegacyAttestation: Buffer.from([179, 37, 252, 144, 135, 136, 184, 115]),
+  expireLegacyAttestation: Buffer.from([241, 10, 82, 212, 108, 174, 245, 192]),
+  closeLegacyAttestation: Buffer.from([184, 155, 136, 87, 171, 7, 34, 225]),
 };
 
+// Offsets into an Attestation account: discriminator(8) + bump(1), then authority(32),
+// jurisdiction(2), attestation_type(1), status(1) and tax_year(2) before the audit hash
+const AUTHORITY_OFFSET = 9;
+const AUDIT_HASH_OFFSET = 47;
+// Legacy-derived accounts predate 2-byte jurisdiction codes
+const LEGACY_AUDIT_HASH_OFFSET = 46;
+
 // Account discriminators (from IDL)
 const ACCOUNT_DISCRIMINATORS = {
   attestation: Buffer.from([152, 125, 183, 86, 36, 146, 121, 73]),
   programState: Buffer.from([77, 209, 137, 229, 149, 67, 167, 230]),
+  issuer: Buffer.from([216, 19, 83, 230, 108, 53, 80, 14]),
+  multisig: Buffer.from([224, 116, 121, 186, 68, 161, 79, 236]),
+  proposal: Buffer.from([26, 94, 189, 187, 116, 136, 53, 33]),
+  tombstone: Buffer.from([45, 187, 252, 155, 232, 114, 36, 22]),
+  removalRequest: Buffer.from([61, 244, 144, 37, 99, 246, 0, 161]),
+  walletAttestationLink: Buffer.from([147, 129, 33, 115, 209, 200, 0, 145]),
+  attestationSlot: Buffer.from([80, 97, 5, 55, 135, 210, 118, 161]),
+  jurisdictionConfig: Buffer.from([186, 40, 247, 64, 96, 82, 52, 38]),
+  attestationSchema: Buffer.from([42, 55, 162, 104, 105, 48, 77, 56]),
 };
 
+/**
+ * ISO 3166-1 alpha-2 code, e.g. 'US'. Attestations can only be created for codes
+ * the program authority has registered with addJurisdiction.
+ */
+export type Jurisdiction = string;
+
+/**
+ * ISO 3166-2 subdivision code within a jurisdiction, without the country prefix:
+ * 'CA' for US-CA. It must be on the jurisdiction's registered subdivision list.
+ */
+export type Subdivision = string;
+
 // Enums
-export enum Jurisdiction {
-  US = 0,
-  EU = 1,
-  BR = 2,
-  UK = 3,
-  JP = 4,
-  AU = 5,
-  CA = 6,
-  CH = 7,
-  SG = 8,
-}
 
 export enum AttestationType {
   TaxCompliance = 0,
@@ -57,9 +145,73 @@ export enum AttestationStatus {
   Active = 1,
   Expired = 2,
   Revoked = 3,
+  Superseded = 4,
+  Suspended = 5,
+}
+
+export enum SuspensionReason {
+  DiscrepancyUnderReview = 0,
+  PendingDocumentation = 1,
+  ClientDispute = 2,
+  RegulatoryInquiry = 3,
+  Other = 4,
+}
+
+export enum RevocationReason {
+  IssuerError = 0,
+  ClientRequest = 1,
+  Fraud = 2,
+  Superseded = 3,
+  RegulatoryOrder = 4,
+  Other = 5,
+}
+
+export enum RemovalResolution {
+  Revoke = 0,
+  Amend = 1,
+  Reject = 2,
+}
+
+export enum IssuerStatus {
+  Active = 0,
+  Suspended = 1,
+}
+
+/** Length of the fiscal period an attestation covers */
+export enum PeriodKind {
+  Annual = 0,
+  Quarterly = 1,
+  Monthly = 2,
+  /** Any non-empty range, e.g. a partial year after a change of residence */
+  Custom = 3,
+}
+
+/** Flags for AttestationSchemaData.requiredFields */
+export enum SchemaField {
+  /** The attestation must name a subdivision */
+  Subdivision = 1 << 0,
+  /** The attestation must require wallet consent */
+  WalletConsent = 1 << 1,
+  /** The attestation must list its wallets rather than commit to a Merkle root */
+  ListedWallets = 1 << 2,
 }
 
 // Interfaces
+
+/**
+ * Fiscal period covered by an attestation, [start, end) in Unix seconds. It must
+ * start in the fiscal year of the attestation's taxYear and end in that of its
+ * endTaxYear, where a tax year is labelled by the calendar year its fiscal year
+ * starts in (see fiscalYearPeriod). Annual, quarterly and monthly periods start at
+ * midnight UTC on the jurisdiction's fiscal year start day and run exactly one
+ * period.
+ */
+export interface FiscalPeriod {
+  kind: PeriodKind;
+  start: number;
+  end: number;
+}
+
 export interface AttestationData {
   bump: number;
   authority: PublicKey;
@@ -71,6 +223,69 @@ export interface AttestationData {
   issuedAt: bigint;
   expiresAt: bigint;
   revokedAt: bigint;
+  /** Attestation this one amends, or null for an original */
+  supersedes: PublicKey | null;
+  /** Amendment that replaced this one, or null while current */
+  supersededBy: PublicKey | null;
+  renewedAt: bigint;
+  renewalCount: number;
+  /** Hash of the latest re-review document, or null if never renewed with one */
+  reviewHash: Uint8Array | null;
+  suspendedAt: bigint;
+  reinstatedAt: bigint;
+  /** Reason for the most recent suspension, or null if never suspended */
+  suspensionReason: SuspensionReason | null;
+  /** Reason recorded at revocation, or null if not revoked */
+  revocationReason: RevocationReason | null;
+  /** Hash of the document backing the revocation, or null if none was given */
+  revocationHash: Uint8Array | null;
+  /** Whether every listed wallet must acknowledge before the attestation is Active */
+  consentRequired: boolean;
+  /** Held Pending by the issuer until activateAttestation */
+  activationPending: boolean;
+  /** Bit i is set once wallets[i] has acknowledged */
+  acknowledged: number;
+  /** Bit i is set while wallets[i] has an open removal request */
+  contestedBy: number;
+  /** Merkle root of the covered wallets, or null when they are listed in `wallets` */
+  walletRoot: Uint8Array | null;
+  /** Number of covered wallets, listed or under walletRoot */
+  walletCount: number;
+  /** Issuer's sequence number for this attestation, part of its PDA seeds */
+  sequence: bigint;
+  /** Subdivision within the jurisdiction, or null when it covers all of it */
+  subdivision: Subdivision | null;
+  /** Schema the attestation was issued under */
+  schemaId: number;
+  schemaVersion: number;
+  /** Fiscal period covered, [periodStart, periodEnd) in Unix seconds */
+  periodKind: PeriodKind;
+  periodStart: bigint;
+  periodEnd: bigint;
+  /** Last tax year covered; equal to taxYear for a single-year attestation */
+  endTaxYear: number;
+  numWallets: number;
+  wallets: PublicKey[];
+}
+
+/**
+ * An attestation still in the original layout, at its legacy
+ * ["attestation", auditHash] address. Only the *LegacyAttestation instructions
+ * accept it.
+ */
+export interface LegacyAttestationData {
+  bump: number;
+  authority: PublicKey;
+  /** Code of the original jurisdiction enum, with UK reported as GB */
+  jurisdiction: Jurisdiction;
+  attestationType: AttestationType;
+  /** Pending, Active, Expired or Revoked */
+  status: AttestationStatus;
+  taxYear: number;
+  auditHash: Uint8Array;
+  issuedAt: bigint;
+  expiresAt: bigint;
+  revokedAt: bigint;
   numWallets: number;
   wallets: PublicKey[];
 }
@@ -79,27 +294,211 @@ export interface ProgramStateData {
   authority: PublicKey;
   attestationCount: bigint;
   bump: number;
+  /** Proposed authority awaiting acceptance, or null when none */
+  pendingAuthority: PublicKey | null;
+  /** Inclusive tax year window accepted by createAttestation */
+  minTaxYear: number;
+  maxTaxYear: number;
+  /** Seconds a revoked or expired attestation is kept before it can be closed */
+  retentionPeriod: bigint;
+}
+
+/** An open removal request; resolving it closes the account */
+export interface RemovalRequestData {
+  bump: number;
+  attestation: PublicKey;
+  wallet: PublicKey;
+  reasonHash: Uint8Array;
+  requestedAt: bigint;
+}
+
+export interface TombstoneData {
+  bump: number;
+  auditHash: Uint8Array;
+  /** Status of the attestation when it was closed (Revoked or Expired) */
+  finalStatus: AttestationStatus;
+  closedAt: bigint;
+}
+
+export interface WalletAttestationLinkData {
+  bump: number;
+  wallet: PublicKey;
+  attestation: PublicKey;
+  linkedAt: bigint;
+  /** When the attestation was revoked (0 if it has not been) */
+  revokedAt: bigint;
+}
+
+export interface AttestationSlotData {
+  bump: number;
+  wallet: PublicKey;
+  jurisdiction: Jurisdiction;
+  subdivision: Subdivision | null;
+  attestationType: AttestationType;
+  taxYear: number;
+  /** Start of the quarter or month; 0 for annual and custom periods */
+  periodStart: bigint;
+  /** Attestation holding the slot, or null when free */
+  attestation: PublicKey | null;
+  claimedAt: bigint;
+  /** Holder's expiry; past it another attestation may claim the slot */
+  expiresAt: bigint;
+}
+
+/**
+ * An attestation's listed wallets and the fields that key their uniqueness slots.
+ * A fetched AttestationData can be passed as is.
+ */
+export type ListedWallets = Pick<
+  AttestationData,
+  | 'wallets'
+  | 'jurisdiction'
+  | 'subdivision'
+  | 'attestationType'
+  | 'taxYear'
+  | 'endTaxYear'
+  | 'periodKind'
+  | 'periodStart'
+>;
+
+export interface JurisdictionConfigData {
+  bump: number;
+  code: Jurisdiction;
+  registeredAt: bigint;
+  /** Month (1-12) and day (1-28) the fiscal year starts on */
+  fiscalYearStartMonth: number;
+  fiscalYearStartDay: number;
+  /** Subdivisions attestations for this jurisdiction may name */
+  subdivisions: Subdivision[];
+}
+
+/** Identifies one version of an attestation schema */
+export interface SchemaRef {
+  id: number;
+  version: number;
+}
+
+export interface AttestationSchemaData {
+  bump: number;
+  id: number;
+  version: number;
+  /** SHA-256 of the schema's human-readable name */
+  nameHash: Uint8Array;
+  attestationType: AttestationType;
+  /** SchemaField flags */
+  requiredFields: number;
+  /** Seconds an attestation created with expiresAt 0 stays valid (0 for none) */
+  defaultValidity: bigint;
+  /** Jurisdictions the schema may be used for; empty for any */
+  jurisdictions: Jurisdiction[];
+  deprecated: boolean;
+  createdAt: bigint;
+}
+
+export interface AddSchemaParams extends SchemaRef {
+  nameHash: Buffer;
+  attestationType: AttestationType;
+  requiredFields?: number;
+  defaultValidity?: number;
+  jurisdictions?: Jurisdiction[];
+}
+
+export interface IssuerData {
+  bump: number;
+  authority: PublicKey;
+  status: IssuerStatus;
+  jurisdictions: Jurisdiction[];
+  attestationTypes: AttestationType[];
+  attestationCount: bigint;
+  addedAt: bigint;
+  /** Sequence number the issuer's next attestation is created under */
+  nextSequence: bigint;
+}
+
+export interface MultisigData {
+  threshold: number;
+  signers: PublicKey[];
+  proposalCount: bigint;
+  vaultBump: number;
+  bump: number;
 }
 
 export interface CreateAttestationParams {
   authority: Keypair;
   jurisdiction: Jurisdiction;
+  /** Narrow the attestation to one subdivision of the jurisdiction */
+  subdivision?: Subdivision;
   attestationType: AttestationType;
+  /** Schema to issue under; it must be of attestationType */
+  schema: SchemaRef;
+  /** First tax year covered */
   taxYear: number;
+  /**
+   * Last tax year covered (at most 10 years on); defaults to taxYear. Wallets
+   * times covered years may not exceed MAX_WALLET_SLOTS.
+   */
+  endTaxYear?: number;
+  /** Fiscal period covered; see fiscalYearPeriod */
+  period: FiscalPeriod;
   auditHash: Buffer;
+  /** 0 takes the schema's default validity */
   expiresAt: number;
   wallets: PublicKey[];
+  /** Keep the attestation Pending until every wallet has acknowledged it */
+  requireConsent?: boolean;
+  /** Pending holds the attestation until activateAttestation (default Active) */
+  initialStatus?: AttestationStatus.Pending | AttestationStatus.Active;
 }
 
-export interface UpdateStatusParams {
+/** Merkle attestations cannot be TaxCompliance, which must list its wallets */
+export interface CreateMerkleAttestationParams {
+  authority: Keypair;
+  jurisdiction: Jurisdiction;
+  subdivision?: Subdivision;
+  attestationType: AttestationType;
+  schema: SchemaRef;
+  taxYear: number;
+  endTaxYear?: number;
+  period: FiscalPeriod;
+  auditHash: Buffer;
+  expiresAt: number;
+  /** Root of the wallet tree built as in programs/attestation/src/merkle.rs */
+  walletRoot: Buffer;
+  walletCount: number;
+  initialStatus?: AttestationStatus.Pending | AttestationStatus.Active;
+}
+
+export interface SupersedeAttestationParams {
   authority: Keypair;
+  /** Address of the attestation being amended */
+  previousAttestation: PublicKey;
+  /** Schema to issue the amendment under; it must be of the previous attestation's type */
+  schema: SchemaRef;
   auditHash: Buffer;
+  /** 0 takes the schema's default validity */
+  expiresAt: number;
+  /** Times the previous attestation's tax years, at most MAX_WALLET_SLOTS */
+  wallets: PublicKey[];
+}
+
+export interface RenewAttestationParams {
+  authority: Keypair;
+  attestation: PublicKey;
+  newExpiresAt: number;
+  reviewHash?: Buffer;
+}
+
+export interface UpdateStatusParams {
+  authority: Keypair;
+  attestation: PublicKey;
   newStatus: AttestationStatus;
 }
 
 export interface RevokeAttestationParams {
   authority: Keypair;
-  auditHash: Buffer;
+  attestation: PublicKey;
+  reason: RevocationReason;
+  reasonHash?: Buffer;
 }
 
 /**
@@ -110,19 +509,296 @@ export function getStatePDA(programId: PublicKey = PROGRAM_ID): [PublicKey, numb
 }
 
 /**
- * Get the PDA for an attestation by audit hash.
- * Seeds: ["attestation", auditHash] where auditHash is 32 bytes.
+ * Get the ProgramData address holding the program's upgrade authority.
+ */
+export function getProgramDataAddress(programId: PublicKey = PROGRAM_ID): PublicKey {
+  return PublicKey.findProgramAddressSync([programId.toBuffer()], BPF_LOADER_UPGRADEABLE_ID)[0];
+}
+
+/**
+ * Get the PDA for an attestation by its issuer and the issuer's sequence number.
+ * Seeds: ["attestation", authority, sequence] where sequence is a u64 LE.
  */
 export function getAttestationPDA(
+  authority: PublicKey,
+  sequence: number | bigint,
+  programId: PublicKey = PROGRAM_ID,
+): [PublicKey, number] {
+  const sequenceBuf = Buffer.alloc(8);
+  sequenceBuf.writeBigUInt64LE(BigInt(sequence));
+  return PublicKey.findProgramAddressSync(
+    [ATTESTATION_SEED, authority.toBuffer(), sequenceBuf],
+    programId,
+  );
+}
+
+/**
+ * Get the address an attestation had under the original, global scheme.
+ * Seeds: ["attestation", auditHash]. The program no longer creates attestations
+ * here; this is kept for migration tooling.
+ */
+export function getLegacyAttestationPDA(
   auditHash: Buffer,
   programId: PublicKey = PROGRAM_ID,
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([ATTESTATION_SEED, auditHash], programId);
+}
+
+/** Seed scheme an attestation account's address was derived with */
+export enum AttestationDerivation {
+  /** ["attestation", auditHash] */
+  Legacy = 'legacy',
+  /** ["attestation", authority, sequence] */
+  Namespaced = 'namespaced',
+}
+
+/**
+ * Tell which seed scheme `address` was derived with, given the account's data,
+ * or null if it matches neither. The legacy check only reads the audit hash, at
+ * its offset in the layouts that were current under the legacy scheme.
+ */
+export function getAttestationDerivation(
+  address: PublicKey,
+  data: Buffer,
+  programId: PublicKey = PROGRAM_ID,
+): AttestationDerivation | null {
+  const auditHash = data.slice(LEGACY_AUDIT_HASH_OFFSET, LEGACY_AUDIT_HASH_OFFSET + 32);
+  if (getLegacyAttestationPDA(auditHash, programId)[0].equals(address)) {
+    return AttestationDerivation.Legacy;
+  }
+
+  let attestation: AttestationData;
+  try {
+    attestation = parseAttestationData(data);
+  } catch {
+    return null;
+  }
+  const [namespaced] = getAttestationPDA(attestation.authority, attestation.sequence, programId);
+  return namespaced.equals(address) ? AttestationDerivation.Namespaced : null;
+}
+
+/**
+ * Get the PDA of the tombstone left when an attestation is closed.
+ * Seeds: ["tombstone", attestation].
+ */
+export function getTombstonePDA(
+  attestation: PublicKey,
+  programId: PublicKey = PROGRAM_ID,
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([TOMBSTONE_SEED, attestation.toBuffer()], programId);
+}
+
+/**
+ * Get the PDA of a wallet's removal request against an attestation.
+ * Seeds: ["removal", attestation, wallet].
+ */
+export function getRemovalRequestPDA(
+  attestation: PublicKey,
+  wallet: PublicKey,
+  programId: PublicKey = PROGRAM_ID,
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [REMOVAL_SEED, attestation.toBuffer(), wallet.toBuffer()],
+    programId,
+  );
+}
+
+/**
+ * Get the PDA of the reverse-index link from a listed wallet to an attestation.
+ * Seeds: ["wallet_link", wallet, attestation].
+ */
+export function getWalletLinkPDA(
+  wallet: PublicKey,
+  attestation: PublicKey,
+  programId: PublicKey = PROGRAM_ID,
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [WALLET_LINK_SEED, wallet.toBuffer(), attestation.toBuffer()],
+    programId,
+  );
+}
+
+/**
+ * Get the PDA of a wallet's uniqueness slot for a jurisdiction and subdivision, tax
+ * year, type and period (see getSlotPeriod).
+ * Seeds: ["slot", wallet, jurisdiction, subdivision, taxYear (u16 LE), attestationType,
+ * period (i64 LE)].
+ */
+export function getAttestationSlotPDA(
+  wallet: PublicKey,
+  jurisdiction: Jurisdiction,
+  subdivision: Subdivision | null,
+  taxYear: number,
+  attestationType: AttestationType,
+  period: bigint,
+  programId: PublicKey = PROGRAM_ID,
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      SLOT_SEED,
+      wallet.toBuffer(),
+      serializeJurisdiction(jurisdiction),
+      serializeSubdivision(subdivision),
+      serializeU16LE(taxYear),
+      serializeEnum(attestationType),
+      serializeI64LE(period),
+    ],
+    programId,
+  );
+}
+
+/**
+ * Period part of a uniqueness slot's seeds: quarters and months of a tax year each
+ * have their own slot, keyed by their start; annual and custom periods share slot 0.
+ */
+export function getSlotPeriod(kind: PeriodKind, periodStart: number | bigint): bigint {
+  return kind === PeriodKind.Quarterly || kind === PeriodKind.Monthly ? BigInt(periodStart) : 0n;
+}
+
+/**
+ * Annual fiscal period starting in `year` on a jurisdiction's fiscal calendar
+ * (JurisdictionConfigData.fiscalYearStartMonth and fiscalYearStartDay); the
+ * calendar year by default. This is the period of tax year `year`: a July-June
+ * fiscal year running from July 2024 is tax year 2024. `years` > 1 spans a
+ * multi-year attestation.
+ */
+export function fiscalYearPeriod(
+  year: number,
+  startMonth = 1,
+  startDay = 1,
+  years = 1,
+): FiscalPeriod {
+  return {
+    kind: PeriodKind.Annual,
+    start: Date.UTC(year, startMonth - 1, startDay) / 1000,
+    end: Date.UTC(year + years, startMonth - 1, startDay) / 1000,
+  };
+}
+
+/** Tax years an attestation covers, taxYear through endTaxYear */
+export function getCoveredTaxYears(
+  attestation: Pick<AttestationData, 'taxYear' | 'endTaxYear'>,
+): number[] {
+  const { taxYear, endTaxYear } = attestation;
+  return Array.from({ length: endTaxYear - taxYear + 1 }, (_, i) => taxYear + i);
+}
+
+/**
+ * Get the PDA for an issuer registry entry.
+ * Seeds: ["issuer", issuerAuthority].
+ */
+export function getIssuerPDA(
+  issuerAuthority: PublicKey,
+  programId: PublicKey = PROGRAM_ID,
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([ISSUER_SEED, issuerAuthority.toBuffer()], programId);
+}
+
+/**
+ * Get the PDA of a jurisdiction registry entry.
+ * Seeds: ["jurisdiction", code].
+ */
+export function getJurisdictionConfigPDA(
+  code: Jurisdiction,
+  programId: PublicKey = PROGRAM_ID,
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [JURISDICTION_SEED, serializeJurisdiction(code)],
+    programId,
+  );
+}
+
+/**
+ * Get the PDA of an attestation schema version.
+ * Seeds: ["schema", id (u32 LE), version (u16 LE)].
+ */
+export function getSchemaPDA(
+  id: number,
+  version: number,
+  programId: PublicKey = PROGRAM_ID,
 ): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
-    [ATTESTATION_SEED, auditHash],
+    [SCHEMA_SEED, serializeU32LE(id), serializeU16LE(version)],
     programId,
   );
 }
 
+/**
+ * Get the PDA for the authority multisig.
+ */
+export function getMultisigPDA(programId: PublicKey = PROGRAM_ID): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([MULTISIG_SEED], programId);
+}
+
+/**
+ * Get the multisig vault PDA. Once a multisig exists this is the program
+ * authority; it signs only through executed proposals.
+ */
+export function getMultisigVaultPDA(programId: PublicKey = PROGRAM_ID): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([MULTISIG_VAULT_SEED], programId);
+}
+
+/**
+ * Get the PDA for a multisig proposal.
+ * Seeds: ["proposal", index] where index is a u64 LE.
+ */
+export function getProposalPDA(
+  index: number | bigint,
+  programId: PublicKey = PROGRAM_ID,
+): [PublicKey, number] {
+  const indexBuf = Buffer.alloc(8);
+  indexBuf.writeBigUInt64LE(BigInt(index));
+  return PublicKey.findProgramAddressSync([PROPOSAL_SEED, indexBuf], programId);
+}
+
+/**
+ * Build the message a wallet signs to consent to an attestation:
+ * prefix || wallet (32) || attestation (32) || auditHash (32) || attestationType (1)
+ * || jurisdiction (2) || taxYear (u16 LE) || endTaxYear (u16 LE).
+ * `attestation` is the PDA it will be created at, from the issuer's authority and
+ * its current `nextSequence` (see getAttestationPDA), so the consent only counts
+ * for that one attestation.
+ */
+export function buildConsentMessage(
+  wallet: PublicKey,
+  attestation: PublicKey,
+  auditHash: Buffer,
+  attestationType: AttestationType,
+  jurisdiction: Jurisdiction,
+  taxYear: number,
+  endTaxYear = taxYear,
+): Buffer {
+  if (auditHash.length !== 32) {
+    throw new Error('auditHash must be exactly 32 bytes');
+  }
+  return Buffer.concat([
+    CONSENT_MESSAGE_PREFIX,
+    wallet.toBuffer(),
+    attestation.toBuffer(),
+    auditHash,
+    serializeEnum(attestationType),
+    serializeJurisdiction(jurisdiction),
+    serializeU16LE(taxYear),
+    serializeU16LE(endTaxYear),
+  ]);
+}
+
+/**
+ * Build the Ed25519SigVerify instruction that carries a wallet's signed consent.
+ * Place it before createAttestation in the same transaction.
+ */
+export function buildConsentVerifyInstruction(
+  wallet: PublicKey,
+  signature: Uint8Array,
+  message: Buffer,
+): TransactionInstruction {
+  return Ed25519Program.createInstructionWithPublicKey({
+    publicKey: wallet.toBytes(),
+    message,
+    signature,
+  });
+}
+
 // -- Serialization helpers --
 
 function serializeEnum(value: number): Buffer {
@@ -130,6 +806,60 @@ function serializeEnum(value: number): Buffer {
   return Buffer.from([value]);
 }
 
+function serializeJurisdiction(code: Jurisdiction): Buffer {
+  // [u8; 2]: the code's two ASCII letters
+  if (!/^[A-Z]{2}$/.test(code)) {
+    throw new Error(`Invalid ISO 3166-1 alpha-2 code: ${code}`);
+  }
+  return Buffer.from(code, 'ascii');
+}
+
+function serializeSubdivision(code?: Subdivision | null): Buffer {
+  // [u8; 3]: 1-3 letters or digits, zero-padded; all zeros for none
+  const buf = Buffer.alloc(3);
+  if (code) {
+    if (!/^[A-Z0-9]{1,3}$/.test(code)) {
+      throw new Error(`Invalid ISO 3166-2 subdivision code: ${code}`);
+    }
+    buf.write(code, 'ascii');
+  }
+  return buf;
+}
+
+function readSubdivision(data: Buffer, offset: number): Subdivision | null {
+  const code = data.slice(offset, offset + 3).toString('ascii').replace(/\0+$/, '');
+  return code.length > 0 ? code : null;
+}
+
+function serializePeriod(period: FiscalPeriod): Buffer[] {
+  // period_kind (1) + period_start (i64 LE) + period_end (i64 LE)
+  return [serializeEnum(period.kind), serializeI64LE(period.start), serializeI64LE(period.end)];
+}
+
+// The program's limits on listed wallets, checked before sending
+function checkWalletSlots(walletCount: number, taxYear: number, endTaxYear: number): void {
+  if (walletCount < 1 || walletCount > MAX_WALLETS) {
+    throw new Error(`wallets must contain 1-${MAX_WALLETS} entries`);
+  }
+  const years = endTaxYear - taxYear + 1;
+  if (walletCount * years > MAX_WALLET_SLOTS) {
+    throw new Error(
+      `${walletCount} wallets over ${years} tax years need ${walletCount * years} slots; ` +
+        `at most ${MAX_WALLET_SLOTS} are allowed`,
+    );
+  }
+}
+
+function serializeBool(value: boolean): Buffer {
+  return Buffer.from([value ? 1 : 0]);
+}
+
+function serializeU32LE(value: number): Buffer {
+  const buf = Buffer.alloc(4);
+  buf.writeUInt32LE(value);
+  return buf;
+}
+
 function serializeU16LE(value: number): Buffer {
   const buf = Buffer.alloc(2);
   buf.writeUInt16LE(value);
@@ -149,21 +879,140 @@ function serializeVecPubkey(keys: PublicKey[]): Buffer {
   return Buffer.concat([lenBuf, ...keys.map((k) => k.toBuffer())]);
 }
 
-// -- Account parsing helpers --
+function serializeVecEnum(values: number[]): Buffer {
+  // Vec<enum>: 4-byte LE length prefix + one byte per variant index
+  const lenBuf = Buffer.alloc(4);
+  lenBuf.writeUInt32LE(values.length);
+  return Buffer.concat([lenBuf, Buffer.from(values)]);
+}
 
-function parseAttestationData(data: Buffer): AttestationData {
-  // Skip 8-byte account discriminator
-  let offset = 8;
+function serializeVecJurisdiction(codes: Jurisdiction[]): Buffer {
+  // Vec<[u8; 2]>: 4-byte LE length prefix + two bytes per code
+  const lenBuf = Buffer.alloc(4);
+  lenBuf.writeUInt32LE(codes.length);
+  return Buffer.concat([lenBuf, ...codes.map(serializeJurisdiction)]);
+}
 
-  const bump = data[offset];
-  offset += 1;
+function serializeVecBytes(bytes: Buffer): Buffer {
+  // Vec<u8>: 4-byte LE length prefix + raw bytes
+  const lenBuf = Buffer.alloc(4);
+  lenBuf.writeUInt32LE(bytes.length);
+  return Buffer.concat([lenBuf, bytes]);
+}
 
-  const authority = new PublicKey(data.slice(offset, offset + 32));
+function serializeProposalAccounts(ix: TransactionInstruction): Buffer {
+  // Vec<ProposalAccount>: 4-byte LE length + N * (pubkey(32) + is_signer(1) + is_writable(1))
+  const lenBuf = Buffer.alloc(4);
+  lenBuf.writeUInt32LE(ix.keys.length);
+  return Buffer.concat([
+    lenBuf,
+    ...ix.keys.map((k) =>
+      Buffer.concat([k.pubkey.toBuffer(), Buffer.from([k.isSigner ? 1 : 0, k.isWritable ? 1 : 0])]),
+    ),
+  ]);
+}
+
+function serializeOptionBytes32(value?: Buffer): Buffer {
+  // Option<[u8; 32]>: 1-byte tag, followed by the bytes when Some
+  return value ? Buffer.concat([Buffer.from([1]), value]) : Buffer.from([0]);
+}
+
+// Variant order of the original on-chain Jurisdiction enum
+const LEGACY_JURISDICTIONS: Jurisdiction[] = ['US', 'EU', 'BR', 'GB', 'JP', 'AU', 'CA', 'CH', 'SG'];
+
+function readOptionalPubkey(data: Buffer, offset: number): PublicKey | null {
+  // Pubkey::default() marks an unset reference on-chain
+  const key = new PublicKey(data.slice(offset, offset + 32));
+  return key.equals(PublicKey.default) ? null : key;
+}
+
+// -- Account parsing helpers --
+
+/**
+ * Parse an attestation account written in the original layout. Use
+ * getAttestationDerivation to tell such accounts apart first: the bytes of a
+ * current-layout account do not fail to parse, they parse wrong.
+ */
+export function parseLegacyAttestationData(data: Buffer): LegacyAttestationData {
+  // Skip 8-byte account discriminator
+  let offset = 8;
+
+  const bump = data[offset];
+  offset += 1;
+
+  const authority = new PublicKey(data.slice(offset, offset + 32));
+  offset += 32;
+
+  const jurisdiction = LEGACY_JURISDICTIONS[data[offset]];
+  if (!jurisdiction) {
+    throw new Error(`Unknown legacy jurisdiction index: ${data[offset]}`);
+  }
+  offset += 1;
+
+  const attestationType = data[offset] as AttestationType;
+  offset += 1;
+
+  const status = data[offset] as AttestationStatus;
+  offset += 1;
+
+  const taxYear = data.readUInt16LE(offset);
+  offset += 2;
+
+  const auditHash = new Uint8Array(data.slice(offset, offset + 32));
   offset += 32;
 
-  const jurisdiction = data[offset] as Jurisdiction;
+  const issuedAt = data.readBigInt64LE(offset);
+  offset += 8;
+
+  const expiresAt = data.readBigInt64LE(offset);
+  offset += 8;
+
+  const revokedAt = data.readBigInt64LE(offset);
+  offset += 8;
+
+  const numWallets = data[offset];
+  offset += 1;
+
+  // Vec<Pubkey>: 4-byte LE length + N * 32 bytes
+  const vecLen = data.readUInt32LE(offset);
+  offset += 4;
+
+  const wallets: PublicKey[] = [];
+  for (let i = 0; i < vecLen; i++) {
+    wallets.push(new PublicKey(data.slice(offset, offset + 32)));
+    offset += 32;
+  }
+
+  return {
+    bump,
+    authority,
+    jurisdiction,
+    attestationType,
+    status,
+    taxYear,
+    auditHash,
+    issuedAt,
+    expiresAt,
+    revokedAt,
+    numWallets,
+    wallets,
+  };
+}
+
+/** Parse an attestation account in the current layout */
+export function parseAttestationData(data: Buffer): AttestationData {
+  // Skip 8-byte account discriminator
+  let offset = 8;
+
+  const bump = data[offset];
   offset += 1;
 
+  const authority = new PublicKey(data.slice(offset, offset + 32));
+  offset += 32;
+
+  const jurisdiction = data.slice(offset, offset + 2).toString('ascii');
+  offset += 2;
+
   const attestationType = data[offset] as AttestationType;
   offset += 1;
 
@@ -185,6 +1034,93 @@ function parseAttestationData(data: Buffer): AttestationData {
   const revokedAt = data.readBigInt64LE(offset);
   offset += 8;
 
+  const supersedes = readOptionalPubkey(data, offset);
+  offset += 32;
+
+  const supersededBy = readOptionalPubkey(data, offset);
+  offset += 32;
+
+  const renewedAt = data.readBigInt64LE(offset);
+  offset += 8;
+
+  const renewalCount = data.readUInt16LE(offset);
+  offset += 2;
+
+  const reviewHashBytes = new Uint8Array(data.slice(offset, offset + 32));
+  const reviewHash = reviewHashBytes.some((b) => b !== 0) ? reviewHashBytes : null;
+  offset += 32;
+
+  const suspendedAt = data.readBigInt64LE(offset);
+  offset += 8;
+
+  const reinstatedAt = data.readBigInt64LE(offset);
+  offset += 8;
+
+  // Option<SuspensionReason>: 1-byte tag, then the variant index when Some
+  let suspensionReason: SuspensionReason | null = null;
+  if (data[offset] === 1) {
+    suspensionReason = data[offset + 1] as SuspensionReason;
+    offset += 2;
+  } else {
+    offset += 1;
+  }
+
+  // Option<RevocationReason>
+  let revocationReason: RevocationReason | null = null;
+  if (data[offset] === 1) {
+    revocationReason = data[offset + 1] as RevocationReason;
+    offset += 2;
+  } else {
+    offset += 1;
+  }
+
+  const revocationHashBytes = new Uint8Array(data.slice(offset, offset + 32));
+  const revocationHash = revocationHashBytes.some((b) => b !== 0) ? revocationHashBytes : null;
+  offset += 32;
+
+  const consentRequired = data[offset] === 1;
+  offset += 1;
+
+  const activationPending = data[offset] === 1;
+  offset += 1;
+
+  const acknowledged = data.readUInt16LE(offset);
+  offset += 2;
+
+  const contestedBy = data.readUInt16LE(offset);
+  offset += 2;
+
+  const walletRootBytes = new Uint8Array(data.slice(offset, offset + 32));
+  const walletRoot = walletRootBytes.some((b) => b !== 0) ? walletRootBytes : null;
+  offset += 32;
+
+  const walletCount = data.readUInt32LE(offset);
+  offset += 4;
+
+  const sequence = data.readBigUInt64LE(offset);
+  offset += 8;
+
+  const subdivision = readSubdivision(data, offset);
+  offset += 3;
+
+  const schemaId = data.readUInt32LE(offset);
+  offset += 4;
+
+  const schemaVersion = data.readUInt16LE(offset);
+  offset += 2;
+
+  const periodKind = data[offset] as PeriodKind;
+  offset += 1;
+
+  const periodStart = data.readBigInt64LE(offset);
+  offset += 8;
+
+  const periodEnd = data.readBigInt64LE(offset);
+  offset += 8;
+
+  const endTaxYear = data.readUInt16LE(offset);
+  offset += 2;
+
   const numWallets = data[offset];
   offset += 1;
 
@@ -209,6 +1145,30 @@ function parseAttestationData(data: Buffer): AttestationData {
     issuedAt,
     expiresAt,
     revokedAt,
+    supersedes,
+    supersededBy,
+    renewedAt,
+    renewalCount,
+    reviewHash,
+    suspendedAt,
+    reinstatedAt,
+    suspensionReason,
+    revocationReason,
+    revocationHash,
+    consentRequired,
+    activationPending,
+    acknowledged,
+    contestedBy,
+    walletRoot,
+    walletCount,
+    sequence,
+    subdivision,
+    schemaId,
+    schemaVersion,
+    periodKind,
+    periodStart,
+    periodEnd,
+    endTaxYear,
     numWallets,
     wallets,
   };
@@ -225,272 +1185,1660 @@ function parseProgramStateData(data: Buffer): ProgramStateData {
   offset += 8;
 
   const bump = data[offset];
+  offset += 1;
+
+  const pending = new PublicKey(data.slice(offset, offset + 32));
+  const pendingAuthority = pending.equals(PublicKey.default) ? null : pending;
+  offset += 32;
+
+  const minTaxYear = data.readUInt16LE(offset);
+  offset += 2;
+
+  const maxTaxYear = data.readUInt16LE(offset);
+  offset += 2;
+
+  const retentionPeriod = data.readBigInt64LE(offset);
 
-  return { authority, attestationCount, bump };
+  return {
+    authority,
+    attestationCount,
+    bump,
+    pendingAuthority,
+    minTaxYear,
+    maxTaxYear,
+    retentionPeriod,
+  };
 }
 
-/**
- * AuditSwarm Attestation SDK
- *
- * Provides read and write operations for the on-chain attestation program.
- * Write operations build and send transactions using the provided keypair.
- * Read operations only require a Connection.
- */
-export class AuditSwarmSolana {
-  private connection: Connection;
-  private programId: PublicKey;
+function parseRemovalRequestData(data: Buffer): RemovalRequestData {
+  // Skip 8-byte account discriminator
+  let offset = 8;
 
-  constructor(connection: Connection, programId: PublicKey = PROGRAM_ID) {
-    this.connection = connection;
-    this.programId = programId;
-  }
+  const bump = data[offset];
+  offset += 1;
 
-  // ===================
-  // Write Operations
-  // ===================
+  const attestation = new PublicKey(data.slice(offset, offset + 32));
+  offset += 32;
 
-  /**
-   * Initialize the program state. Must be called once by the authority.
-   * Returns the transaction signature.
-   */
-  async initialize(authority: Keypair): Promise<string> {
-    const [statePDA] = getStatePDA(this.programId);
+  const wallet = new PublicKey(data.slice(offset, offset + 32));
+  offset += 32;
 
-    const ix = new TransactionInstruction({
-      programId: this.programId,
-      keys: [
-        { pubkey: statePDA, isSigner: false, isWritable: true },
-        { pubkey: authority.publicKey, isSigner: true, isWritable: true },
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-      ],
-      data: DISCRIMINATORS.initialize,
-    });
+  const reasonHash = new Uint8Array(data.slice(offset, offset + 32));
+  offset += 32;
 
-    const tx = new Transaction().add(ix);
-    return sendAndConfirmTransaction(this.connection, tx, [authority]);
-  }
+  const requestedAt = data.readBigInt64LE(offset);
 
-  /**
-   * Create a new attestation covering multiple wallets.
-   * Returns the transaction signature.
-   */
-  async createAttestation(params: CreateAttestationParams): Promise<string> {
-    const { authority, jurisdiction, attestationType, taxYear, auditHash, expiresAt, wallets } = params;
+  return {
+    bump,
+    attestation,
+    wallet,
+    reasonHash,
+    requestedAt,
+  };
+}
 
-    if (auditHash.length !== 32) {
-      throw new Error('auditHash must be exactly 32 bytes');
-    }
-    if (wallets.length < 1 || wallets.length > 10) {
-      throw new Error('wallets must contain 1-10 entries');
-    }
+function parseTombstoneData(data: Buffer): TombstoneData {
+  // Skip 8-byte account discriminator
+  let offset = 8;
 
-    const [statePDA] = getStatePDA(this.programId);
-    const [attestationPDA] = getAttestationPDA(auditHash, this.programId);
+  const bump = data[offset];
+  offset += 1;
 
-    // Serialize instruction data:
-    // discriminator(8) + jurisdiction(1) + attestation_type(1) + tax_year(2) + audit_hash(32) + expires_at(8) + wallets(4 + N*32)
-    const instructionData = Buffer.concat([
-      DISCRIMINATORS.createAttestation,
-      serializeEnum(jurisdiction),
-      serializeEnum(attestationType),
-      serializeU16LE(taxYear),
-      auditHash,
-      serializeI64LE(expiresAt),
-      serializeVecPubkey(wallets),
-    ]);
+  const auditHash = new Uint8Array(data.slice(offset, offset + 32));
+  offset += 32;
+
+  const finalStatus = data[offset] as AttestationStatus;
+  offset += 1;
+
+  const closedAt = data.readBigInt64LE(offset);
+
+  return { bump, auditHash, finalStatus, closedAt };
+}
+
+function parseWalletAttestationLinkData(data: Buffer): WalletAttestationLinkData {
+  // Skip 8-byte account discriminator
+  let offset = 8;
+
+  const bump = data[offset];
+  offset += 1;
+
+  const wallet = new PublicKey(data.slice(offset, offset + 32));
+  offset += 32;
+
+  const attestation = new PublicKey(data.slice(offset, offset + 32));
+  offset += 32;
+
+  const linkedAt = data.readBigInt64LE(offset);
+  offset += 8;
+
+  const revokedAt = data.readBigInt64LE(offset);
+
+  return { bump, wallet, attestation, linkedAt, revokedAt };
+}
+
+function parseAttestationSlotData(data: Buffer): AttestationSlotData {
+  // Skip 8-byte account discriminator
+  let offset = 8;
+
+  const bump = data[offset];
+  offset += 1;
+
+  const wallet = new PublicKey(data.slice(offset, offset + 32));
+  offset += 32;
+
+  const jurisdiction = data.slice(offset, offset + 2).toString('ascii');
+  offset += 2;
+
+  const subdivision = readSubdivision(data, offset);
+  offset += 3;
+
+  const attestationType = data[offset] as AttestationType;
+  offset += 1;
+
+  const taxYear = data.readUInt16LE(offset);
+  offset += 2;
+
+  const periodStart = data.readBigInt64LE(offset);
+  offset += 8;
+
+  const holder = new PublicKey(data.slice(offset, offset + 32));
+  const attestation = holder.equals(PublicKey.default) ? null : holder;
+  offset += 32;
+
+  const claimedAt = data.readBigInt64LE(offset);
+  offset += 8;
+
+  const expiresAt = data.readBigInt64LE(offset);
+
+  return {
+    bump,
+    wallet,
+    jurisdiction,
+    subdivision,
+    attestationType,
+    taxYear,
+    periodStart,
+    attestation,
+    claimedAt,
+    expiresAt,
+  };
+}
+
+function parseJurisdictionConfigData(data: Buffer): JurisdictionConfigData {
+  // Skip 8-byte account discriminator
+  let offset = 8;
+
+  const bump = data[offset];
+  offset += 1;
+
+  const code = data.slice(offset, offset + 2).toString('ascii');
+  offset += 2;
+
+  const registeredAt = data.readBigInt64LE(offset);
+  offset += 8;
+
+  const fiscalYearStartMonth = data[offset];
+  const fiscalYearStartDay = data[offset + 1];
+  offset += 2;
+
+  const subdivisionsLen = data.readUInt32LE(offset);
+  offset += 4;
+  const subdivisions: Subdivision[] = [];
+  for (let i = 0; i < subdivisionsLen; i++) {
+    subdivisions.push(readSubdivision(data, offset) as Subdivision);
+    offset += 3;
+  }
+
+  return { bump, code, registeredAt, fiscalYearStartMonth, fiscalYearStartDay, subdivisions };
+}
+
+function parseAttestationSchemaData(data: Buffer): AttestationSchemaData {
+  // Skip 8-byte account discriminator
+  let offset = 8;
+
+  const bump = data[offset];
+  offset += 1;
+
+  const id = data.readUInt32LE(offset);
+  offset += 4;
+
+  const version = data.readUInt16LE(offset);
+  offset += 2;
+
+  const nameHash = new Uint8Array(data.slice(offset, offset + 32));
+  offset += 32;
+
+  const attestationType = data[offset] as AttestationType;
+  offset += 1;
+
+  const requiredFields = data.readUInt16LE(offset);
+  offset += 2;
+
+  const defaultValidity = data.readBigInt64LE(offset);
+  offset += 8;
+
+  const jurisdictionsLen = data.readUInt32LE(offset);
+  offset += 4;
+  const jurisdictions: Jurisdiction[] = [];
+  for (let i = 0; i < jurisdictionsLen; i++) {
+    jurisdictions.push(data.slice(offset, offset + 2).toString('ascii'));
+    offset += 2;
+  }
+
+  const deprecated = data[offset] === 1;
+  offset += 1;
+
+  const createdAt = data.readBigInt64LE(offset);
+
+  return {
+    bump,
+    id,
+    version,
+    nameHash,
+    attestationType,
+    requiredFields,
+    defaultValidity,
+    jurisdictions,
+    deprecated,
+    createdAt,
+  };
+}
+
+function parseIssuerData(data: Buffer): IssuerData {
+  // Skip 8-byte account discriminator
+  let offset = 8;
+
+  const bump = data[offset];
+  offset += 1;
+
+  const authority = new PublicKey(data.slice(offset, offset + 32));
+  offset += 32;
+
+  const status = data[offset] as IssuerStatus;
+  offset += 1;
+
+  const jurisdictionsLen = data.readUInt32LE(offset);
+  offset += 4;
+  const jurisdictions: Jurisdiction[] = [];
+  for (let i = 0; i < jurisdictionsLen; i++) {
+    jurisdictions.push(data.slice(offset, offset + 2).toString('ascii'));
+    offset += 2;
+  }
+
+  const typesLen = data.readUInt32LE(offset);
+  offset += 4;
+  const attestationTypes = Array.from(data.slice(offset, offset + typesLen)) as AttestationType[];
+  offset += typesLen;
+
+  const attestationCount = data.readBigUInt64LE(offset);
+  offset += 8;
+
+  const addedAt = data.readBigInt64LE(offset);
+  offset += 8;
+
+  const nextSequence = data.readBigUInt64LE(offset);
+
+  return {
+    bump,
+    authority,
+    status,
+    jurisdictions,
+    attestationTypes,
+    attestationCount,
+    addedAt,
+    nextSequence,
+  };
+}
+
+function parseMultisigData(data: Buffer): MultisigData {
+  // Skip 8-byte account discriminator
+  let offset = 8;
+
+  const threshold = data[offset];
+  offset += 1;
+
+  const vecLen = data.readUInt32LE(offset);
+  offset += 4;
+
+  const signers: PublicKey[] = [];
+  for (let i = 0; i < vecLen; i++) {
+    signers.push(new PublicKey(data.slice(offset, offset + 32)));
+    offset += 32;
+  }
+
+  const proposalCount = data.readBigUInt64LE(offset);
+  offset += 8;
+
+  const vaultBump = data[offset];
+  offset += 1;
+
+  const bump = data[offset];
+
+  return { threshold, signers, proposalCount, vaultBump, bump };
+}
+
+/**
+ * AuditSwarm Attestation SDK
+ *
+ * Provides read and write operations for the on-chain attestation program.
+ * Write operations build and send transactions using the provided keypair.
+ * Read operations only require a Connection.
+ */
+export class AuditSwarmSolana {
+  private connection: Connection;
+  private programId: PublicKey;
+
+  constructor(connection: Connection, programId: PublicKey = PROGRAM_ID) {
+    this.connection = connection;
+    this.programId = programId;
+  }
+
+  // ===================
+  // Write Operations
+  // ===================
+
+  /**
+   * Initialize the program state. Must be called once by the program's
+   * upgrade authority.
+   * Returns the transaction signature.
+   */
+  async initialize(authority: Keypair): Promise<string> {
+    const ix = this.buildInitializeInstruction(authority.publicKey);
+
+    const tx = new Transaction().add(ix);
+    return sendAndConfirmTransaction(this.connection, tx, [authority]);
+  }
+
+  /**
+   * Create a new attestation covering multiple wallets.
+   * The authority must be a registered, active issuer; the attestation is created
+   * at the address returned by getNextAttestationPDA beforehand.
+   * Returns the transaction signature.
+   */
+  async createAttestation(params: CreateAttestationParams): Promise<string> {
+    const { authority, ...rest } = params;
+    const ix = this.buildCreateAttestationInstruction(
+      authority.publicKey,
+      rest,
+      await this.getNextSequence(authority.publicKey),
+    );
+
+    const tx = new Transaction().add(ix);
+    return sendAndConfirmTransaction(this.connection, tx, [authority]);
+  }
+
+  /**
+   * Amend an active attestation. The new attestation inherits the jurisdiction,
+   * type and tax year of the previous one, which becomes Superseded, and is
+   * checked against the schema and jurisdiction rules in force now.
+   * Returns the transaction signature.
+   */
+  async supersedeAttestation(params: SupersedeAttestationParams): Promise<string> {
+    const { authority, ...rest } = params;
+    const ix = this.buildSupersedeAttestationInstruction(
+      authority.publicKey,
+      rest,
+      await this.getListedWallets(params.previousAttestation),
+      await this.getNextSequence(authority.publicKey),
+    );
+
+    const tx = new Transaction().add(ix);
+    return sendAndConfirmTransaction(this.connection, tx, [authority]);
+  }
+
+  /**
+   * Extend an attestation's validity (TaxCompliance and AnnualReview only).
+   * Expired attestations become Active again, unless another attestation has
+   * claimed one of their slots in the meantime.
+   * Returns the transaction signature.
+   */
+  async renewAttestation(params: RenewAttestationParams): Promise<string> {
+    const { authority, attestation, newExpiresAt, reviewHash } = params;
+    const ix = this.buildRenewAttestationInstruction(
+      authority.publicKey,
+      attestation,
+      newExpiresAt,
+      reviewHash,
+      await this.getListedWallets(attestation),
+    );
+
+    const tx = new Transaction().add(ix);
+    return sendAndConfirmTransaction(this.connection, tx, [authority]);
+  }
+
+  /**
+   * Update the status of an existing attestation.
+   * Returns the transaction signature.
+   */
+  async updateStatus(params: UpdateStatusParams): Promise<string> {
+    const { authority, attestation, newStatus } = params;
+    const listed =
+      newStatus === AttestationStatus.Revoked ? await this.getListedWallets(attestation) : undefined;
+
+    const ix = this.buildUpdateStatusInstruction(
+      authority.publicKey,
+      attestation,
+      newStatus,
+      listed,
+    );
+
+    const tx = new Transaction().add(ix);
+    return sendAndConfirmTransaction(this.connection, tx, [authority]);
+  }
+
+  /**
+   * Revoke an attestation.
+   * Returns the transaction signature.
+   */
+  async revokeAttestation(params: RevokeAttestationParams): Promise<string> {
+    const { authority, attestation, reason, reasonHash } = params;
+
+    const ix = this.buildRevokeAttestationInstruction(
+      authority.publicKey,
+      attestation,
+      reason,
+      reasonHash,
+      await this.getListedWallets(attestation),
+    );
+
+    const tx = new Transaction().add(ix);
+    return sendAndConfirmTransaction(this.connection, tx, [authority]);
+  }
+
+  // ==========================
+  // Instruction Builders
+  // ==========================
+
+  /**
+   * Build an initialize instruction without sending.
+   * Useful for composing with other instructions in a single transaction.
+   */
+  buildInitializeInstruction(authority: PublicKey): TransactionInstruction {
+    const [statePDA] = getStatePDA(this.programId);
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: statePDA, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: true },
+        { pubkey: this.programId, isSigner: false, isWritable: false },
+        { pubkey: getProgramDataAddress(this.programId), isSigner: false, isWritable: false },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      data: DISCRIMINATORS.initialize,
+    });
+  }
+
+  /**
+   * Build a createAttestation instruction without sending. `sequence` is the
+   * issuer's current nextSequence.
+   */
+  buildCreateAttestationInstruction(
+    authority: PublicKey,
+    params: Omit<CreateAttestationParams, 'authority'> & { authority?: never },
+    sequence: number | bigint,
+  ): TransactionInstruction {
+    const {
+      jurisdiction,
+      subdivision = null,
+      attestationType,
+      schema,
+      taxYear,
+      endTaxYear = taxYear,
+      period,
+      auditHash,
+      expiresAt,
+      wallets,
+      requireConsent = false,
+      initialStatus = AttestationStatus.Active,
+    } = params;
+
+    if (auditHash.length !== 32) {
+      throw new Error('auditHash must be exactly 32 bytes');
+    }
+    checkWalletSlots(wallets.length, taxYear, endTaxYear);
+
+    const [statePDA] = getStatePDA(this.programId);
+    const [attestationPDA] = getAttestationPDA(authority, sequence, this.programId);
+
+    // discriminator(8) + jurisdiction(2) + subdivision(3) + attestation_type(1) + tax_year(2)
+    // + end_tax_year(2) + period_kind(1) + period_start(8) + period_end(8) + audit_hash(32)
+    // + expires_at(8) + wallets(4 + N*32) + require_consent(1) + initial_status(1)
+    const instructionData = Buffer.concat([
+      DISCRIMINATORS.createAttestation,
+      serializeJurisdiction(jurisdiction),
+      serializeSubdivision(subdivision),
+      serializeEnum(attestationType),
+      serializeU16LE(taxYear),
+      serializeU16LE(endTaxYear),
+      ...serializePeriod(period),
+      auditHash,
+      serializeI64LE(expiresAt),
+      serializeVecPubkey(wallets),
+      serializeBool(requireConsent),
+      serializeEnum(initialStatus),
+    ]);
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: statePDA, isSigner: false, isWritable: true },
+        { pubkey: attestationPDA, isSigner: false, isWritable: true },
+        { pubkey: getIssuerPDA(authority, this.programId)[0], isSigner: false, isWritable: true },
+        {
+          pubkey: getJurisdictionConfigPDA(jurisdiction, this.programId)[0],
+          isSigner: false,
+          isWritable: false,
+        },
+        {
+          pubkey: getSchemaPDA(schema.id, schema.version, this.programId)[0],
+          isSigner: false,
+          isWritable: false,
+        },
+        { pubkey: authority, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
+        ...this.walletAccountKeys(attestationPDA, {
+          wallets,
+          jurisdiction,
+          subdivision,
+          attestationType,
+          taxYear,
+          endTaxYear,
+          periodKind: period.kind,
+          periodStart: BigInt(period.start),
+        }),
+      ],
+      data: instructionData,
+    });
+  }
+
+  /**
+   * Build a createMerkleAttestation instruction without sending. `sequence` is the
+   * issuer's current nextSequence.
+   */
+  buildCreateMerkleAttestationInstruction(
+    authority: PublicKey,
+    params: Omit<CreateMerkleAttestationParams, 'authority'>,
+    sequence: number | bigint,
+  ): TransactionInstruction {
+    const {
+      jurisdiction,
+      subdivision,
+      attestationType,
+      schema,
+      taxYear,
+      endTaxYear = taxYear,
+      period,
+      auditHash,
+      expiresAt,
+      walletRoot,
+      walletCount,
+      initialStatus = AttestationStatus.Active,
+    } = params;
+
+    if (auditHash.length !== 32 || walletRoot.length !== 32) {
+      throw new Error('auditHash and walletRoot must be exactly 32 bytes');
+    }
+    if (walletCount < 1) {
+      throw new Error('walletCount must be at least 1');
+    }
+    if (attestationType === AttestationType.TaxCompliance) {
+      throw new Error('TaxCompliance attestations must list their wallets');
+    }
+
+    const [statePDA] = getStatePDA(this.programId);
+    const [attestationPDA] = getAttestationPDA(authority, sequence, this.programId);
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: statePDA, isSigner: false, isWritable: true },
+        { pubkey: attestationPDA, isSigner: false, isWritable: true },
+        { pubkey: getIssuerPDA(authority, this.programId)[0], isSigner: false, isWritable: true },
+        {
+          pubkey: getJurisdictionConfigPDA(jurisdiction, this.programId)[0],
+          isSigner: false,
+          isWritable: false,
+        },
+        {
+          pubkey: getSchemaPDA(schema.id, schema.version, this.programId)[0],
+          isSigner: false,
+          isWritable: false,
+        },
+        { pubkey: authority, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      data: Buffer.concat([
+        DISCRIMINATORS.createMerkleAttestation,
+        serializeJurisdiction(jurisdiction),
+        serializeSubdivision(subdivision),
+        serializeEnum(attestationType),
+        serializeU16LE(taxYear),
+        serializeU16LE(endTaxYear),
+        ...serializePeriod(period),
+        auditHash,
+        serializeI64LE(expiresAt),
+        walletRoot,
+        serializeU32LE(walletCount),
+        serializeEnum(initialStatus),
+      ]),
+    });
+  }
+
+  /**
+   * Build a verifyWalletInclusion instruction. Simulate it and read the program's
+   * return data: a single byte, 1 if the wallet is covered. `proof` is ignored for
+   * attestations that list their wallets.
+   */
+  buildVerifyWalletInclusionInstruction(
+    attestation: PublicKey,
+    wallet: PublicKey,
+    proof: Buffer[] = [],
+  ): TransactionInstruction {
+    if (proof.some((node) => node.length !== 32)) {
+      throw new Error('proof nodes must be exactly 32 bytes');
+    }
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [{ pubkey: attestation, isSigner: false, isWritable: false }],
+      data: Buffer.concat([
+        DISCRIMINATORS.verifyWalletInclusion,
+        wallet.toBuffer(),
+        serializeU32LE(proof.length),
+        ...proof,
+      ]),
+    });
+  }
+
+  /**
+   * Build a coversTaxYear instruction. Simulate it and read the program's return
+   * data: a single byte, 1 if the attestation covers `taxYear`.
+   */
+  buildCoversTaxYearInstruction(attestation: PublicKey, taxYear: number): TransactionInstruction {
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [{ pubkey: attestation, isSigner: false, isWritable: false }],
+      data: Buffer.concat([DISCRIMINATORS.coversTaxYear, serializeU16LE(taxYear)]),
+    });
+  }
+
+  /**
+   * Build an addWallets instruction. The authority pays rent for the extra space.
+   * `listed` is the attestation as it stands; the wallets after the change are
+   * subject to MAX_WALLET_SLOTS.
+   */
+  buildAddWalletsInstruction(
+    authority: PublicKey,
+    attestation: PublicKey,
+    wallets: PublicKey[],
+    listed: ListedWallets,
+  ): TransactionInstruction {
+    const added = wallets.filter((w) => !listed.wallets.some((k) => k.equals(w)));
+    checkWalletSlots(listed.wallets.length + added.length, listed.taxYear, listed.endTaxYear);
+
+    return this.buildChangeWalletsInstruction(
+      DISCRIMINATORS.addWallets,
+      authority,
+      attestation,
+      wallets,
+      listed,
+    );
+  }
+
+  /**
+   * Build a removeWallets instruction. Freed rent is refunded to the authority.
+   * `listed` is the attestation as it stands.
+   */
+  buildRemoveWalletsInstruction(
+    authority: PublicKey,
+    attestation: PublicKey,
+    wallets: PublicKey[],
+    listed: ListedWallets,
+  ): TransactionInstruction {
+    return this.buildChangeWalletsInstruction(
+      DISCRIMINATORS.removeWallets,
+      authority,
+      attestation,
+      wallets,
+      listed,
+    );
+  }
+
+  private buildChangeWalletsInstruction(
+    discriminator: Buffer,
+    authority: PublicKey,
+    attestation: PublicKey,
+    wallets: PublicKey[],
+    listed: ListedWallets,
+  ): TransactionInstruction {
+    if (wallets.length < 1) {
+      throw new Error('wallets must not be empty');
+    }
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: attestation, isSigner: false, isWritable: true },
+        { pubkey: getIssuerPDA(authority, this.programId)[0], isSigner: false, isWritable: false },
+        { pubkey: authority, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        ...this.walletAccountKeys(attestation, listed, wallets),
+      ],
+      data: Buffer.concat([discriminator, serializeVecPubkey(wallets)]),
+    });
+  }
+
+  /**
+   * Build a supersedeAttestation instruction without sending. `previous` is the
+   * attestation being amended and `sequence` the issuer's current nextSequence.
+   */
+  buildSupersedeAttestationInstruction(
+    authority: PublicKey,
+    params: Omit<SupersedeAttestationParams, 'authority'>,
+    previous: ListedWallets,
+    sequence: number | bigint,
+  ): TransactionInstruction {
+    const { previousAttestation, schema, auditHash, expiresAt, wallets } = params;
+
+    if (auditHash.length !== 32) {
+      throw new Error('auditHash must be exactly 32 bytes');
+    }
+    checkWalletSlots(wallets.length, previous.taxYear, previous.endTaxYear);
+
+    const [statePDA] = getStatePDA(this.programId);
+    const [attestationPDA] = getAttestationPDA(authority, sequence, this.programId);
+    const dropped = previous.wallets.filter((w) => !wallets.some((k) => k.equals(w)));
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: statePDA, isSigner: false, isWritable: true },
+        { pubkey: previousAttestation, isSigner: false, isWritable: true },
+        { pubkey: attestationPDA, isSigner: false, isWritable: true },
+        { pubkey: getIssuerPDA(authority, this.programId)[0], isSigner: false, isWritable: true },
+        {
+          pubkey: getJurisdictionConfigPDA(previous.jurisdiction, this.programId)[0],
+          isSigner: false,
+          isWritable: false,
+        },
+        {
+          pubkey: getSchemaPDA(schema.id, schema.version, this.programId)[0],
+          isSigner: false,
+          isWritable: false,
+        },
+        { pubkey: authority, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        ...this.walletAccountKeys(attestationPDA, { ...previous, wallets }),
+        // Slots of the previous attestation's dropped wallets are freed
+        ...this.slotKeys(previous, dropped),
+      ],
+      data: Buffer.concat([
+        DISCRIMINATORS.supersedeAttestation,
+        auditHash,
+        serializeI64LE(expiresAt),
+        serializeVecPubkey(wallets),
+      ]),
+    });
+  }
+
+  /**
+   * Build a renewAttestation instruction without sending. `listed` is the
+   * attestation being renewed: its uniqueness slots take the new expiry.
+   */
+  buildRenewAttestationInstruction(
+    authority: PublicKey,
+    attestation: PublicKey,
+    newExpiresAt: number,
+    reviewHash: Buffer | undefined,
+    listed: ListedWallets,
+  ): TransactionInstruction {
+    if (reviewHash && reviewHash.length !== 32) {
+      throw new Error('reviewHash must be exactly 32 bytes');
+    }
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: attestation, isSigner: false, isWritable: true },
+        { pubkey: getIssuerPDA(authority, this.programId)[0], isSigner: false, isWritable: false },
+        { pubkey: authority, isSigner: true, isWritable: false },
+        ...this.slotKeys(listed, listed.wallets),
+      ],
+      data: Buffer.concat([
+        DISCRIMINATORS.renewAttestation,
+        serializeI64LE(newExpiresAt),
+        serializeOptionBytes32(reviewHash),
+      ]),
+    });
+  }
+
+  /**
+   * Build an updateStatus instruction without sending. Revoking needs the
+   * attestation in `listed`, as for buildRevokeAttestationInstruction.
+   */
+  buildUpdateStatusInstruction(
+    authority: PublicKey,
+    attestation: PublicKey,
+    newStatus: AttestationStatus,
+    listed?: ListedWallets,
+  ): TransactionInstruction {
+    const [statePDA] = getStatePDA(this.programId);
+
+    const instructionData = Buffer.concat([
+      DISCRIMINATORS.updateStatus,
+      serializeEnum(newStatus),
+    ]);
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: statePDA, isSigner: false, isWritable: false },
+        { pubkey: attestation, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: false },
+        ...this.walletAccountKeys(attestation, listed),
+      ],
+      data: instructionData,
+    });
+  }
+
+  /**
+   * Build a suspendAttestation instruction without sending.
+   */
+  buildSuspendAttestationInstruction(
+    authority: PublicKey,
+    attestation: PublicKey,
+    reason: SuspensionReason,
+  ): TransactionInstruction {
+    return this.buildAdminAttestationInstruction(
+      authority,
+      attestation,
+      Buffer.concat([DISCRIMINATORS.suspendAttestation, serializeEnum(reason)]),
+    );
+  }
+
+  /**
+   * Build a reinstateAttestation instruction without sending. An attestation whose
+   * expiresAt passed while suspended is reinstated as Expired.
+   */
+  buildReinstateAttestationInstruction(
+    authority: PublicKey,
+    attestation: PublicKey,
+  ): TransactionInstruction {
+    return this.buildAdminAttestationInstruction(
+      authority,
+      attestation,
+      DISCRIMINATORS.reinstateAttestation,
+    );
+  }
+
+  // Accounts shared by authority-gated status changes: state, attestation, authority,
+  // then the wallet accounts of `listed` if given
+  private buildAdminAttestationInstruction(
+    authority: PublicKey,
+    attestation: PublicKey,
+    data: Buffer,
+    listed?: ListedWallets,
+  ): TransactionInstruction {
+    const [statePDA] = getStatePDA(this.programId);
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: statePDA, isSigner: false, isWritable: false },
+        { pubkey: attestation, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: false },
+        ...this.walletAccountKeys(attestation, listed),
+      ],
+      data,
+    });
+  }
+
+  /**
+   * Build a revokeAttestation instruction without sending. `listed` is the
+   * attestation being revoked: its wallet links record the revocation and its
+   * uniqueness slots are freed.
+   */
+  buildRevokeAttestationInstruction(
+    authority: PublicKey,
+    attestation: PublicKey,
+    reason: RevocationReason,
+    reasonHash: Buffer | undefined,
+    listed: ListedWallets,
+  ): TransactionInstruction {
+    if (reasonHash && reasonHash.length !== 32) {
+      throw new Error('reasonHash must be exactly 32 bytes');
+    }
+
+    return this.buildAdminAttestationInstruction(
+      authority,
+      attestation,
+      Buffer.concat([
+        DISCRIMINATORS.revokeAttestation,
+        serializeEnum(reason),
+        serializeOptionBytes32(reasonHash),
+      ]),
+      listed,
+    );
+  }
+
+  /**
+   * Build an activateAttestation instruction, signed by the issuer, for an
+   * attestation created Pending.
+   */
+  buildActivateAttestationInstruction(
+    authority: PublicKey,
+    attestation: PublicKey,
+  ): TransactionInstruction {
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: attestation, isSigner: false, isWritable: true },
+        { pubkey: getIssuerPDA(authority, this.programId)[0], isSigner: false, isWritable: false },
+        { pubkey: authority, isSigner: true, isWritable: false },
+      ],
+      data: DISCRIMINATORS.activateAttestation,
+    });
+  }
+
+  /**
+   * Build an acknowledgeAttestation instruction, signed by one of the listed wallets.
+   */
+  buildAcknowledgeAttestationInstruction(
+    wallet: PublicKey,
+    attestation: PublicKey,
+  ): TransactionInstruction {
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: attestation, isSigner: false, isWritable: true },
+        { pubkey: wallet, isSigner: true, isWritable: false },
+      ],
+      data: DISCRIMINATORS.acknowledgeAttestation,
+    });
+  }
+
+  /**
+   * Build a requestRemoval instruction, signed (and paid for) by a listed wallet.
+   */
+  buildRequestRemovalInstruction(
+    wallet: PublicKey,
+    attestation: PublicKey,
+    reasonHash: Buffer,
+  ): TransactionInstruction {
+    if (reasonHash.length !== 32) {
+      throw new Error('reasonHash must be exactly 32 bytes');
+    }
+
+    const [requestPDA] = getRemovalRequestPDA(attestation, wallet, this.programId);
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: attestation, isSigner: false, isWritable: true },
+        { pubkey: requestPDA, isSigner: false, isWritable: true },
+        { pubkey: wallet, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      data: Buffer.concat([DISCRIMINATORS.requestRemoval, reasonHash]),
+    });
+  }
+
+  /**
+   * Build a resolveRemoval instruction, signed by the attestation's issuer. The
+   * request is closed and its rent refunded to `wallet`, which may then dispute
+   * again. Resolve as Amend only after the attestation has been superseded.
+   * Revoking needs the attestation in `listed`, as for
+   * buildRevokeAttestationInstruction.
+   */
+  buildResolveRemovalInstruction(
+    authority: PublicKey,
+    attestation: PublicKey,
+    wallet: PublicKey,
+    resolution: RemovalResolution,
+    resolutionHash: Buffer,
+    listed?: ListedWallets,
+  ): TransactionInstruction {
+    if (resolutionHash.length !== 32) {
+      throw new Error('resolutionHash must be exactly 32 bytes');
+    }
+
+    const [requestPDA] = getRemovalRequestPDA(attestation, wallet, this.programId);
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: attestation, isSigner: false, isWritable: true },
+        { pubkey: requestPDA, isSigner: false, isWritable: true },
+        { pubkey: wallet, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: false },
+        ...this.walletAccountKeys(attestation, listed),
+      ],
+      data: Buffer.concat([
+        DISCRIMINATORS.resolveRemoval,
+        serializeEnum(resolution),
+        resolutionHash,
+      ]),
+    });
+  }
+
+  /**
+   * Build a closeAttestation instruction without sending. Only the issuer that
+   * paid for the attestation may close it; the rent of the attestation and of its
+   * wallet links is refunded to it. Open removal requests are closed too, their
+   * rent going back to the disputing wallets. `listed` is the attestation being
+   * closed.
+   */
+  buildCloseAttestationInstruction(
+    authority: PublicKey,
+    attestation: PublicKey,
+    listed: ListedWallets & Pick<AttestationData, 'contestedBy'>,
+  ): TransactionInstruction {
+    const [statePDA] = getStatePDA(this.programId);
+    const [tombstonePDA] = getTombstonePDA(attestation, this.programId);
+    // Each contested wallet's request, followed by the wallet, in wallet order
+    const requestKeys = listed.wallets
+      .filter((_, i) => listed.contestedBy & (1 << i))
+      .flatMap((wallet) => [
+        {
+          pubkey: getRemovalRequestPDA(attestation, wallet, this.programId)[0],
+          isSigner: false,
+          isWritable: true,
+        },
+        { pubkey: wallet, isSigner: false, isWritable: true },
+      ]);
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: statePDA, isSigner: false, isWritable: false },
+        { pubkey: attestation, isSigner: false, isWritable: true },
+        { pubkey: tombstonePDA, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        ...this.walletAccountKeys(attestation, listed),
+        ...requestKeys,
+      ],
+      data: DISCRIMINATORS.closeAttestation,
+    });
+  }
+
+  /**
+   * Build an expireAttestation instruction. Permissionless: any fee payer may
+   * send it once the attestation's `expires_at` has passed.
+   */
+  buildExpireAttestationInstruction(attestation: PublicKey): TransactionInstruction {
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [{ pubkey: attestation, isSigner: false, isWritable: true }],
+      data: DISCRIMINATORS.expireAttestation,
+    });
+  }
+
+  /**
+   * Build an expireAttestations instruction for a batch of attestation accounts.
+   * Accounts that are not active or not yet due are skipped on-chain.
+   */
+  buildExpireAttestationsInstruction(attestations: PublicKey[]): TransactionInstruction {
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: attestations.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })),
+      data: DISCRIMINATORS.expireAttestations,
+    });
+  }
+
+  /**
+   * Build a revokeLegacyAttestation instruction, signed by the program authority,
+   * for an attestation still at its legacy ["attestation", auditHash] address.
+   * Legacy attestations have no wallet links or slots to pass.
+   */
+  buildRevokeLegacyAttestationInstruction(
+    authority: PublicKey,
+    attestation: PublicKey,
+    reason: RevocationReason,
+  ): TransactionInstruction {
+    const [statePDA] = getStatePDA(this.programId);
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: statePDA, isSigner: false, isWritable: false },
+        { pubkey: attestation, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: false },
+      ],
+      data: Buffer.concat([DISCRIMINATORS.revokeLegacyAttestation, serializeEnum(reason)]),
+    });
+  }
+
+  /**
+   * Build an expireLegacyAttestation instruction. Permissionless, like
+   * expireAttestation.
+   */
+  buildExpireLegacyAttestationInstruction(attestation: PublicKey): TransactionInstruction {
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [{ pubkey: attestation, isSigner: false, isWritable: true }],
+      data: DISCRIMINATORS.expireLegacyAttestation,
+    });
+  }
+
+  /**
+   * Build a closeLegacyAttestation instruction, signed by the issuer that created
+   * the legacy attestation, once it is revoked or expired and past retention.
+   */
+  buildCloseLegacyAttestationInstruction(
+    authority: PublicKey,
+    attestation: PublicKey,
+  ): TransactionInstruction {
+    const [statePDA] = getStatePDA(this.programId);
+    const [tombstonePDA] = getTombstonePDA(attestation, this.programId);
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: statePDA, isSigner: false, isWritable: false },
+        { pubkey: attestation, isSigner: false, isWritable: true },
+        { pubkey: tombstonePDA, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      data: DISCRIMINATORS.closeLegacyAttestation,
+    });
+  }
+
+  // Wallet links, then uniqueness slots, passed after the named accounts in wallet
+  // order. `wallets` defaults to every listed wallet.
+  private walletAccountKeys(
+    attestation: PublicKey,
+    listed?: ListedWallets,
+    wallets: PublicKey[] = listed?.wallets ?? [],
+  ): AccountMeta[] {
+    if (!listed) return [];
+
+    return [
+      ...wallets.map((wallet) => ({
+        pubkey: getWalletLinkPDA(wallet, attestation, this.programId)[0],
+        isSigner: false,
+        isWritable: true,
+      })),
+      ...this.slotKeys(listed, wallets),
+    ];
+  }
+
+  // Uniqueness slots of `wallets`, one per covered tax year, wallet by wallet
+  private slotKeys(listed: ListedWallets, wallets: PublicKey[]): AccountMeta[] {
+    const { jurisdiction, subdivision, attestationType } = listed;
+    const period = getSlotPeriod(listed.periodKind, listed.periodStart);
+    return wallets
+      .flatMap((wallet) =>
+        getCoveredTaxYears(listed).map(
+          (taxYear) =>
+            getAttestationSlotPDA(
+              wallet,
+              jurisdiction,
+              subdivision,
+              taxYear,
+              attestationType,
+              period,
+              this.programId,
+            )[0],
+        ),
+      )
+      .map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }));
+  }
+
+  /**
+   * Build an addIssuer instruction without sending.
+   * `authority` is the program authority (admin).
+   */
+  buildAddIssuerInstruction(
+    authority: PublicKey,
+    issuer: PublicKey,
+    jurisdictions: Jurisdiction[],
+    attestationTypes: AttestationType[],
+  ): TransactionInstruction {
+    const [statePDA] = getStatePDA(this.programId);
+    const [issuerPDA] = getIssuerPDA(issuer, this.programId);
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: statePDA, isSigner: false, isWritable: false },
+        { pubkey: issuerPDA, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      data: Buffer.concat([
+        DISCRIMINATORS.addIssuer,
+        issuer.toBuffer(),
+        serializeVecJurisdiction(jurisdictions),
+        serializeVecEnum(attestationTypes),
+      ]),
+    });
+  }
+
+  /**
+   * Build an updateIssuer instruction replacing the issuer's rights.
+   */
+  buildUpdateIssuerInstruction(
+    authority: PublicKey,
+    issuer: PublicKey,
+    jurisdictions: Jurisdiction[],
+    attestationTypes: AttestationType[],
+  ): TransactionInstruction {
+    return this.buildManageIssuerInstruction(
+      authority,
+      issuer,
+      Buffer.concat([
+        DISCRIMINATORS.updateIssuer,
+        serializeVecJurisdiction(jurisdictions),
+        serializeVecEnum(attestationTypes),
+      ]),
+    );
+  }
+
+  /**
+   * Build a suspendIssuer (or, with `suspended = false`, reactivateIssuer) instruction.
+   */
+  buildSetIssuerSuspendedInstruction(
+    authority: PublicKey,
+    issuer: PublicKey,
+    suspended: boolean,
+  ): TransactionInstruction {
+    return this.buildManageIssuerInstruction(
+      authority,
+      issuer,
+      suspended ? DISCRIMINATORS.suspendIssuer : DISCRIMINATORS.reactivateIssuer,
+    );
+  }
+
+  /**
+   * Build a removeIssuer instruction; rent is returned to the authority.
+   */
+  buildRemoveIssuerInstruction(authority: PublicKey, issuer: PublicKey): TransactionInstruction {
+    const [statePDA] = getStatePDA(this.programId);
+    const [issuerPDA] = getIssuerPDA(issuer, this.programId);
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: statePDA, isSigner: false, isWritable: false },
+        { pubkey: issuerPDA, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: true },
+      ],
+      data: DISCRIMINATORS.removeIssuer,
+    });
+  }
+
+  /**
+   * Build an addJurisdiction instruction registering an ISO 3166-1 alpha-2 code.
+   * `authority` is the program authority (admin).
+   */
+  buildAddJurisdictionInstruction(authority: PublicKey, code: Jurisdiction): TransactionInstruction {
+    const [statePDA] = getStatePDA(this.programId);
+    const [configPDA] = getJurisdictionConfigPDA(code, this.programId);
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: statePDA, isSigner: false, isWritable: false },
+        { pubkey: configPDA, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      data: Buffer.concat([DISCRIMINATORS.addJurisdiction, serializeJurisdiction(code)]),
+    });
+  }
+
+  /**
+   * Build a setJurisdictionSubdivisions instruction replacing the subdivisions
+   * attestations for the jurisdiction may name.
+   */
+  buildSetJurisdictionSubdivisionsInstruction(
+    authority: PublicKey,
+    code: Jurisdiction,
+    subdivisions: Subdivision[],
+  ): TransactionInstruction {
+    const [statePDA] = getStatePDA(this.programId);
+    const [configPDA] = getJurisdictionConfigPDA(code, this.programId);
+    const lenBuf = Buffer.alloc(4);
+    lenBuf.writeUInt32LE(subdivisions.length);
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: statePDA, isSigner: false, isWritable: false },
+        { pubkey: configPDA, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: false },
+      ],
+      data: Buffer.concat([
+        DISCRIMINATORS.setJurisdictionSubdivisions,
+        lenBuf,
+        ...subdivisions.map(serializeSubdivision),
+      ]),
+    });
+  }
+
+  /**
+   * Build an addSchema instruction registering a schema version.
+   * `authority` is the program authority (admin).
+   */
+  buildAddSchemaInstruction(authority: PublicKey, params: AddSchemaParams): TransactionInstruction {
+    const {
+      id,
+      version,
+      nameHash,
+      attestationType,
+      requiredFields = 0,
+      defaultValidity = 0,
+      jurisdictions = [],
+    } = params;
+
+    if (nameHash.length !== 32) {
+      throw new Error('nameHash must be exactly 32 bytes');
+    }
+
+    const [statePDA] = getStatePDA(this.programId);
+    const [schemaPDA] = getSchemaPDA(id, version, this.programId);
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: statePDA, isSigner: false, isWritable: false },
+        { pubkey: schemaPDA, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      data: Buffer.concat([
+        DISCRIMINATORS.addSchema,
+        serializeU32LE(id),
+        serializeU16LE(version),
+        nameHash,
+        serializeEnum(attestationType),
+        serializeU16LE(requiredFields),
+        serializeI64LE(defaultValidity),
+        serializeVecJurisdiction(jurisdictions),
+      ]),
+    });
+  }
+
+  /**
+   * Build a deprecateSchema instruction; no new attestations can use the schema.
+   */
+  buildDeprecateSchemaInstruction(authority: PublicKey, schema: SchemaRef): TransactionInstruction {
+    const [statePDA] = getStatePDA(this.programId);
+    const [schemaPDA] = getSchemaPDA(schema.id, schema.version, this.programId);
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: statePDA, isSigner: false, isWritable: false },
+        { pubkey: schemaPDA, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: false },
+      ],
+      data: DISCRIMINATORS.deprecateSchema,
+    });
+  }
+
+  /**
+   * Build a setFiscalCalendar instruction setting the month (1-12) and day (1-28)
+   * the jurisdiction's fiscal year starts on.
+   */
+  buildSetFiscalCalendarInstruction(
+    authority: PublicKey,
+    code: Jurisdiction,
+    startMonth: number,
+    startDay: number,
+  ): TransactionInstruction {
+    const [statePDA] = getStatePDA(this.programId);
+    const [configPDA] = getJurisdictionConfigPDA(code, this.programId);
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: statePDA, isSigner: false, isWritable: false },
+        { pubkey: configPDA, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: false },
+      ],
+      data: Buffer.concat([
+        DISCRIMINATORS.setFiscalCalendar,
+        Buffer.from([startMonth, startDay]),
+      ]),
+    });
+  }
+
+  /**
+   * Build a removeJurisdiction instruction; rent is returned to the authority.
+   */
+  buildRemoveJurisdictionInstruction(
+    authority: PublicKey,
+    code: Jurisdiction,
+  ): TransactionInstruction {
+    const [statePDA] = getStatePDA(this.programId);
+    const [configPDA] = getJurisdictionConfigPDA(code, this.programId);
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: statePDA, isSigner: false, isWritable: false },
+        { pubkey: configPDA, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: true },
+      ],
+      data: DISCRIMINATORS.removeJurisdiction,
+    });
+  }
+
+  private buildManageIssuerInstruction(
+    authority: PublicKey,
+    issuer: PublicKey,
+    data: Buffer,
+  ): TransactionInstruction {
+    const [statePDA] = getStatePDA(this.programId);
+    const [issuerPDA] = getIssuerPDA(issuer, this.programId);
+
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: statePDA, isSigner: false, isWritable: false },
+        { pubkey: issuerPDA, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: false },
+      ],
+      data,
+    });
+  }
+
+  /**
+   * Build a setTaxYearWindow instruction (inclusive range) without sending.
+   */
+  buildSetTaxYearWindowInstruction(
+    authority: PublicKey,
+    minTaxYear: number,
+    maxTaxYear: number,
+  ): TransactionInstruction {
+    const [statePDA] = getStatePDA(this.programId);
 
-    const ix = new TransactionInstruction({
+    return new TransactionInstruction({
       programId: this.programId,
       keys: [
         { pubkey: statePDA, isSigner: false, isWritable: true },
-        { pubkey: attestationPDA, isSigner: false, isWritable: true },
-        { pubkey: authority.publicKey, isSigner: true, isWritable: true },
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: authority, isSigner: true, isWritable: false },
       ],
-      data: instructionData,
+      data: Buffer.concat([
+        DISCRIMINATORS.setTaxYearWindow,
+        serializeU16LE(minTaxYear),
+        serializeU16LE(maxTaxYear),
+      ]),
     });
-
-    const tx = new Transaction().add(ix);
-    return sendAndConfirmTransaction(this.connection, tx, [authority]);
   }
 
   /**
-   * Update the status of an existing attestation.
-   * Returns the transaction signature.
+   * Build a setRetentionPeriod instruction (seconds) without sending.
    */
-  async updateStatus(params: UpdateStatusParams): Promise<string> {
-    const { authority, auditHash, newStatus } = params;
-
-    if (auditHash.length !== 32) {
-      throw new Error('auditHash must be exactly 32 bytes');
-    }
-
+  buildSetRetentionPeriodInstruction(
+    authority: PublicKey,
+    retentionPeriod: number | bigint,
+  ): TransactionInstruction {
     const [statePDA] = getStatePDA(this.programId);
-    const [attestationPDA] = getAttestationPDA(auditHash, this.programId);
-
-    const instructionData = Buffer.concat([
-      DISCRIMINATORS.updateStatus,
-      serializeEnum(newStatus),
-    ]);
 
-    const ix = new TransactionInstruction({
+    return new TransactionInstruction({
       programId: this.programId,
       keys: [
-        { pubkey: statePDA, isSigner: false, isWritable: false },
-        { pubkey: attestationPDA, isSigner: false, isWritable: true },
-        { pubkey: authority.publicKey, isSigner: true, isWritable: false },
+        { pubkey: statePDA, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: false },
       ],
-      data: instructionData,
+      data: Buffer.concat([DISCRIMINATORS.setRetentionPeriod, serializeI64LE(retentionPeriod)]),
     });
-
-    const tx = new Transaction().add(ix);
-    return sendAndConfirmTransaction(this.connection, tx, [authority]);
   }
 
   /**
-   * Revoke an attestation.
-   * Returns the transaction signature.
+   * Build a proposeAuthority instruction without sending.
+   * The transfer only takes effect once `newAuthority` accepts it.
    */
-  async revokeAttestation(params: RevokeAttestationParams): Promise<string> {
-    const { authority, auditHash } = params;
+  buildProposeAuthorityInstruction(
+    authority: PublicKey,
+    newAuthority: PublicKey,
+  ): TransactionInstruction {
+    const [statePDA] = getStatePDA(this.programId);
 
-    if (auditHash.length !== 32) {
-      throw new Error('auditHash must be exactly 32 bytes');
-    }
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: statePDA, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: false },
+      ],
+      data: Buffer.concat([DISCRIMINATORS.proposeAuthority, newAuthority.toBuffer()]),
+    });
+  }
 
+  /**
+   * Build an acceptAuthority instruction, signed by the pending authority.
+   */
+  buildAcceptAuthorityInstruction(pendingAuthority: PublicKey): TransactionInstruction {
     const [statePDA] = getStatePDA(this.programId);
-    const [attestationPDA] = getAttestationPDA(auditHash, this.programId);
 
-    const ix = new TransactionInstruction({
+    return new TransactionInstruction({
       programId: this.programId,
       keys: [
-        { pubkey: statePDA, isSigner: false, isWritable: false },
-        { pubkey: attestationPDA, isSigner: false, isWritable: true },
-        { pubkey: authority.publicKey, isSigner: true, isWritable: false },
+        { pubkey: statePDA, isSigner: false, isWritable: true },
+        { pubkey: pendingAuthority, isSigner: true, isWritable: false },
       ],
-      data: DISCRIMINATORS.revokeAttestation,
+      data: DISCRIMINATORS.acceptAuthority,
     });
-
-    const tx = new Transaction().add(ix);
-    return sendAndConfirmTransaction(this.connection, tx, [authority]);
   }
 
-  // ==========================
-  // Instruction Builders
-  // ==========================
-
   /**
-   * Build an initialize instruction without sending.
-   * Useful for composing with other instructions in a single transaction.
+   * Build a cancelAuthorityTransfer instruction without sending.
    */
-  buildInitializeInstruction(authority: PublicKey): TransactionInstruction {
+  buildCancelAuthorityTransferInstruction(authority: PublicKey): TransactionInstruction {
     const [statePDA] = getStatePDA(this.programId);
 
     return new TransactionInstruction({
       programId: this.programId,
       keys: [
         { pubkey: statePDA, isSigner: false, isWritable: true },
-        { pubkey: authority, isSigner: true, isWritable: true },
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: authority, isSigner: true, isWritable: false },
       ],
-      data: DISCRIMINATORS.initialize,
+      data: DISCRIMINATORS.cancelAuthorityTransfer,
     });
   }
 
   /**
-   * Build a createAttestation instruction without sending.
+   * Build a createMultisig instruction without sending.
+   * Hands the program authority over to the multisig vault.
    */
-  buildCreateAttestationInstruction(
+  buildCreateMultisigInstruction(
     authority: PublicKey,
-    params: Omit<CreateAttestationParams, 'authority'> & { authority?: never },
+    threshold: number,
+    signers: PublicKey[],
   ): TransactionInstruction {
-    const { jurisdiction, attestationType, taxYear, auditHash, expiresAt, wallets } = params;
-
-    if (auditHash.length !== 32) {
-      throw new Error('auditHash must be exactly 32 bytes');
+    if (signers.length < 1 || signers.length > 10) {
+      throw new Error('signers must contain 1-10 entries');
     }
-    if (wallets.length < 1 || wallets.length > 10) {
-      throw new Error('wallets must contain 1-10 entries');
+    if (threshold < 1 || threshold > signers.length) {
+      throw new Error('threshold must be between 1 and the number of signers');
     }
 
     const [statePDA] = getStatePDA(this.programId);
-    const [attestationPDA] = getAttestationPDA(auditHash, this.programId);
-
-    const instructionData = Buffer.concat([
-      DISCRIMINATORS.createAttestation,
-      serializeEnum(jurisdiction),
-      serializeEnum(attestationType),
-      serializeU16LE(taxYear),
-      auditHash,
-      serializeI64LE(expiresAt),
-      serializeVecPubkey(wallets),
-    ]);
+    const [multisigPDA] = getMultisigPDA(this.programId);
+    const [vaultPDA] = getMultisigVaultPDA(this.programId);
 
     return new TransactionInstruction({
       programId: this.programId,
       keys: [
         { pubkey: statePDA, isSigner: false, isWritable: true },
-        { pubkey: attestationPDA, isSigner: false, isWritable: true },
+        { pubkey: multisigPDA, isSigner: false, isWritable: true },
+        { pubkey: vaultPDA, isSigner: false, isWritable: false },
         { pubkey: authority, isSigner: true, isWritable: true },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
       ],
-      data: instructionData,
+      data: Buffer.concat([
+        DISCRIMINATORS.createMultisig,
+        Buffer.from([threshold]),
+        serializeVecPubkey(signers),
+      ]),
     });
   }
 
   /**
-   * Build an updateStatus instruction without sending.
+   * Build a proposeTransaction instruction wrapping `inner`, a program
+   * instruction built with the multisig vault as its authority.
+   * `proposalIndex` must equal the multisig's current proposal count.
    */
-  buildUpdateStatusInstruction(
-    authority: PublicKey,
-    auditHash: Buffer,
-    newStatus: AttestationStatus,
+  buildProposeTransactionInstruction(
+    proposer: PublicKey,
+    proposalIndex: number | bigint,
+    inner: TransactionInstruction,
   ): TransactionInstruction {
-    if (auditHash.length !== 32) {
-      throw new Error('auditHash must be exactly 32 bytes');
-    }
+    const [multisigPDA] = getMultisigPDA(this.programId);
+    const [proposalPDA] = getProposalPDA(proposalIndex, this.programId);
 
-    const [statePDA] = getStatePDA(this.programId);
-    const [attestationPDA] = getAttestationPDA(auditHash, this.programId);
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: multisigPDA, isSigner: false, isWritable: true },
+        { pubkey: proposalPDA, isSigner: false, isWritable: true },
+        { pubkey: proposer, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      data: Buffer.concat([
+        DISCRIMINATORS.proposeTransaction,
+        serializeProposalAccounts(inner),
+        serializeVecBytes(inner.data),
+      ]),
+    });
+  }
 
-    const instructionData = Buffer.concat([
-      DISCRIMINATORS.updateStatus,
-      serializeEnum(newStatus),
-    ]);
+  /**
+   * Build an approveTransaction instruction without sending.
+   */
+  buildApproveTransactionInstruction(
+    signer: PublicKey,
+    proposalIndex: number | bigint,
+  ): TransactionInstruction {
+    const [multisigPDA] = getMultisigPDA(this.programId);
+    const [proposalPDA] = getProposalPDA(proposalIndex, this.programId);
 
     return new TransactionInstruction({
       programId: this.programId,
       keys: [
-        { pubkey: statePDA, isSigner: false, isWritable: false },
-        { pubkey: attestationPDA, isSigner: false, isWritable: true },
-        { pubkey: authority, isSigner: true, isWritable: false },
+        { pubkey: multisigPDA, isSigner: false, isWritable: false },
+        { pubkey: proposalPDA, isSigner: false, isWritable: true },
+        { pubkey: signer, isSigner: true, isWritable: false },
       ],
-      data: instructionData,
+      data: DISCRIMINATORS.approveTransaction,
     });
   }
 
   /**
-   * Build a revokeAttestation instruction without sending.
+   * Build an executeTransaction instruction for a proposal wrapping `inner`.
+   * The inner instruction's accounts are forwarded as remaining accounts.
    */
-  buildRevokeAttestationInstruction(
-    authority: PublicKey,
-    auditHash: Buffer,
+  buildExecuteTransactionInstruction(
+    executor: PublicKey,
+    proposalIndex: number | bigint,
+    inner: TransactionInstruction,
   ): TransactionInstruction {
-    if (auditHash.length !== 32) {
-      throw new Error('auditHash must be exactly 32 bytes');
-    }
+    const [multisigPDA] = getMultisigPDA(this.programId);
+    const [proposalPDA] = getProposalPDA(proposalIndex, this.programId);
+    const [vaultPDA] = getMultisigVaultPDA(this.programId);
 
-    const [statePDA] = getStatePDA(this.programId);
-    const [attestationPDA] = getAttestationPDA(auditHash, this.programId);
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: multisigPDA, isSigner: false, isWritable: false },
+        { pubkey: proposalPDA, isSigner: false, isWritable: true },
+        { pubkey: vaultPDA, isSigner: false, isWritable: true },
+        { pubkey: executor, isSigner: true, isWritable: false },
+        { pubkey: this.programId, isSigner: false, isWritable: false },
+        // The vault signs via PDA seeds inside the program, not in this transaction
+        ...inner.keys.map((k) => ({ pubkey: k.pubkey, isSigner: false, isWritable: k.isWritable })),
+      ],
+      data: DISCRIMINATORS.executeTransaction,
+    });
+  }
+
+  /**
+   * Build a cancelProposal instruction. `signer` must be the proposer unless the
+   * proposal has expired; its rent goes back to `proposer`.
+   */
+  buildCancelProposalInstruction(
+    signer: PublicKey,
+    proposalIndex: number | bigint,
+    proposer: PublicKey,
+  ): TransactionInstruction {
+    const [multisigPDA] = getMultisigPDA(this.programId);
+    const [proposalPDA] = getProposalPDA(proposalIndex, this.programId);
 
     return new TransactionInstruction({
       programId: this.programId,
       keys: [
-        { pubkey: statePDA, isSigner: false, isWritable: false },
-        { pubkey: attestationPDA, isSigner: false, isWritable: true },
-        { pubkey: authority, isSigner: true, isWritable: false },
+        { pubkey: multisigPDA, isSigner: false, isWritable: false },
+        { pubkey: proposalPDA, isSigner: false, isWritable: true },
+        { pubkey: proposer, isSigner: false, isWritable: true },
+        { pubkey: signer, isSigner: true, isWritable: false },
       ],
-      data: DISCRIMINATORS.revokeAttestation,
+      data: DISCRIMINATORS.cancelProposal,
     });
   }
 
@@ -514,15 +2862,148 @@ export class AuditSwarmSolana {
   }
 
   /**
-   * Get an attestation by its audit hash (derives PDA from hash).
+   * Get an issuer registry entry by the issuer's signing key.
+   */
+  async getIssuer(issuer: PublicKey): Promise<IssuerData | null> {
+    const [issuerPDA] = getIssuerPDA(issuer, this.programId);
+
+    try {
+      const accountInfo = await this.connection.getAccountInfo(issuerPDA);
+      if (!accountInfo) return null;
+      return parseIssuerData(accountInfo.data as Buffer);
+    } catch {
+      return null;
+    }
+  }
+
+  /**
+   * Get a jurisdiction registry entry, or null if the code is not registered.
+   */
+  async getJurisdictionConfig(code: Jurisdiction): Promise<JurisdictionConfigData | null> {
+    const [configPDA] = getJurisdictionConfigPDA(code, this.programId);
+
+    try {
+      const accountInfo = await this.connection.getAccountInfo(configPDA);
+      if (!accountInfo) return null;
+      return parseJurisdictionConfigData(accountInfo.data as Buffer);
+    } catch {
+      return null;
+    }
+  }
+
+  /**
+   * Get a schema version, or null if it is not registered.
+   */
+  async getSchema(schema: SchemaRef): Promise<AttestationSchemaData | null> {
+    const [schemaPDA] = getSchemaPDA(schema.id, schema.version, this.programId);
+
+    try {
+      const accountInfo = await this.connection.getAccountInfo(schemaPDA);
+      if (!accountInfo) return null;
+      return parseAttestationSchemaData(accountInfo.data as Buffer);
+    } catch {
+      return null;
+    }
+  }
+
+  /**
+   * Get the authority multisig, or null if the program still has a single authority.
+   */
+  async getMultisig(): Promise<MultisigData | null> {
+    const [multisigPDA] = getMultisigPDA(this.programId);
+
+    try {
+      const accountInfo = await this.connection.getAccountInfo(multisigPDA);
+      if (!accountInfo) return null;
+      return parseMultisigData(accountInfo.data as Buffer);
+    } catch {
+      return null;
+    }
+  }
+
+  /**
+   * Get a wallet's open removal request against an attestation, or null if there
+   * is none.
+   */
+  async getRemovalRequest(
+    attestation: PublicKey,
+    wallet: PublicKey,
+  ): Promise<RemovalRequestData | null> {
+    const [requestPDA] = getRemovalRequestPDA(attestation, wallet, this.programId);
+
+    try {
+      const accountInfo = await this.connection.getAccountInfo(requestPDA);
+      if (!accountInfo) return null;
+      return parseRemovalRequestData(accountInfo.data as Buffer);
+    } catch {
+      return null;
+    }
+  }
+
+  /**
+   * Get the tombstone of a closed attestation, or null if it was never closed.
+   */
+  async getTombstone(attestation: PublicKey): Promise<TombstoneData | null> {
+    const [tombstonePDA] = getTombstonePDA(attestation, this.programId);
+
+    try {
+      const accountInfo = await this.connection.getAccountInfo(tombstonePDA);
+      if (!accountInfo) return null;
+      return parseTombstoneData(accountInfo.data as Buffer);
+    } catch {
+      return null;
+    }
+  }
+
+  /**
+   * Get the address the issuer's next attestation will be created at.
    */
-  async getAttestation(auditHash: Buffer): Promise<AttestationData | null> {
+  async getNextAttestationPDA(authority: PublicKey): Promise<PublicKey> {
+    return getAttestationPDA(authority, await this.getNextSequence(authority), this.programId)[0];
+  }
+
+  /**
+   * Find the attestations recorded with an audit hash, optionally only those of
+   * one issuer. Audit hashes are not unique: an issuer may reuse one, and other
+   * issuers' hashes may collide with it.
+   */
+  async findAttestationsByHash(
+    auditHash: Buffer,
+    authority?: PublicKey,
+  ): Promise<{ address: PublicKey; attestation: AttestationData }[]> {
     if (auditHash.length !== 32) {
       throw new Error('auditHash must be exactly 32 bytes');
     }
 
-    const [attestationPDA] = getAttestationPDA(auditHash, this.programId);
-    return this.getAttestationByAddress(attestationPDA);
+    const accounts = await this.connection.getProgramAccounts(this.programId, {
+      filters: [
+        {
+          memcmp: {
+            offset: 0,
+            bytes: Buffer.from(ACCOUNT_DISCRIMINATORS.attestation).toString('base64'),
+            encoding: 'base64' as any,
+          },
+        },
+        {
+          memcmp: {
+            offset: AUDIT_HASH_OFFSET,
+            bytes: auditHash.toString('base64'),
+            encoding: 'base64' as any,
+          },
+        },
+        ...(authority ? [{ memcmp: { offset: AUTHORITY_OFFSET, bytes: authority.toBase58() } }] : []),
+      ],
+    });
+
+    const found: { address: PublicKey; attestation: AttestationData }[] = [];
+    for (const { pubkey, account } of accounts) {
+      try {
+        found.push({ address: pubkey, attestation: parseAttestationData(account.data as Buffer) });
+      } catch {
+        // Skip accounts written with an older layout
+      }
+    }
+    return found;
   }
 
   /**
@@ -539,32 +3020,45 @@ export class AuditSwarmSolana {
   }
 
   /**
-   * Get all attestations that include the given wallet.
-   * Fetches all program attestation accounts and filters client-side.
+   * Get the wallet links of a wallet: one per attestation that lists it.
    */
-  async getWalletAttestations(wallet: PublicKey): Promise<AttestationData[]> {
+  async getWalletLinks(wallet: PublicKey): Promise<WalletAttestationLinkData[]> {
     const accounts = await this.connection.getProgramAccounts(this.programId, {
       filters: [
         {
           memcmp: {
             offset: 0,
-            bytes: Buffer.from(ACCOUNT_DISCRIMINATORS.attestation).toString('base64'),
+            bytes: Buffer.from(ACCOUNT_DISCRIMINATORS.walletAttestationLink).toString('base64'),
             encoding: 'base64' as any,
           },
         },
+        // wallet follows the discriminator and bump
+        { memcmp: { offset: 9, bytes: wallet.toBase58() } },
       ],
     });
 
+    return accounts.map(({ account }) => parseWalletAttestationLinkData(account.data as Buffer));
+  }
+
+  /**
+   * Get all attestations that include the given wallet, found through its
+   * wallet links. Merkle-root attestations are not included; check those with
+   * buildVerifyWalletInclusionInstruction.
+   */
+  async getWalletAttestations(wallet: PublicKey): Promise<AttestationData[]> {
+    const links = await this.getWalletLinks(wallet);
+    if (links.length === 0) return [];
+
+    const accounts = await this.connection.getMultipleAccountsInfo(
+      links.map((link) => link.attestation),
+    );
+
     const attestations: AttestationData[] = [];
 
-    for (const { account } of accounts) {
+    for (const account of accounts) {
+      if (!account) continue;
       try {
-        const attestation = parseAttestationData(account.data as Buffer);
-        // Check if any wallet in the attestation matches the queried wallet
-        const hasWallet = attestation.wallets.some((w) => w.equals(wallet));
-        if (hasWallet) {
-          attestations.push(attestation);
-        }
+        attestations.push(parseAttestationData(account.data as Buffer));
       } catch {
         // Skip malformed accounts
       }
@@ -573,10 +3067,60 @@ export class AuditSwarmSolana {
     return attestations;
   }
 
+  // Fetch an attestation for instructions that take its wallet accounts
+  private async getListedWallets(address: PublicKey): Promise<ListedWallets> {
+    const attestation = await this.getAttestationByAddress(address);
+    if (!attestation) {
+      throw new Error('Attestation not found');
+    }
+    return attestation;
+  }
+
+  // Sequence number of the issuer's next attestation, part of its PDA seeds
+  private async getNextSequence(authority: PublicKey): Promise<bigint> {
+    const issuer = await this.getIssuer(authority);
+    if (!issuer) {
+      throw new Error('Issuer not found');
+    }
+    return issuer.nextSequence;
+  }
+
+  /**
+   * Get a wallet's uniqueness slot for a jurisdiction (or one of its subdivisions),
+   * tax year, type and quarter or month, or null if no attestation has claimed it
+   * yet. Annual and custom periods share the slot returned when `period` is null.
+   */
+  async getAttestationSlot(
+    wallet: PublicKey,
+    jurisdiction: Jurisdiction,
+    taxYear: number,
+    attestationType: AttestationType,
+    subdivision: Subdivision | null = null,
+    period: FiscalPeriod | null = null,
+  ): Promise<AttestationSlotData | null> {
+    const [slotPDA] = getAttestationSlotPDA(
+      wallet,
+      jurisdiction,
+      subdivision,
+      taxYear,
+      attestationType,
+      period ? getSlotPeriod(period.kind, period.start) : 0n,
+      this.programId,
+    );
+
+    try {
+      const accountInfo = await this.connection.getAccountInfo(slotPDA);
+      if (!accountInfo) return null;
+      return parseAttestationSlotData(accountInfo.data as Buffer);
+    } catch {
+      return null;
+    }
+  }
+
   /**
    * Check if a wallet is compliant for a given jurisdiction and optional tax year.
    * Searches all attestations that include the wallet and checks for an active,
-   * non-expired TaxCompliance attestation.
+   * non-expired TaxCompliance attestation covering the tax year.
    */
   async isCompliant(
     wallet: PublicKey,
@@ -586,17 +3130,22 @@ export class AuditSwarmSolana {
     compliant: boolean;
     attestation?: AttestationData;
     reason?: string;
+    /** True while a listed wallet has an open removal request */
+    contested?: boolean;
   }> {
     const year = taxYear || new Date().getFullYear();
     const attestations = await this.getWalletAttestations(wallet);
 
-    // Find matching TaxCompliance attestation for the jurisdiction and year
-    const matching = attestations.find(
+    // Find matching TaxCompliance attestation for the jurisdiction and year,
+    // preferring an active one over superseded/revoked entries of the same chain
+    const candidates = attestations.filter(
       (a) =>
         a.jurisdiction === jurisdiction &&
         a.attestationType === AttestationType.TaxCompliance &&
-        a.taxYear === year,
+        getCoveredTaxYears(a).includes(year),
     );
+    const matching =
+      candidates.find((a) => a.status === AttestationStatus.Active) ?? candidates[0];
 
     if (!matching) {
       return {
@@ -625,6 +3174,7 @@ export class AuditSwarmSolana {
     return {
       compliant: true,
       attestation: matching,
+      contested: matching.contestedBy !== 0,
     };
   }
 
@@ -632,10 +3182,10 @@ export class AuditSwarmSolana {
    * Verify that an attestation's on-chain hash matches the expected hash.
    */
   async verifyAttestation(
-    auditHash: Buffer,
+    address: PublicKey,
     expectedHash: Uint8Array,
   ): Promise<boolean> {
-    const attestation = await this.getAttestation(auditHash);
+    const attestation = await this.getAttestationByAddress(address);
 
     if (!attestation) return false;
     if (attestation.status !== AttestationStatus.Active) return false;
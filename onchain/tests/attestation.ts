@@ -1,7 +1,15 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
-import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  Ed25519Program,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  TransactionInstruction,
+} from "@solana/web3.js";
 import { expect } from "chai";
+import { createHash } from "crypto";
 import { Attestation } from "../target/types/attestation";
 
 describe("attestation", () => {
@@ -18,12 +26,194 @@ describe("attestation", () => {
       program.programId
     );
 
-  const findAttestationPda = (auditHash: number[]) =>
+  const findAttestationPda = (issuer: PublicKey, sequence: anchor.BN) =>
     PublicKey.findProgramAddressSync(
-      [Buffer.from("attestation"), Buffer.from(auditHash)],
+      [
+        Buffer.from("attestation"),
+        issuer.toBuffer(),
+        sequence.toArrayLike(Buffer, "le", 8),
+      ],
       program.programId
     );
 
+  // Address the issuer's next attestation is created at
+  const nextAttestationPda = async (issuer: PublicKey = authority.publicKey) => {
+    const { nextSequence } = await program.account.issuer.fetch(findIssuerPda(issuer)[0]);
+    return findAttestationPda(issuer, nextSequence);
+  };
+
+  const findTombstonePda = (attestation: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("tombstone"), attestation.toBuffer()],
+      program.programId
+    );
+
+  const findRemovalPda = (attestation: PublicKey, wallet: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("removal"), attestation.toBuffer(), wallet.toBuffer()],
+      program.programId
+    );
+
+  const findWalletLinkPda = (wallet: PublicKey, attestation: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("wallet_link"), wallet.toBuffer(), attestation.toBuffer()],
+      program.programId
+    );
+
+  type SlotScope = {
+    jurisdiction: number[];
+    subdivision?: number[];
+    attestationType: any;
+    taxYear: number;
+    endTaxYear?: number;
+    periodKind?: any;
+    periodStart?: anchor.BN;
+  };
+
+  const findSlotPda = (wallet: PublicKey, scope: SlotScope) => {
+    const variant = (all: object[], value: object) =>
+      all.findIndex((v) => Object.keys(v)[0] === Object.keys(value)[0]);
+    const taxYear = Buffer.alloc(2);
+    taxYear.writeUInt16LE(scope.taxYear);
+    // Quarters and months have a slot each; annual and custom periods share one
+    const subAnnual = scope.periodKind && ("quarterly" in scope.periodKind || "monthly" in scope.periodKind);
+    const period = (subAnnual ? scope.periodStart : new anchor.BN(0)).toArrayLike(Buffer, "le", 8);
+    return PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("slot"),
+        wallet.toBuffer(),
+        Buffer.from(scope.jurisdiction),
+        Buffer.from(scope.subdivision ?? NO_SUBDIVISION),
+        taxYear,
+        Buffer.from([variant(ALL_TYPES, scope.attestationType)]),
+        period,
+      ],
+      program.programId
+    );
+  };
+
+  // Tax years an attestation covers, first to last
+  const coveredYears = (scope: SlotScope) =>
+    Array.from(
+      { length: (scope.endTaxYear ?? scope.taxYear) - scope.taxYear + 1 },
+      (_, i) => scope.taxYear + i
+    );
+
+  // Slots of listed wallets: one per covered year, wallet by wallet
+  const slotPdas = (wallets: PublicKey[], scope: SlotScope) =>
+    wallets.flatMap((wallet) =>
+      coveredYears(scope).map((taxYear) => findSlotPda(wallet, { ...scope, taxYear })[0])
+    );
+
+  // Remaining accounts for listed wallets: their links, then their slots
+  const walletAccounts = (attestation: PublicKey, wallets: PublicKey[], scope: SlotScope) =>
+    [
+      ...wallets.map((wallet) => findWalletLinkPda(wallet, attestation)[0]),
+      ...slotPdas(wallets, scope),
+    ].map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }));
+
+  const attestationAccounts = async (attestation: PublicKey) => {
+    const account = await program.account.attestation.fetch(attestation);
+    return walletAccounts(attestation, account.wallets, account);
+  };
+
+  const [programDataPda] = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+  );
+
+  const findIssuerPda = (issuer: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("issuer"), issuer.toBuffer()],
+      program.programId
+    );
+
+  const findJurisdictionPda = (code: number[]) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("jurisdiction"), Buffer.from(code)],
+      program.programId
+    );
+
+  // ISO 3166-1 alpha-2 code as the program's [u8; 2]
+  const iso = (code: string) => Array.from(Buffer.from(code, "ascii"));
+
+  // The backend's jurisdictions as the processor maps them: UK is GB, and EU is the
+  // code ISO 3166-1 exceptionally reserves for the European Union
+  const ALL_JURISDICTIONS = ["US", "EU", "BR", "GB", "JP", "AU", "CA", "CH", "SG"].map(iso);
+
+  // ISO 3166-2 subdivision code as the program's zero-padded [u8; 3]
+  const subdivision = (code: string) => [...Buffer.from(code, "ascii"), 0, 0, 0].slice(0, 3);
+  const NO_SUBDIVISION = [0, 0, 0];
+
+  // Midnight UTC on a date, in Unix seconds
+  const utc = (year: number, month: number, day = 1) =>
+    new anchor.BN(Date.UTC(year, month - 1, day) / 1000);
+
+  type Period = { kind: any; start: anchor.BN; end: anchor.BN };
+
+  // Annual period of a jurisdiction on the default calendar-year fiscal calendar,
+  // spanning taxYear through endTaxYear
+  const calendarYears = (taxYear: number, endTaxYear = taxYear): Period => ({
+    kind: { annual: {} },
+    start: utc(taxYear, 1),
+    end: utc(endTaxYear + 1, 1),
+  });
+  const ALL_TYPES = [
+    { taxCompliance: {} },
+    { auditComplete: {} },
+    { reportingComplete: {} },
+    { quarterlyReview: {} },
+    { annualReview: {} },
+  ];
+
+  const findSchemaPda = (id: number, version = 1) => {
+    const idBytes = Buffer.alloc(4);
+    idBytes.writeUInt32LE(id);
+    const versionBytes = Buffer.alloc(2);
+    versionBytes.writeUInt16LE(version);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("schema"), idBytes, versionBytes],
+      program.programId
+    );
+  };
+
+  // Each attestation type has a base schema: id = the type's index, version 1
+  const baseSchemaPda = (attestationType: object) =>
+    findSchemaPda(
+      ALL_TYPES.findIndex((t) => Object.keys(t)[0] === Object.keys(attestationType)[0])
+    )[0];
+
+  // AttestationSchema.required_fields flags
+  const REQUIRES_SUBDIVISION = 1 << 0;
+  const REQUIRES_LISTED_WALLETS = 1 << 2;
+
+  const findMultisigPda = () =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("multisig")],
+      program.programId
+    );
+
+  const findVaultPda = () =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("multisig_vault")],
+      program.programId
+    );
+
+  const findProposalPda = (index: anchor.BN) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), index.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+  // Helper to fund a fresh keypair so it can pay for accounts
+  const airdrop = async (pubkey: PublicKey, sol = 2) => {
+    const sig = await provider.connection.requestAirdrop(
+      pubkey,
+      sol * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+  };
+
   // Helper to create a unique 32-byte audit hash
   let hashCounter = 0;
   const makeAuditHash = (): number[] => {
@@ -34,15 +224,55 @@ describe("attestation", () => {
     return hash;
   };
 
+  // Helper to register an attestation schema
+  const addSchema = (
+    id: number,
+    attestationType: any,
+    options: {
+      version?: number;
+      requiredFields?: number;
+      defaultValidity?: number;
+      jurisdictions?: number[][];
+      admin?: Keypair;
+    } = {}
+  ) => {
+    const version = options.version ?? 1;
+    const nameHash = Array.from(createHash("sha256").update(`schema-${id}-v${version}`).digest());
+    const builder = program.methods
+      .addSchema(
+        id,
+        version,
+        nameHash,
+        attestationType,
+        options.requiredFields ?? 0,
+        new anchor.BN(options.defaultValidity ?? 0),
+        options.jurisdictions ?? []
+      )
+      .accounts({
+        state: findStatePda()[0],
+        schema: findSchemaPda(id, version)[0],
+        authority: options.admin ? options.admin.publicKey : authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      });
+    return options.admin ? builder.signers([options.admin]).rpc() : builder.rpc();
+  };
+
   // Helper to create an attestation with defaults
   const createAttestation = async (
     overrides: {
-      jurisdiction?: any;
+      jurisdiction?: number[];
+      subdivision?: number[];
       attestationType?: any;
+      schema?: PublicKey;
       taxYear?: number;
+      endTaxYear?: number;
+      period?: Period;
       auditHash?: number[];
       expiresAt?: anchor.BN;
       wallets?: PublicKey[];
+      requireConsent?: boolean;
+      initialStatus?: any;
+      preInstructions?: TransactionInstruction[];
       signers?: Keypair[];
       authorityPubkey?: PublicKey;
     } = {}
@@ -50,28 +280,57 @@ describe("attestation", () => {
     const auditHash = overrides.auditHash ?? makeAuditHash();
     const wallets = overrides.wallets ?? [Keypair.generate().publicKey];
     const [statePda] = findStatePda();
-    const [attestationPda] = findAttestationPda(auditHash);
     const expiresAt =
       overrides.expiresAt ?? new anchor.BN(Math.floor(Date.now() / 1000) + 86400 * 365);
 
+    const issuerAuthority = overrides.authorityPubkey ?? authority.publicKey;
+    const [attestationPda] = await nextAttestationPda(issuerAuthority);
+    const taxYear = overrides.taxYear ?? 2025;
+    const endTaxYear = overrides.endTaxYear ?? taxYear;
+    const period = overrides.period ?? calendarYears(taxYear, endTaxYear);
+    const scope = {
+      jurisdiction: overrides.jurisdiction ?? iso("US"),
+      subdivision: overrides.subdivision ?? NO_SUBDIVISION,
+      attestationType: overrides.attestationType ?? { taxCompliance: {} },
+      taxYear,
+      endTaxYear,
+      periodKind: period.kind,
+      periodStart: period.start,
+    };
+
     const accounts: any = {
       state: statePda,
       attestation: attestationPda,
-      authority: overrides.authorityPubkey ?? authority.publicKey,
+      issuer: findIssuerPda(issuerAuthority)[0],
+      jurisdictionConfig: findJurisdictionPda(scope.jurisdiction)[0],
+      schema: overrides.schema ?? baseSchemaPda(scope.attestationType),
+      authority: issuerAuthority,
       systemProgram: SystemProgram.programId,
+      instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
     };
 
     const builder = program.methods
       .createAttestation(
-        overrides.jurisdiction ?? { us: {} },
-        overrides.attestationType ?? { taxCompliance: {} },
-        overrides.taxYear ?? 2025,
+        scope.jurisdiction,
+        scope.subdivision,
+        scope.attestationType,
+        scope.taxYear,
+        scope.endTaxYear,
+        period.kind,
+        period.start,
+        period.end,
         auditHash,
         expiresAt,
-        wallets
+        wallets,
+        overrides.requireConsent ?? false,
+        overrides.initialStatus ?? { active: {} }
       )
-      .accounts(accounts);
+      .accounts(accounts)
+      .remainingAccounts(walletAccounts(attestationPda, wallets, scope));
 
+    if (overrides.preInstructions) {
+      builder.preInstructions(overrides.preInstructions);
+    }
     if (overrides.signers) {
       builder.signers(overrides.signers);
     }
@@ -85,6 +344,53 @@ describe("attestation", () => {
   // ============================================
 
   describe("initialize", () => {
+    it("fails when the signer is not the upgrade authority", async () => {
+      const stranger = Keypair.generate();
+      await airdrop(stranger.publicKey);
+      const [statePda] = findStatePda();
+
+      try {
+        await program.methods
+          .initialize()
+          .accounts({
+            state: statePda,
+            authority: stranger.publicKey,
+            program: program.programId,
+            programData: programDataPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([stranger])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("NotUpgradeAuthority");
+      }
+    });
+
+    it("fails with a program data account for another program", async () => {
+      const [statePda] = findStatePda();
+      const [otherProgramData] = PublicKey.findProgramAddressSync(
+        [Keypair.generate().publicKey.toBuffer()],
+        new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+      );
+
+      try {
+        await program.methods
+          .initialize()
+          .accounts({
+            state: statePda,
+            authority: authority.publicKey,
+            program: program.programId,
+            programData: otherProgramData,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err).to.exist;
+      }
+    });
+
     it("initializes program state", async () => {
       const [statePda, bump] = findStatePda();
 
@@ -93,6 +399,8 @@ describe("attestation", () => {
         .accounts({
           state: statePda,
           authority: authority.publicKey,
+          program: program.programId,
+          programData: programDataPda,
           systemProgram: SystemProgram.programId,
         })
         .rpc();
@@ -101,6 +409,8 @@ describe("attestation", () => {
       expect(state.authority.toBase58()).to.equal(authority.publicKey.toBase58());
       expect(state.attestationCount.toNumber()).to.equal(0);
       expect(state.bump).to.equal(bump);
+      expect(state.minTaxYear).to.equal(2009);
+      expect(state.maxTaxYear).to.equal(2100);
     });
 
     it("fails to initialize twice", async () => {
@@ -112,6 +422,8 @@ describe("attestation", () => {
           .accounts({
             state: statePda,
             authority: authority.publicKey,
+            program: program.programId,
+            programData: programDataPda,
             systemProgram: SystemProgram.programId,
           })
           .rpc();
@@ -124,499 +436,3669 @@ describe("attestation", () => {
   });
 
   // ============================================
-  // Create Attestation
+  // Jurisdiction Registry
   // ============================================
 
-  describe("create_attestation", () => {
-    it("creates attestation with a single wallet", async () => {
-      const wallet1 = Keypair.generate().publicKey;
-      const auditHash = makeAuditHash();
-      const expiresAt = new anchor.BN(Math.floor(Date.now() / 1000) + 86400 * 365);
+  describe("jurisdiction registry", () => {
+    const addJurisdiction = (code: number[], admin: Keypair | null = null) => {
+      const builder = program.methods
+        .addJurisdiction(code)
+        .accounts({
+          state: findStatePda()[0],
+          jurisdictionConfig: findJurisdictionPda(code)[0],
+          authority: admin ? admin.publicKey : authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        });
+      return admin ? builder.signers([admin]).rpc() : builder.rpc();
+    };
 
-      const { attestationPda } = await createAttestation({
-        jurisdiction: { us: {} },
-        attestationType: { taxCompliance: {} },
-        taxYear: 2025,
-        auditHash,
-        expiresAt,
-        wallets: [wallet1],
-      });
+    it("registers the supported jurisdictions", async () => {
+      for (const code of ALL_JURISDICTIONS) {
+        await addJurisdiction(code);
+      }
 
-      const attestation = await program.account.attestation.fetch(attestationPda);
-      expect(attestation.authority.toBase58()).to.equal(authority.publicKey.toBase58());
-      expect(attestation.jurisdiction).to.deep.equal({ us: {} });
-      expect(attestation.attestationType).to.deep.equal({ taxCompliance: {} });
-      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ active: {} }));
-      expect(attestation.taxYear).to.equal(2025);
-      expect(attestation.auditHash).to.deep.equal(auditHash);
-      expect(attestation.expiresAt.toNumber()).to.equal(expiresAt.toNumber());
-      expect(attestation.issuedAt.toNumber()).to.be.greaterThan(0);
-      expect(attestation.revokedAt.toNumber()).to.equal(0);
-      expect(attestation.numWallets).to.equal(1);
-      expect(attestation.wallets.length).to.equal(1);
-      expect(attestation.wallets[0].toBase58()).to.equal(wallet1.toBase58());
+      const [configPda, bump] = findJurisdictionPda(iso("BR"));
+      const config = await program.account.jurisdictionConfig.fetch(configPda);
+      expect(config.code).to.deep.equal(iso("BR"));
+      expect(config.bump).to.equal(bump);
+      expect(config.registeredAt.toNumber()).to.be.greaterThan(0);
     });
 
-    it("creates attestation with multiple wallets (3)", async () => {
-      const wallets = [
-        Keypair.generate().publicKey,
-        Keypair.generate().publicKey,
-        Keypair.generate().publicKey,
-      ];
+    it("fails with a code that is not two uppercase letters", async () => {
+      try {
+        await addJurisdiction(iso("pt"));
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("InvalidJurisdiction");
+      }
+    });
 
-      const { attestationPda } = await createAttestation({ wallets });
+    it("fails to register a jurisdiction from a non-admin", async () => {
+      const stranger = Keypair.generate();
+      await airdrop(stranger.publicKey);
 
-      const attestation = await program.account.attestation.fetch(attestationPda);
-      expect(attestation.numWallets).to.equal(3);
-      expect(attestation.wallets.length).to.equal(3);
-      for (let i = 0; i < 3; i++) {
-        expect(attestation.wallets[i].toBase58()).to.equal(wallets[i].toBase58());
+      try {
+        await addJurisdiction(iso("PT"), stranger);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("Unauthorized");
       }
     });
 
-    it("creates attestation with max wallets (10)", async () => {
-      const wallets = Array.from({ length: 10 }, () => Keypair.generate().publicKey);
+    it("sets a jurisdiction's subdivisions", async () => {
+      const [configPda] = findJurisdictionPda(iso("US"));
+      const subdivisions = ["CA", "NY"].map(subdivision);
 
-      const { attestationPda } = await createAttestation({ wallets });
+      await program.methods
+        .setJurisdictionSubdivisions(subdivisions)
+        .accounts({
+          state: findStatePda()[0],
+          jurisdictionConfig: configPda,
+          authority: authority.publicKey,
+        })
+        .rpc();
 
-      const attestation = await program.account.attestation.fetch(attestationPda);
-      expect(attestation.numWallets).to.equal(10);
-      expect(attestation.wallets.length).to.equal(10);
-      for (let i = 0; i < 10; i++) {
-        expect(attestation.wallets[i].toBase58()).to.equal(wallets[i].toBase58());
-      }
+      const config = await program.account.jurisdictionConfig.fetch(configPda);
+      expect(config.subdivisions).to.deep.equal(subdivisions);
     });
 
-    it("fails with 0 wallets", async () => {
+    it("fails with a malformed subdivision code", async () => {
       try {
-        await createAttestation({ wallets: [] });
+        await program.methods
+          .setJurisdictionSubdivisions([[0x43, 0, 0x41]])
+          .accounts({
+            state: findStatePda()[0],
+            jurisdictionConfig: findJurisdictionPda(iso("US"))[0],
+            authority: authority.publicKey,
+          })
+          .rpc();
         expect.fail("should have thrown");
       } catch (err: any) {
-        const errMsg = err.toString();
-        expect(errMsg).to.contain("InvalidWalletCount");
+        expect(err.toString()).to.contain("InvalidSubdivision");
       }
     });
 
-    it("fails with 11 wallets (exceeds max)", async () => {
-      const wallets = Array.from({ length: 11 }, () => Keypair.generate().publicKey);
-      try {
-        await createAttestation({ wallets });
-        expect.fail("should have thrown");
-      } catch (err: any) {
-        const errMsg = err.toString();
-        expect(errMsg).to.contain("InvalidWalletCount");
-      }
+    it("starts the fiscal year on 1 January by default", async () => {
+      const config = await program.account.jurisdictionConfig.fetch(findJurisdictionPda(iso("US"))[0]);
+      expect(config.fiscalYearStartMonth).to.equal(1);
+      expect(config.fiscalYearStartDay).to.equal(1);
     });
 
-    it("fails with wrong authority", async () => {
-      const fakeAuthority = Keypair.generate();
+    it("sets a jurisdiction's fiscal calendar", async () => {
+      const [configPda] = findJurisdictionPda(iso("AU"));
 
-      // Airdrop SOL to fake authority so it can pay
-      const sig = await provider.connection.requestAirdrop(
-        fakeAuthority.publicKey,
-        2 * anchor.web3.LAMPORTS_PER_SOL
-      );
-      await provider.connection.confirmTransaction(sig);
+      await program.methods
+        .setFiscalCalendar(7, 1)
+        .accounts({
+          state: findStatePda()[0],
+          jurisdictionConfig: configPda,
+          authority: authority.publicKey,
+        })
+        .rpc();
 
-      const auditHash = makeAuditHash();
-      const [statePda] = findStatePda();
-      const [attestationPda] = findAttestationPda(auditHash);
+      const config = await program.account.jurisdictionConfig.fetch(configPda);
+      expect(config.fiscalYearStartMonth).to.equal(7);
+      expect(config.fiscalYearStartDay).to.equal(1);
+    });
 
+    it("fails with a fiscal year starting after the 28th", async () => {
       try {
         await program.methods
-          .createAttestation(
-            { us: {} },
-            { taxCompliance: {} },
-            2025,
-            auditHash,
-            new anchor.BN(Math.floor(Date.now() / 1000) + 86400),
-            [Keypair.generate().publicKey]
-          )
+          .setFiscalCalendar(3, 31)
           .accounts({
-            state: statePda,
-            attestation: attestationPda,
-            authority: fakeAuthority.publicKey,
-            systemProgram: SystemProgram.programId,
+            state: findStatePda()[0],
+            jurisdictionConfig: findJurisdictionPda(iso("AU"))[0],
+            authority: authority.publicKey,
           })
-          .signers([fakeAuthority])
           .rpc();
         expect.fail("should have thrown");
       } catch (err: any) {
-        const errMsg = err.toString();
-        expect(errMsg).to.contain("Unauthorized");
+        expect(err.toString()).to.contain("InvalidFiscalCalendar");
       }
     });
 
-    it("fails with duplicate audit_hash", async () => {
-      const auditHash = makeAuditHash();
-      await createAttestation({ auditHash });
+    it("removes a jurisdiction", async () => {
+      const [configPda] = findJurisdictionPda(iso("IN"));
+      await addJurisdiction(iso("IN"));
+
+      await program.methods
+        .removeJurisdiction()
+        .accounts({
+          state: findStatePda()[0],
+          jurisdictionConfig: configPda,
+          authority: authority.publicKey,
+        })
+        .rpc();
+
+      const info = await provider.connection.getAccountInfo(configPda);
+      expect(info).to.equal(null);
+    });
+  });
+
+  // ============================================
+  // Schema Registry
+  // ============================================
+
+  describe("schema registry", () => {
+    it("registers a base schema for each attestation type", async () => {
+      for (const [id, attestationType] of ALL_TYPES.entries()) {
+        await addSchema(id, attestationType);
+      }
+
+      const [schemaPda, bump] = findSchemaPda(2);
+      const schema = await program.account.attestationSchema.fetch(schemaPda);
+      expect(schema.id).to.equal(2);
+      expect(schema.version).to.equal(1);
+      expect(schema.bump).to.equal(bump);
+      expect(schema.attestationType).to.deep.equal({ reportingComplete: {} });
+      expect(schema.jurisdictions).to.deep.equal([]);
+      expect(schema.deprecated).to.equal(false);
+    });
 
+    it("fails with unknown required-field flags", async () => {
       try {
-        await createAttestation({ auditHash });
+        await addSchema(90, { taxCompliance: {} }, { requiredFields: 1 << 7 });
         expect.fail("should have thrown");
       } catch (err: any) {
-        // Anchor will fail because the PDA account already exists
-        expect(err).to.exist;
+        expect(err.toString()).to.contain("InvalidSchema");
       }
     });
 
-    it("creates attestations for different jurisdictions", async () => {
-      const jurisdictions = [
-        { us: {} },
-        { eu: {} },
-        { br: {} },
-        { uk: {} },
-        { jp: {} },
-      ];
+    it("fails to register a schema from a non-admin", async () => {
+      const stranger = Keypair.generate();
+      await airdrop(stranger.publicKey);
 
-      for (const jurisdiction of jurisdictions) {
-        const { attestationPda } = await createAttestation({ jurisdiction });
-        const attestation = await program.account.attestation.fetch(attestationPda);
-        expect(attestation.jurisdiction).to.deep.equal(jurisdiction);
+      try {
+        await addSchema(91, { taxCompliance: {} }, { admin: stranger });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("Unauthorized");
       }
     });
+  });
 
-    it("creates attestations with different types", async () => {
-      const types = [
-        { taxCompliance: {} },
-        { auditComplete: {} },
-        { reportingComplete: {} },
-        { quarterlyReview: {} },
-        { annualReview: {} },
-      ];
-
-      for (const attestationType of types) {
-        const { attestationPda } = await createAttestation({ attestationType });
-        const attestation = await program.account.attestation.fetch(attestationPda);
-        expect(attestation.attestationType).to.deep.equal(attestationType);
+  // ============================================
+  // Issuer Registry
+  // ============================================
+
+  describe("issuer registry", () => {
+    const brIssuer = Keypair.generate();
+    const [brIssuerPda] = findIssuerPda(brIssuer.publicKey);
+
+    const addIssuer = (
+      issuer: PublicKey,
+      jurisdictions: any[],
+      attestationTypes: any[],
+      admin: Keypair | null = null
+    ) => {
+      const builder = program.methods
+        .addIssuer(issuer, jurisdictions, attestationTypes)
+        .accounts({
+          state: findStatePda()[0],
+          issuer: findIssuerPda(issuer)[0],
+          authority: admin ? admin.publicKey : authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        });
+      return admin ? builder.signers([admin]).rpc() : builder.rpc();
+    };
+
+    const manageIssuerAccounts = (issuerPda: PublicKey) => ({
+      state: findStatePda()[0],
+      issuer: issuerPda,
+      authority: authority.publicKey,
+    });
+
+    before(async () => {
+      await airdrop(brIssuer.publicKey);
+    });
+
+    it("registers the provider wallet with full issuing rights", async () => {
+      await addIssuer(authority.publicKey, ALL_JURISDICTIONS, ALL_TYPES);
+
+      const issuer = await program.account.issuer.fetch(findIssuerPda(authority.publicKey)[0]);
+      expect(issuer.authority.toBase58()).to.equal(authority.publicKey.toBase58());
+      expect(JSON.stringify(issuer.status)).to.equal(JSON.stringify({ active: {} }));
+      expect(issuer.jurisdictions.length).to.equal(9);
+      expect(issuer.attestationTypes.length).to.equal(5);
+      expect(issuer.attestationCount.toNumber()).to.equal(0);
+    });
+
+    it("registers a BR-only issuer", async () => {
+      await addIssuer(brIssuer.publicKey, [iso("BR")], [{ taxCompliance: {} }]);
+
+      const issuer = await program.account.issuer.fetch(brIssuerPda);
+      expect(issuer.jurisdictions).to.deep.equal([iso("BR")]);
+      expect(issuer.attestationTypes).to.deep.equal([{ taxCompliance: {} }]);
+    });
+
+    it("lets the BR issuer attest for BR", async () => {
+      const { attestationPda } = await createAttestation({
+        jurisdiction: iso("BR"),
+        authorityPubkey: brIssuer.publicKey,
+        signers: [brIssuer],
+      });
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.authority.toBase58()).to.equal(brIssuer.publicKey.toBase58());
+
+      const issuer = await program.account.issuer.fetch(brIssuerPda);
+      expect(issuer.attestationCount.toNumber()).to.equal(1);
+    });
+
+    it("fails when the BR issuer attests for US", async () => {
+      try {
+        await createAttestation({
+          jurisdiction: iso("US"),
+          authorityPubkey: brIssuer.publicKey,
+          signers: [brIssuer],
+        });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("JurisdictionNotAllowed");
       }
     });
 
-    it("increments attestation_count correctly", async () => {
+    it("fails for a jurisdiction that is not registered", async () => {
+      await program.methods
+        .updateIssuer([iso("BR"), iso("PT")], [{ taxCompliance: {} }])
+        .accounts(manageIssuerAccounts(brIssuerPda))
+        .rpc();
+
+      try {
+        await createAttestation({
+          jurisdiction: iso("PT"),
+          authorityPubkey: brIssuer.publicKey,
+          signers: [brIssuer],
+        });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("InvalidJurisdiction");
+      }
+    });
+
+    it("fails when the BR issuer uses a type it was not granted", async () => {
+      try {
+        await createAttestation({
+          jurisdiction: iso("BR"),
+          attestationType: { auditComplete: {} },
+          authorityPubkey: brIssuer.publicKey,
+          signers: [brIssuer],
+        });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("AttestationTypeNotAllowed");
+      }
+    });
+
+    it("updates issuer rights", async () => {
+      await program.methods
+        .updateIssuer([iso("BR")], [{ taxCompliance: {} }, { auditComplete: {} }])
+        .accounts(manageIssuerAccounts(brIssuerPda))
+        .rpc();
+
+      await createAttestation({
+        jurisdiction: iso("BR"),
+        attestationType: { auditComplete: {} },
+        authorityPubkey: brIssuer.publicKey,
+        signers: [brIssuer],
+      });
+    });
+
+    it("fails with a malformed jurisdiction code", async () => {
+      try {
+        await program.methods
+          .updateIssuer([iso("B1")], [{ taxCompliance: {} }])
+          .accounts(manageIssuerAccounts(brIssuerPda))
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("InvalidJurisdiction");
+      }
+    });
+
+    it("fails with duplicate jurisdictions", async () => {
+      try {
+        await program.methods
+          .updateIssuer([iso("BR"), iso("BR")], [{ taxCompliance: {} }])
+          .accounts(manageIssuerAccounts(brIssuerPda))
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("InvalidIssuerRights");
+      }
+    });
+
+    it("blocks a suspended issuer and restores it on reactivation", async () => {
+      await program.methods.suspendIssuer().accounts(manageIssuerAccounts(brIssuerPda)).rpc();
+
+      try {
+        await createAttestation({
+          jurisdiction: iso("BR"),
+          authorityPubkey: brIssuer.publicKey,
+          signers: [brIssuer],
+        });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("IssuerSuspended");
+      }
+
+      await program.methods.reactivateIssuer().accounts(manageIssuerAccounts(brIssuerPda)).rpc();
+      await createAttestation({
+        jurisdiction: iso("BR"),
+        authorityPubkey: brIssuer.publicKey,
+        signers: [brIssuer],
+      });
+    });
+
+    it("fails to add an issuer from a non-admin", async () => {
+      try {
+        await addIssuer(Keypair.generate().publicKey, [iso("US")], ALL_TYPES, brIssuer);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("Unauthorized");
+      }
+    });
+
+    it("removes an issuer", async () => {
+      const temp = Keypair.generate().publicKey;
+      const [tempPda] = findIssuerPda(temp);
+      await addIssuer(temp, [iso("US")], ALL_TYPES);
+
+      await program.methods
+        .removeIssuer()
+        .accounts(manageIssuerAccounts(tempPda))
+        .rpc();
+
+      const info = await provider.connection.getAccountInfo(tempPda);
+      expect(info).to.equal(null);
+    });
+  });
+
+  // ============================================
+  // Create Attestation
+  // ============================================
+
+  describe("create_attestation", () => {
+    it("creates attestation with a single wallet", async () => {
+      const wallet1 = Keypair.generate().publicKey;
+      const auditHash = makeAuditHash();
+      const expiresAt = new anchor.BN(Math.floor(Date.now() / 1000) + 86400 * 365);
+
+      const { attestationPda } = await createAttestation({
+        jurisdiction: iso("US"),
+        attestationType: { taxCompliance: {} },
+        taxYear: 2025,
+        auditHash,
+        expiresAt,
+        wallets: [wallet1],
+      });
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.authority.toBase58()).to.equal(authority.publicKey.toBase58());
+      expect(attestation.jurisdiction).to.deep.equal(iso("US"));
+      expect(attestation.attestationType).to.deep.equal({ taxCompliance: {} });
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ active: {} }));
+      expect(attestation.taxYear).to.equal(2025);
+      expect(attestation.auditHash).to.deep.equal(auditHash);
+      expect(attestation.expiresAt.toNumber()).to.equal(expiresAt.toNumber());
+      expect(attestation.issuedAt.toNumber()).to.be.greaterThan(0);
+      expect(attestation.revokedAt.toNumber()).to.equal(0);
+      expect(attestation.supersedes.toBase58()).to.equal(PublicKey.default.toBase58());
+      expect(attestation.supersededBy.toBase58()).to.equal(PublicKey.default.toBase58());
+      expect(attestation.numWallets).to.equal(1);
+      expect(attestation.wallets.length).to.equal(1);
+      expect(attestation.wallets[0].toBase58()).to.equal(wallet1.toBase58());
+    });
+
+    it("creates attestation with multiple wallets (3)", async () => {
+      const wallets = [
+        Keypair.generate().publicKey,
+        Keypair.generate().publicKey,
+        Keypair.generate().publicKey,
+      ];
+
+      const { attestationPda } = await createAttestation({ wallets });
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.numWallets).to.equal(3);
+      expect(attestation.wallets.length).to.equal(3);
+      for (let i = 0; i < 3; i++) {
+        expect(attestation.wallets[i].toBase58()).to.equal(wallets[i].toBase58());
+      }
+    });
+
+    it("creates attestation with max wallets (10)", async () => {
+      const wallets = Array.from({ length: 10 }, () => Keypair.generate().publicKey);
+
+      const { attestationPda } = await createAttestation({ wallets });
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.numWallets).to.equal(10);
+      expect(attestation.wallets.length).to.equal(10);
+      for (let i = 0; i < 10; i++) {
+        expect(attestation.wallets[i].toBase58()).to.equal(wallets[i].toBase58());
+      }
+    });
+
+    it("fails with 0 wallets", async () => {
+      try {
+        await createAttestation({ wallets: [] });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        const errMsg = err.toString();
+        expect(errMsg).to.contain("InvalidWalletCount");
+      }
+    });
+
+    it("fails with 11 wallets (exceeds max)", async () => {
+      const wallets = Array.from({ length: 11 }, () => Keypair.generate().publicKey);
+      try {
+        await createAttestation({ wallets });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        const errMsg = err.toString();
+        expect(errMsg).to.contain("InvalidWalletCount");
+      }
+    });
+
+    it("fails with wrong authority", async () => {
+      const fakeAuthority = Keypair.generate();
+
+      // Airdrop SOL to fake authority so it can pay
+      const sig = await provider.connection.requestAirdrop(
+        fakeAuthority.publicKey,
+        2 * anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+
+      const auditHash = makeAuditHash();
       const [statePda] = findStatePda();
-      const stateBefore = await program.account.programState.fetch(statePda);
-      const countBefore = stateBefore.attestationCount.toNumber();
+      // Never created: the signer has no issuer account to take a sequence from
+      const [attestationPda] = findAttestationPda(fakeAuthority.publicKey, new anchor.BN(0));
 
-      await createAttestation();
-      await createAttestation();
-      await createAttestation();
+      try {
+        await program.methods
+          .createAttestation(
+            iso("US"),
+            NO_SUBDIVISION,
+            { taxCompliance: {} },
+            2025,
+            2025,
+            { annual: {} },
+            utc(2025, 1),
+            utc(2026, 1),
+            auditHash,
+            new anchor.BN(Math.floor(Date.now() / 1000) + 86400),
+            [Keypair.generate().publicKey],
+            false,
+            { active: {} }
+          )
+          .accounts({
+            state: statePda,
+            attestation: attestationPda,
+            issuer: findIssuerPda(fakeAuthority.publicKey)[0],
+            jurisdictionConfig: findJurisdictionPda(iso("US"))[0],
+            schema: baseSchemaPda({ taxCompliance: {} }),
+            authority: fakeAuthority.publicKey,
+            systemProgram: SystemProgram.programId,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          })
+          .signers([fakeAuthority])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        // Signers without an Issuer account cannot create attestations
+        const errMsg = err.toString();
+        expect(errMsg).to.contain("AccountNotInitialized");
+      }
+    });
+
+    it("allows an issuer to reuse an audit hash", async () => {
+      const auditHash = makeAuditHash();
+      const first = await createAttestation({ auditHash });
+      const second = await createAttestation({ auditHash });
+
+      expect(second.attestationPda.toBase58()).to.not.equal(first.attestationPda.toBase58());
+      const a = await program.account.attestation.fetch(first.attestationPda);
+      const b = await program.account.attestation.fetch(second.attestationPda);
+      expect(b.sequence.toNumber()).to.equal(a.sequence.toNumber() + 1);
+      expect(b.auditHash).to.deep.equal(a.auditHash);
+    });
+
+    it("creates attestations for different jurisdictions", async () => {
+      const jurisdictions = [
+        iso("US"),
+        iso("EU"),
+        iso("BR"),
+        iso("GB"),
+        iso("JP"),
+      ];
+
+      for (const jurisdiction of jurisdictions) {
+        const { attestationPda } = await createAttestation({ jurisdiction });
+        const attestation = await program.account.attestation.fetch(attestationPda);
+        expect(attestation.jurisdiction).to.deep.equal(jurisdiction);
+      }
+    });
+
+    it("creates an attestation for a registered subdivision", async () => {
+      const { attestationPda } = await createAttestation({ subdivision: subdivision("CA") });
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.jurisdiction).to.deep.equal(iso("US"));
+      expect(attestation.subdivision).to.deep.equal(subdivision("CA"));
+    });
+
+    it("fails for a subdivision the jurisdiction does not list", async () => {
+      try {
+        await createAttestation({ subdivision: subdivision("TX") });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("InvalidSubdivision");
+      }
+    });
+
+    it("keeps national and subdivision attestations of a wallet apart", async () => {
+      const wallets = [Keypair.generate().publicKey];
+      await createAttestation({ wallets, taxYear: 2024 });
+      const { attestationPda } = await createAttestation({
+        wallets,
+        taxYear: 2024,
+        subdivision: subdivision("NY"),
+      });
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.subdivision).to.deep.equal(subdivision("NY"));
+    });
+
+    describe("fiscal periods", () => {
+      const expectInvalidPeriod = async (
+        jurisdiction: number[],
+        period: Period,
+        taxYears: { taxYear?: number; endTaxYear?: number } = {}
+      ) => {
+        try {
+          await createAttestation({ jurisdiction, period, ...taxYears });
+          expect.fail("should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.contain("InvalidPeriod");
+        }
+      };
+
+      it("records the period covered", async () => {
+        const { attestationPda } = await createAttestation({ taxYear: 2024 });
+
+        const attestation = await program.account.attestation.fetch(attestationPda);
+        expect(attestation.periodKind).to.deep.equal({ annual: {} });
+        expect(attestation.periodStart.toNumber()).to.equal(utc(2024, 1).toNumber());
+        expect(attestation.periodEnd.toNumber()).to.equal(utc(2025, 1).toNumber());
+      });
+
+      it("follows a July-June fiscal year", async () => {
+        // Tax year 2024 is the fiscal year starting in July 2024
+        const period = { kind: { annual: {} }, start: utc(2024, 7), end: utc(2025, 7) };
+        const { attestationPda } = await createAttestation({
+          jurisdiction: iso("AU"),
+          taxYear: 2024,
+          period,
+        });
+
+        const attestation = await program.account.attestation.fetch(attestationPda);
+        expect(attestation.periodStart.toNumber()).to.equal(utc(2024, 7).toNumber());
+      });
+
+      it("fails for a calendar year in a July-June jurisdiction", async () => {
+        await expectInvalidPeriod(iso("AU"), calendarYears(2025));
+      });
+
+      it("accepts fiscal quarters and months", async () => {
+        await createAttestation({
+          jurisdiction: iso("AU"),
+          taxYear: 2024,
+          period: { kind: { quarterly: {} }, start: utc(2024, 10), end: utc(2025, 1) },
+        });
+        await createAttestation({
+          jurisdiction: iso("BR"),
+          period: { kind: { monthly: {} }, start: utc(2025, 2), end: utc(2025, 3) },
+        });
+      });
+
+      it("fails for a quarter that does not start on a quarter boundary", async () => {
+        await expectInvalidPeriod(
+          iso("AU"),
+          { kind: { quarterly: {} }, start: utc(2024, 8), end: utc(2024, 11) },
+          { taxYear: 2024 }
+        );
+      });
+
+      it("fails for a period outside the tax year it is labelled with", async () => {
+        // A well-formed annual period, but for 2019
+        await expectInvalidPeriod(iso("US"), calendarYears(2019), { taxYear: 2025 });
+        // The fiscal year ending in June 2025 is tax year 2024, not 2025
+        await expectInvalidPeriod(
+          iso("AU"),
+          { kind: { annual: {} }, start: utc(2024, 7), end: utc(2025, 7) },
+          { taxYear: 2025 }
+        );
+        // A 2025 quarter labelled 2024
+        await expectInvalidPeriod(
+          iso("US"),
+          { kind: { quarterly: {} }, start: utc(2025, 4), end: utc(2025, 7) },
+          { taxYear: 2024 }
+        );
+      });
+
+      it("fails for a period that does not end in the last tax year", async () => {
+        await expectInvalidPeriod(iso("US"), calendarYears(2024), {
+          taxYear: 2024,
+          endTaxYear: 2025,
+        });
+        await expectInvalidPeriod(
+          iso("US"),
+          { kind: { custom: {} }, start: utc(2024, 3), end: utc(2026, 2) },
+          { taxYear: 2024, endTaxYear: 2024 }
+        );
+      });
+
+      it("fails for a month that does not run a whole month", async () => {
+        await expectInvalidPeriod(iso("BR"), {
+          kind: { monthly: {} },
+          start: utc(2025, 1, 15),
+          end: utc(2025, 2, 15),
+        });
+        await expectInvalidPeriod(iso("BR"), {
+          kind: { monthly: {} },
+          start: utc(2025, 1),
+          end: utc(2025, 1, 31),
+        });
+      });
+
+      it("accepts any non-empty custom period", async () => {
+        await createAttestation({
+          period: { kind: { custom: {} }, start: utc(2025, 3, 14), end: utc(2025, 9, 2) },
+        });
+        await expectInvalidPeriod(iso("US"), {
+          kind: { custom: {} },
+          start: utc(2025, 3, 14),
+          end: utc(2025, 3, 14),
+        });
+      });
+    });
+
+    describe("under a registered schema", () => {
+      // DAC8 crypto-asset reporting: EU only, listed wallets, one year by default
+      const DAC8 = 100;
+      const validity = 365 * 86400;
+
+      before(async () => {
+        await addSchema(DAC8, { reportingComplete: {} }, {
+          requiredFields: REQUIRES_LISTED_WALLETS,
+          defaultValidity: validity,
+          jurisdictions: [iso("EU")],
+        });
+      });
+
+      it("records the schema and applies its default validity", async () => {
+        const { attestationPda } = await createAttestation({
+          jurisdiction: iso("EU"),
+          attestationType: { reportingComplete: {} },
+          schema: findSchemaPda(DAC8)[0],
+          expiresAt: new anchor.BN(0),
+        });
+
+        const attestation = await program.account.attestation.fetch(attestationPda);
+        expect(attestation.schemaId).to.equal(DAC8);
+        expect(attestation.schemaVersion).to.equal(1);
+        expect(attestation.expiresAt.toNumber()).to.equal(
+          attestation.issuedAt.toNumber() + validity
+        );
+      });
+
+      it("fails for a jurisdiction the schema does not allow", async () => {
+        try {
+          await createAttestation({
+            jurisdiction: iso("US"),
+            attestationType: { reportingComplete: {} },
+            schema: findSchemaPda(DAC8)[0],
+          });
+          expect.fail("should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.contain("SchemaJurisdictionNotAllowed");
+        }
+      });
+
+      it("fails when the schema is for another attestation type", async () => {
+        try {
+          await createAttestation({
+            jurisdiction: iso("EU"),
+            attestationType: { taxCompliance: {} },
+            schema: findSchemaPda(DAC8)[0],
+          });
+          expect.fail("should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.contain("SchemaTypeMismatch");
+        }
+      });
+
+      it("fails when a field the schema requires is missing", async () => {
+        const stateReturn = 101;
+        await addSchema(stateReturn, { taxCompliance: {} }, {
+          requiredFields: REQUIRES_SUBDIVISION,
+        });
+
+        try {
+          await createAttestation({ schema: findSchemaPda(stateReturn)[0] });
+          expect.fail("should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.contain("SchemaFieldMissing");
+        }
+      });
+
+      it("fails under a deprecated schema", async () => {
+        const [schemaPda] = findSchemaPda(DAC8);
+        await program.methods
+          .deprecateSchema()
+          .accounts({
+            state: findStatePda()[0],
+            schema: schemaPda,
+            authority: authority.publicKey,
+          })
+          .rpc();
+
+        try {
+          await createAttestation({
+            jurisdiction: iso("EU"),
+            attestationType: { reportingComplete: {} },
+            schema: schemaPda,
+          });
+          expect.fail("should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.contain("SchemaDeprecated");
+        }
+      });
+    });
+
+    it("creates attestations with different types", async () => {
+      const types = [
+        { taxCompliance: {} },
+        { auditComplete: {} },
+        { reportingComplete: {} },
+        { quarterlyReview: {} },
+        { annualReview: {} },
+      ];
+
+      for (const attestationType of types) {
+        const { attestationPda } = await createAttestation({ attestationType });
+        const attestation = await program.account.attestation.fetch(attestationPda);
+        expect(attestation.attestationType).to.deep.equal(attestationType);
+      }
+    });
+
+    it("increments attestation_count correctly", async () => {
+      const [statePda] = findStatePda();
+      const stateBefore = await program.account.programState.fetch(statePda);
+      const countBefore = stateBefore.attestationCount.toNumber();
+
+      await createAttestation();
+      await createAttestation();
+      await createAttestation();
+
+      const stateAfter = await program.account.programState.fetch(statePda);
+      expect(stateAfter.attestationCount.toNumber()).to.equal(countBefore + 3);
+    });
+
+    it("fails with expires_at in the past", async () => {
+      try {
+        await createAttestation({
+          expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) - 60),
+        });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("AttestationExpired");
+      }
+    });
+
+    it("fails with tax_year 0", async () => {
+      try {
+        await createAttestation({ taxYear: 0 });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("InvalidTaxYear");
+      }
+    });
+
+    it("fails with tax_year 9999", async () => {
+      try {
+        await createAttestation({ taxYear: 9999 });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("InvalidTaxYear");
+      }
+    });
+
+    it("fails with duplicate wallets", async () => {
+      const wallet = Keypair.generate().publicKey;
+      try {
+        await createAttestation({ wallets: [wallet, Keypair.generate().publicKey, wallet] });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("DuplicateWallet");
+      }
+    });
+
+    it("fails with a default pubkey wallet", async () => {
+      try {
+        await createAttestation({ wallets: [Keypair.generate().publicKey, PublicKey.default] });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("InvalidWallet");
+      }
+    });
+
+    it("fails with an all-zero audit_hash", async () => {
+      try {
+        await createAttestation({ auditHash: new Array(32).fill(0) });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("InvalidAuditHash");
+      }
+    });
+
+    it("enforces a configured tax year window", async () => {
+      const [statePda] = findStatePda();
+      const setWindow = (min: number, max: number) =>
+        program.methods
+          .setTaxYearWindow(min, max)
+          .accounts({ state: statePda, authority: authority.publicKey })
+          .rpc();
+
+      await setWindow(2020, 2026);
+      try {
+        await createAttestation({ taxYear: 2019 });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("InvalidTaxYear");
+      }
+      await createAttestation({ taxYear: 2026 });
+
+      try {
+        await setWindow(2026, 2020);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("InvalidTaxYearWindow");
+      }
+
+      await setWindow(2009, 2100);
+    });
+  });
+
+  // ============================================
+  // PDA Derivation
+  // ============================================
+
+  describe("PDA derivation", () => {
+    it("verifies PDA address matches expected derivation", async () => {
+      const issuer = await program.account.issuer.fetch(findIssuerPda(authority.publicKey)[0]);
+      const [expectedPda, expectedBump] = findAttestationPda(
+        authority.publicKey,
+        issuer.nextSequence
+      );
+
+      const { attestationPda } = await createAttestation();
+
+      expect(attestationPda.toBase58()).to.equal(expectedPda.toBase58());
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.bump).to.equal(expectedBump);
+      expect(attestation.sequence.toString()).to.equal(issuer.nextSequence.toString());
+
+      const after = await program.account.issuer.fetch(findIssuerPda(authority.publicKey)[0]);
+      expect(after.nextSequence.toNumber()).to.equal(issuer.nextSequence.toNumber() + 1);
+    });
+
+    it("namespaces attestations by issuer", async () => {
+      const otherIssuer = Keypair.generate();
+      await airdrop(otherIssuer.publicKey);
+      await program.methods
+        .addIssuer(otherIssuer.publicKey, ALL_JURISDICTIONS, ALL_TYPES)
+        .accounts({
+          state: findStatePda()[0],
+          issuer: findIssuerPda(otherIssuer.publicKey)[0],
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      // A new issuer starts at the program's attestation count, past any sequence
+      // an earlier registration under the same key could have used
+      const state = await program.account.programState.fetch(findStatePda()[0]);
+      const issuer = await program.account.issuer.fetch(findIssuerPda(otherIssuer.publicKey)[0]);
+      expect(issuer.nextSequence.toString()).to.equal(state.attestationCount.toString());
+
+      const auditHash = makeAuditHash();
+      const ours = await createAttestation({ auditHash });
+      const theirs = await createAttestation({
+        auditHash,
+        authorityPubkey: otherIssuer.publicKey,
+        signers: [otherIssuer],
+      });
+
+      expect(theirs.attestationPda.toBase58()).to.equal(
+        findAttestationPda(otherIssuer.publicKey, issuer.nextSequence)[0].toBase58()
+      );
+      expect(theirs.attestationPda.toBase58()).to.not.equal(ours.attestationPda.toBase58());
+    });
+
+    it("verifies state PDA address", async () => {
+      const [expectedStatePda, expectedBump] = findStatePda();
+      const state = await program.account.programState.fetch(expectedStatePda);
+      expect(state.bump).to.equal(expectedBump);
+    });
+  });
+
+  // ============================================
+  // Update Status
+  // ============================================
+
+  describe("update_status", () => {
+    it("updates status from Active to Expired", async () => {
+      const { attestationPda, auditHash } = await createAttestation();
+      const [statePda] = findStatePda();
+
+      await program.methods
+        .updateStatus({ expired: {} })
+        .accounts({
+          state: statePda,
+          attestation: attestationPda,
+          authority: authority.publicKey,
+        })
+        .rpc();
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ expired: {} }));
+      // revoked_at should remain 0 for expired status
+      expect(attestation.revokedAt.toNumber()).to.equal(0);
+    });
+
+    it("updates status from Active to Revoked (sets revoked_at)", async () => {
+      const { attestationPda } = await createAttestation();
+      const [statePda] = findStatePda();
+
+      await program.methods
+        .updateStatus({ revoked: {} })
+        .accounts({
+          state: statePda,
+          attestation: attestationPda,
+          authority: authority.publicKey,
+        })
+        .remainingAccounts(await attestationAccounts(attestationPda))
+        .rpc();
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ revoked: {} }));
+      expect(attestation.revokedAt.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("emits AttestationRevoked when revoking through update_status", async () => {
+      const { attestationPda } = await createAttestation();
+      const [statePda] = findStatePda();
+
+      const signature = await program.methods
+        .updateStatus({ revoked: {} })
+        .accounts({
+          state: statePda,
+          attestation: attestationPda,
+          authority: authority.publicKey,
+        })
+        .remainingAccounts(await attestationAccounts(attestationPda))
+        .rpc({ commitment: "confirmed" });
+
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const parser = new anchor.EventParser(program.programId, program.coder);
+      const events = [...parser.parseLogs(tx.meta.logMessages)];
+      const revoked = events.find((e) => e.name === "attestationRevoked");
+      expect(revoked).to.not.be.undefined;
+      expect(revoked.data.attestation.toBase58()).to.equal(attestationPda.toBase58());
+      expect(JSON.stringify(revoked.data.reason)).to.equal(JSON.stringify({ other: {} }));
+      expect(events.some((e) => e.name === "statusUpdated")).to.be.true;
+    });
+
+    it("fails for invalid transition Active -> Pending", async () => {
+      const { attestationPda } = await createAttestation();
+      const [statePda] = findStatePda();
+
+      try {
+        await program.methods
+          .updateStatus({ pending: {} })
+          .accounts({
+            state: statePda,
+            attestation: attestationPda,
+            authority: authority.publicKey,
+          })
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        const errMsg = err.toString();
+        expect(errMsg).to.contain("InvalidStatusTransition");
+      }
+    });
+
+    it("fails for invalid transition Expired -> Active", async () => {
+      const { attestationPda } = await createAttestation();
+      const [statePda] = findStatePda();
+
+      // First transition to Expired
+      await program.methods
+        .updateStatus({ expired: {} })
+        .accounts({
+          state: statePda,
+          attestation: attestationPda,
+          authority: authority.publicKey,
+        })
+        .rpc();
+
+      // Try invalid transition Expired -> Active
+      try {
+        await program.methods
+          .updateStatus({ active: {} })
+          .accounts({
+            state: statePda,
+            attestation: attestationPda,
+            authority: authority.publicKey,
+          })
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        const errMsg = err.toString();
+        expect(errMsg).to.contain("InvalidStatusTransition");
+      }
+    });
+
+    it("fails to update status on revoked attestation", async () => {
+      const { attestationPda } = await createAttestation();
+      const [statePda] = findStatePda();
+
+      // Revoke it first
+      await program.methods
+        .revokeAttestation({ issuerError: {} }, null)
+        .accounts({
+          state: statePda,
+          attestation: attestationPda,
+          authority: authority.publicKey,
+        })
+        .remainingAccounts(await attestationAccounts(attestationPda))
+        .rpc();
+
+      // Try to update status on revoked attestation
+      try {
+        await program.methods
+          .updateStatus({ expired: {} })
+          .accounts({
+            state: statePda,
+            attestation: attestationPda,
+            authority: authority.publicKey,
+          })
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        const errMsg = err.toString();
+        expect(errMsg).to.contain("InvalidStatusTransition");
+      }
+    });
+
+    it("fails with wrong authority", async () => {
+      const fakeAuthority = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        fakeAuthority.publicKey,
+        2 * anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+
+      const { attestationPda } = await createAttestation();
+      const [statePda] = findStatePda();
+
+      try {
+        await program.methods
+          .updateStatus({ expired: {} })
+          .accounts({
+            state: statePda,
+            attestation: attestationPda,
+            authority: fakeAuthority.publicKey,
+          })
+          .signers([fakeAuthority])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        const errMsg = err.toString();
+        expect(errMsg).to.contain("Unauthorized");
+      }
+    });
+  });
+
+  // ============================================
+  // Revoke Attestation
+  // ============================================
+
+  describe("revoke_attestation", () => {
+    it("revokes an active attestation", async () => {
+      const { attestationPda } = await createAttestation();
+      const [statePda] = findStatePda();
+
+      await program.methods
+        .revokeAttestation({ issuerError: {} }, null)
+        .accounts({
+          state: statePda,
+          attestation: attestationPda,
+          authority: authority.publicKey,
+        })
+        .remainingAccounts(await attestationAccounts(attestationPda))
+        .rpc();
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ revoked: {} }));
+      expect(attestation.revokedAt.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("preserves wallet data after revocation", async () => {
+      const wallets = [
+        Keypair.generate().publicKey,
+        Keypair.generate().publicKey,
+      ];
+      const { attestationPda } = await createAttestation({ wallets });
+      const [statePda] = findStatePda();
+
+      await program.methods
+        .revokeAttestation({ issuerError: {} }, null)
+        .accounts({
+          state: statePda,
+          attestation: attestationPda,
+          authority: authority.publicKey,
+        })
+        .remainingAccounts(await attestationAccounts(attestationPda))
+        .rpc();
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.wallets.length).to.equal(2);
+      expect(attestation.wallets[0].toBase58()).to.equal(wallets[0].toBase58());
+      expect(attestation.wallets[1].toBase58()).to.equal(wallets[1].toBase58());
+    });
+
+    it("records the revocation reason and document hash", async () => {
+      const { attestationPda } = await createAttestation();
+      const [statePda] = findStatePda();
+      const reasonHash = makeAuditHash();
+
+      await program.methods
+        .revokeAttestation({ fraud: {} }, reasonHash)
+        .accounts({
+          state: statePda,
+          attestation: attestationPda,
+          authority: authority.publicKey,
+        })
+        .remainingAccounts(await attestationAccounts(attestationPda))
+        .rpc();
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.revocationReason)).to.equal(
+        JSON.stringify({ fraud: {} })
+      );
+      expect(attestation.revocationHash).to.deep.equal(reasonHash);
+    });
+
+    it("leaves the document hash zeroed when none is given", async () => {
+      const { attestationPda } = await createAttestation();
+      const [statePda] = findStatePda();
+
+      await program.methods
+        .revokeAttestation({ clientRequest: {} }, null)
+        .accounts({
+          state: statePda,
+          attestation: attestationPda,
+          authority: authority.publicKey,
+        })
+        .remainingAccounts(await attestationAccounts(attestationPda))
+        .rpc();
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.revocationReason)).to.equal(
+        JSON.stringify({ clientRequest: {} })
+      );
+      expect(attestation.revocationHash).to.deep.equal(new Array(32).fill(0));
+    });
+
+    it("rejects an all-zero document hash", async () => {
+      const { attestationPda } = await createAttestation();
+      const [statePda] = findStatePda();
+
+      try {
+        await program.methods
+          .revokeAttestation({ regulatoryOrder: {} }, new Array(32).fill(0))
+          .accounts({
+            state: statePda,
+            attestation: attestationPda,
+            authority: authority.publicKey,
+          })
+          .remainingAccounts(await attestationAccounts(attestationPda))
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        const errMsg = err.toString();
+        expect(errMsg).to.contain("InvalidAuditHash");
+      }
+    });
+
+    it("fails to revoke an already revoked attestation", async () => {
+      const { attestationPda } = await createAttestation();
+      const [statePda] = findStatePda();
+
+      // Revoke once
+      await program.methods
+        .revokeAttestation({ issuerError: {} }, null)
+        .accounts({
+          state: statePda,
+          attestation: attestationPda,
+          authority: authority.publicKey,
+        })
+        .remainingAccounts(await attestationAccounts(attestationPda))
+        .rpc();
+
+      // Try to revoke again
+      try {
+        await program.methods
+          .revokeAttestation({ issuerError: {} }, null)
+          .accounts({
+            state: statePda,
+            attestation: attestationPda,
+            authority: authority.publicKey,
+          })
+          .remainingAccounts(await attestationAccounts(attestationPda))
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        const errMsg = err.toString();
+        expect(errMsg).to.contain("AttestationNotActive");
+      }
+    });
+
+    it("fails to revoke an expired attestation", async () => {
+      const { attestationPda } = await createAttestation();
+      const [statePda] = findStatePda();
+
+      // Expire it first
+      await program.methods
+        .updateStatus({ expired: {} })
+        .accounts({
+          state: statePda,
+          attestation: attestationPda,
+          authority: authority.publicKey,
+        })
+        .rpc();
+
+      // Try to revoke
+      try {
+        await program.methods
+          .revokeAttestation({ issuerError: {} }, null)
+          .accounts({
+            state: statePda,
+            attestation: attestationPda,
+            authority: authority.publicKey,
+          })
+          .remainingAccounts(await attestationAccounts(attestationPda))
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        const errMsg = err.toString();
+        expect(errMsg).to.contain("AttestationNotActive");
+      }
+    });
+
+    it("fails with wrong authority", async () => {
+      const fakeAuthority = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        fakeAuthority.publicKey,
+        2 * anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+
+      const { attestationPda } = await createAttestation();
+      const [statePda] = findStatePda();
+
+      try {
+        await program.methods
+          .revokeAttestation({ issuerError: {} }, null)
+          .accounts({
+            state: statePda,
+            attestation: attestationPda,
+            authority: fakeAuthority.publicKey,
+          })
+          .remainingAccounts(await attestationAccounts(attestationPda))
+          .signers([fakeAuthority])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        const errMsg = err.toString();
+        expect(errMsg).to.contain("Unauthorized");
+      }
+    });
+  });
+  // ============================================
+  // Authority Transfer
+  // ============================================
+
+  describe("authority transfer", () => {
+    const newAuthority = Keypair.generate();
+
+    const proposeAuthority = (candidate: PublicKey) =>
+      program.methods
+        .proposeAuthority(candidate)
+        .accounts({ state: findStatePda()[0], authority: authority.publicKey })
+        .rpc();
+
+    it("proposes a new authority without changing the current one", async () => {
+      const [statePda] = findStatePda();
+      await proposeAuthority(newAuthority.publicKey);
+
+      const state = await program.account.programState.fetch(statePda);
+      expect(state.authority.toBase58()).to.equal(authority.publicKey.toBase58());
+      expect(state.pendingAuthority.toBase58()).to.equal(newAuthority.publicKey.toBase58());
+    });
+
+    it("fails to accept from a different signer", async () => {
+      const stranger = Keypair.generate();
+      try {
+        await program.methods
+          .acceptAuthority()
+          .accounts({ state: findStatePda()[0], pendingAuthority: stranger.publicKey })
+          .signers([stranger])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("Unauthorized");
+      }
+    });
+
+    it("cancels a pending transfer", async () => {
+      const [statePda] = findStatePda();
+      await program.methods
+        .cancelAuthorityTransfer()
+        .accounts({ state: statePda, authority: authority.publicKey })
+        .rpc();
+
+      const state = await program.account.programState.fetch(statePda);
+      expect(state.pendingAuthority.toBase58()).to.equal(PublicKey.default.toBase58());
+
+      try {
+        await program.methods
+          .acceptAuthority()
+          .accounts({ state: statePda, pendingAuthority: newAuthority.publicKey })
+          .signers([newAuthority])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("NoPendingAuthority");
+      }
+    });
+
+    it("fails to cancel when nothing is pending", async () => {
+      try {
+        await program.methods
+          .cancelAuthorityTransfer()
+          .accounts({ state: findStatePda()[0], authority: authority.publicKey })
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("NoPendingAuthority");
+      }
+    });
+
+    it("fails to propose the default pubkey", async () => {
+      try {
+        await proposeAuthority(PublicKey.default);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("InvalidAuthority");
+      }
+    });
+
+    it("rotates the authority and back", async () => {
+      const [statePda] = findStatePda();
+      await proposeAuthority(newAuthority.publicKey);
+      await program.methods
+        .acceptAuthority()
+        .accounts({ state: statePda, pendingAuthority: newAuthority.publicKey })
+        .signers([newAuthority])
+        .rpc();
+
+      let state = await program.account.programState.fetch(statePda);
+      expect(state.authority.toBase58()).to.equal(newAuthority.publicKey.toBase58());
+      expect(state.pendingAuthority.toBase58()).to.equal(PublicKey.default.toBase58());
+
+      // The old authority has lost its rights
+      try {
+        await proposeAuthority(authority.publicKey);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("Unauthorized");
+      }
+
+      // Hand it back so the remaining suites keep using the provider wallet
+      await program.methods
+        .proposeAuthority(authority.publicKey)
+        .accounts({ state: statePda, authority: newAuthority.publicKey })
+        .signers([newAuthority])
+        .rpc();
+      await program.methods
+        .acceptAuthority()
+        .accounts({ state: statePda, pendingAuthority: authority.publicKey })
+        .rpc();
+
+      state = await program.account.programState.fetch(statePda);
+      expect(state.authority.toBase58()).to.equal(authority.publicKey.toBase58());
+    });
+  });
+
+  // ============================================
+  // Expiry Crank
+  // ============================================
+
+  describe("expire_attestation", () => {
+    const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+    const cranker = Keypair.generate();
+
+    // Attestation that expires a couple of seconds from now
+    const createShortLived = () =>
+      createAttestation({
+        expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 3),
+      });
+
+    before(async () => {
+      await airdrop(cranker.publicKey);
+    });
+
+    it("fails before expires_at", async () => {
+      const { attestationPda } = await createAttestation();
+
+      try {
+        await program.methods
+          .expireAttestation()
+          .accounts({ attestation: attestationPda })
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("AttestationNotExpired");
+      }
+    });
+
+    it("lets anyone expire an attestation after expires_at", async () => {
+      const { attestationPda } = await createShortLived();
+      await sleep(5000);
+
+      // Sent and paid for by an unrelated wallet
+      const ix = await program.methods
+        .expireAttestation()
+        .accounts({ attestation: attestationPda })
+        .instruction();
+      await anchor.web3.sendAndConfirmTransaction(
+        provider.connection,
+        new anchor.web3.Transaction().add(ix),
+        [cranker]
+      );
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ expired: {} }));
+    });
+
+    it("fails on an attestation that is not active", async () => {
+      const { attestationPda } = await createShortLived();
+      await program.methods
+        .revokeAttestation({ issuerError: {} }, null)
+        .accounts({
+          state: findStatePda()[0],
+          attestation: attestationPda,
+          authority: authority.publicKey,
+        })
+        .remainingAccounts(await attestationAccounts(attestationPda))
+        .rpc();
+      await sleep(5000);
+
+      try {
+        await program.methods
+          .expireAttestation()
+          .accounts({ attestation: attestationPda })
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("AttestationNotActive");
+      }
+    });
+
+    it("expires a batch and skips ineligible attestations", async () => {
+      const due = [await createShortLived(), await createShortLived()];
+      const notDue = await createAttestation();
+      await sleep(5000);
+
+      await program.methods
+        .expireAttestations()
+        .remainingAccounts(
+          [...due, notDue].map(({ attestationPda }) => ({
+            pubkey: attestationPda,
+            isSigner: false,
+            isWritable: true,
+          }))
+        )
+        .rpc();
+
+      for (const { attestationPda } of due) {
+        const attestation = await program.account.attestation.fetch(attestationPda);
+        expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ expired: {} }));
+      }
+      const untouched = await program.account.attestation.fetch(notDue.attestationPda);
+      expect(JSON.stringify(untouched.status)).to.equal(JSON.stringify({ active: {} }));
+    });
+
+    it("fails when a batch account is not an attestation", async () => {
+      try {
+        await program.methods
+          .expireAttestations()
+          .remainingAccounts([
+            { pubkey: findStatePda()[0], isSigner: false, isWritable: true },
+          ])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("AccountDiscriminatorMismatch");
+      }
+    });
+  });
+
+  // ============================================
+  // Supersede Attestation
+  // ============================================
+
+  describe("supersede_attestation", () => {
+    const supersede = async (
+      previous: PublicKey,
+      overrides: {
+        auditHash?: number[];
+        wallets?: PublicKey[];
+        signer?: Keypair;
+        schema?: PublicKey;
+        expiresAt?: anchor.BN;
+      } = {}
+    ) => {
+      const auditHash = overrides.auditHash ?? makeAuditHash();
+      const signerKey = overrides.signer?.publicKey ?? authority.publicKey;
+      const [attestationPda] = await nextAttestationPda(signerKey);
+
+      const wallets = overrides.wallets ?? [Keypair.generate().publicKey];
+      const prior = await program.account.attestation.fetch(previous);
+      const dropped = prior.wallets.filter((w) => !wallets.some((k) => k.equals(w)));
+
+      const builder = program.methods
+        .supersedeAttestation(
+          auditHash,
+          overrides.expiresAt ?? new anchor.BN(Math.floor(Date.now() / 1000) + 86400 * 365),
+          wallets
+        )
+        .accounts({
+          state: findStatePda()[0],
+          previous,
+          attestation: attestationPda,
+          issuer: findIssuerPda(signerKey)[0],
+          jurisdictionConfig: findJurisdictionPda(prior.jurisdiction)[0],
+          schema: overrides.schema ?? baseSchemaPda(prior.attestationType),
+          authority: signerKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([
+          ...walletAccounts(attestationPda, wallets, prior),
+          ...slotPdas(dropped, prior).map((pubkey) => ({
+            pubkey,
+            isSigner: false,
+            isWritable: true,
+          })),
+        ]);
+      if (overrides.signer) builder.signers([overrides.signer]);
+      return builder.rpc().then(() => ({ attestationPda, auditHash }));
+    };
+
+    it("links the amendment and flips the original to Superseded", async () => {
+      const original = await createAttestation({
+        jurisdiction: iso("GB"),
+        attestationType: { annualReview: {} },
+        taxYear: 2024,
+      });
+      const wallets = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+
+      const { attestationPda } = await supersede(original.attestationPda, { wallets });
+
+      const previous = await program.account.attestation.fetch(original.attestationPda);
+      expect(JSON.stringify(previous.status)).to.equal(JSON.stringify({ superseded: {} }));
+      expect(previous.supersededBy.toBase58()).to.equal(attestationPda.toBase58());
+
+      const amended = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(amended.status)).to.equal(JSON.stringify({ active: {} }));
+      expect(amended.supersedes.toBase58()).to.equal(original.attestationPda.toBase58());
+      expect(amended.supersededBy.toBase58()).to.equal(PublicKey.default.toBase58());
+      expect(amended.jurisdiction).to.deep.equal(iso("GB"));
+      expect(amended.attestationType).to.deep.equal({ annualReview: {} });
+      expect(amended.taxYear).to.equal(2024);
+      expect(amended.wallets.map((w) => w.toBase58())).to.deep.equal(
+        wallets.map((w) => w.toBase58())
+      );
+    });
+
+    it("issues the amendment under a current schema and its default validity", async () => {
+      const annualFiling = 102;
+      const validity = 180 * 86400;
+      await addSchema(annualFiling, { taxCompliance: {} });
+      await addSchema(annualFiling, { taxCompliance: {} }, {
+        version: 2,
+        defaultValidity: validity,
+      });
+      const original = await createAttestation({ schema: findSchemaPda(annualFiling)[0] });
+
+      const { attestationPda } = await supersede(original.attestationPda, {
+        schema: findSchemaPda(annualFiling, 2)[0],
+        expiresAt: new anchor.BN(0),
+      });
+
+      const amended = await program.account.attestation.fetch(attestationPda);
+      expect(amended.schemaId).to.equal(annualFiling);
+      expect(amended.schemaVersion).to.equal(2);
+      expect(amended.expiresAt.toNumber()).to.equal(amended.issuedAt.toNumber() + validity);
+    });
+
+    it("fails under a deprecated schema", async () => {
+      const retired = 103;
+      await addSchema(retired, { taxCompliance: {} });
+      const original = await createAttestation({ schema: findSchemaPda(retired)[0] });
+      await program.methods
+        .deprecateSchema()
+        .accounts({
+          state: findStatePda()[0],
+          schema: findSchemaPda(retired)[0],
+          authority: authority.publicKey,
+        })
+        .rpc();
+
+      try {
+        await supersede(original.attestationPda, { schema: findSchemaPda(retired)[0] });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("SchemaDeprecated");
+      }
+    });
+
+    it("walks a chain of amendments", async () => {
+      const first = await createAttestation();
+      const second = await supersede(first.attestationPda);
+      const third = await supersede(second.attestationPda);
+
+      const chain: string[] = [];
+      let cursor: PublicKey = third.attestationPda;
+      while (!cursor.equals(PublicKey.default)) {
+        chain.push(cursor.toBase58());
+        cursor = (await program.account.attestation.fetch(cursor)).supersedes;
+      }
+      expect(chain).to.deep.equal([
+        third.attestationPda.toBase58(),
+        second.attestationPda.toBase58(),
+        first.attestationPda.toBase58(),
+      ]);
+    });
+
+    it("fails to supersede an attestation twice", async () => {
+      const original = await createAttestation();
+      await supersede(original.attestationPda);
+
+      try {
+        await supersede(original.attestationPda);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("AttestationNotActive");
+      }
+    });
+
+    it("fails to revoke a superseded attestation", async () => {
+      const original = await createAttestation();
+      await supersede(original.attestationPda);
+
+      try {
+        await program.methods
+          .revokeAttestation({ issuerError: {} }, null)
+          .accounts({
+            state: findStatePda()[0],
+            attestation: original.attestationPda,
+            authority: authority.publicKey,
+          })
+          .remainingAccounts(await attestationAccounts(original.attestationPda))
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("AttestationNotActive");
+      }
+    });
+
+    it("fails when a different issuer amends", async () => {
+      const otherIssuer = Keypair.generate();
+      await airdrop(otherIssuer.publicKey);
+      await program.methods
+        .addIssuer(otherIssuer.publicKey, ALL_JURISDICTIONS, ALL_TYPES)
+        .accounts({
+          state: findStatePda()[0],
+          issuer: findIssuerPda(otherIssuer.publicKey)[0],
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      const original = await createAttestation();
+
+      try {
+        await supersede(original.attestationPda, { signer: otherIssuer });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("Unauthorized");
+      }
+    });
+  });
+
+  // ============================================
+  // Renew Attestation
+  // ============================================
+
+  describe("renew_attestation", () => {
+    const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+    const inOneYear = () => Math.floor(Date.now() / 1000) + 86400 * 365;
+
+    const renew = async (
+      attestation: PublicKey,
+      newExpiresAt: number,
+      reviewHash: number[] | null = null
+    ) => {
+      const account = await program.account.attestation.fetch(attestation);
+      return program.methods
+        .renewAttestation(new anchor.BN(newExpiresAt), reviewHash)
+        .accounts({
+          attestation,
+          issuer: findIssuerPda(authority.publicKey)[0],
+          authority: authority.publicKey,
+        })
+        .remainingAccounts(
+          slotPdas(account.wallets, account).map((pubkey) => ({
+            pubkey,
+            isSigner: false,
+            isWritable: true,
+          }))
+        )
+        .rpc();
+    };
+
+    it("extends an active attestation and records the renewal", async () => {
+      const { attestationPda, expiresAt } = await createAttestation();
+      const newExpiresAt = expiresAt.toNumber() + 86400 * 30;
+      const reviewHash = makeAuditHash();
+
+      await renew(attestationPda, newExpiresAt, reviewHash);
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.expiresAt.toNumber()).to.equal(newExpiresAt);
+      expect(attestation.renewalCount).to.equal(1);
+      expect(attestation.renewedAt.toNumber()).to.be.greaterThan(0);
+      expect(attestation.reviewHash).to.deep.equal(reviewHash);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ active: {} }));
+
+      const [slotPda] = findSlotPda(attestation.wallets[0], attestation);
+      const slot = await program.account.attestationSlot.fetch(slotPda);
+      expect(slot.expiresAt.toNumber()).to.equal(newExpiresAt);
+    });
+
+    it("keeps the previous review hash when none is given", async () => {
+      const { attestationPda, expiresAt } = await createAttestation();
+      const reviewHash = makeAuditHash();
+      await renew(attestationPda, expiresAt.toNumber() + 100, reviewHash);
+      await renew(attestationPda, expiresAt.toNumber() + 200);
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.renewalCount).to.equal(2);
+      expect(attestation.reviewHash).to.deep.equal(reviewHash);
+    });
+
+    it("brings an expired attestation back to Active", async () => {
+      const { attestationPda } = await createAttestation({
+        attestationType: { annualReview: {} },
+        expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 3),
+      });
+      await sleep(5000);
+      await program.methods.expireAttestation().accounts({ attestation: attestationPda }).rpc();
+
+      await renew(attestationPda, inOneYear(), makeAuditHash());
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ active: {} }));
+    });
+
+    it("fails once another attestation has claimed an expired attestation's slot", async () => {
+      const wallet = Keypair.generate().publicKey;
+      const scope = { attestationType: { annualReview: {} }, wallets: [wallet] };
+      const { attestationPda } = await createAttestation({
+        ...scope,
+        expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 3),
+      });
+      await sleep(5000);
+      await program.methods.expireAttestation().accounts({ attestation: attestationPda }).rpc();
+      await createAttestation(scope);
+
+      try {
+        await renew(attestationPda, inOneYear());
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("SlotOccupied");
+      }
+    });
+
+    it("fails for a non-renewable attestation type", async () => {
+      const { attestationPda } = await createAttestation({
+        attestationType: { auditComplete: {} },
+      });
+
+      try {
+        await renew(attestationPda, inOneYear() + 86400);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("AttestationNotRenewable");
+      }
+    });
+
+    it("fails when the new expiry does not extend the current one", async () => {
+      const { attestationPda, expiresAt } = await createAttestation();
+
+      try {
+        await renew(attestationPda, expiresAt.toNumber() - 60);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("InvalidRenewalExpiry");
+      }
+    });
+
+    it("fails on a revoked attestation", async () => {
+      const { attestationPda } = await createAttestation();
+      await program.methods
+        .revokeAttestation({ issuerError: {} }, null)
+        .accounts({
+          state: findStatePda()[0],
+          attestation: attestationPda,
+          authority: authority.publicKey,
+        })
+        .remainingAccounts(await attestationAccounts(attestationPda))
+        .rpc();
+
+      try {
+        await renew(attestationPda, inOneYear() + 86400);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("InvalidStatusTransition");
+      }
+    });
+  });
+
+  // ============================================
+  // Suspend / Reinstate
+  // ============================================
+
+  describe("suspend_attestation", () => {
+    const adminAccounts = (attestation: PublicKey) => ({
+      state: findStatePda()[0],
+      attestation,
+      authority: authority.publicKey,
+    });
+
+    it("suspends with a reason and reinstates", async () => {
+      const { attestationPda } = await createAttestation();
+
+      await program.methods
+        .suspendAttestation({ discrepancyUnderReview: {} })
+        .accounts(adminAccounts(attestationPda))
+        .rpc();
+
+      let attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ suspended: {} }));
+      expect(attestation.suspensionReason).to.deep.equal({ discrepancyUnderReview: {} });
+      expect(attestation.suspendedAt.toNumber()).to.be.greaterThan(0);
+      expect(attestation.reinstatedAt.toNumber()).to.equal(0);
+
+      await program.methods
+        .reinstateAttestation()
+        .accounts(adminAccounts(attestationPda))
+        .rpc();
+
+      attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ active: {} }));
+      expect(attestation.reinstatedAt.toNumber()).to.be.greaterThan(0);
+      // The last suspension stays on record
+      expect(attestation.suspensionReason).to.deep.equal({ discrepancyUnderReview: {} });
+    });
+
+    it("reinstates to Expired once expires_at has passed", async () => {
+      const { attestationPda } = await createAttestation({
+        expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 3),
+      });
+      await program.methods
+        .suspendAttestation({ pendingDocumentation: {} })
+        .accounts(adminAccounts(attestationPda))
+        .rpc();
+      await new Promise((resolve) => setTimeout(resolve, 5000));
+
+      await program.methods
+        .reinstateAttestation()
+        .accounts(adminAccounts(attestationPda))
+        .rpc();
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ expired: {} }));
+      expect(attestation.reinstatedAt.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("revokes a suspended attestation", async () => {
+      const { attestationPda } = await createAttestation();
+      await program.methods
+        .suspendAttestation({ regulatoryInquiry: {} })
+        .accounts(adminAccounts(attestationPda))
+        .rpc();
+
+      await program.methods
+        .revokeAttestation({ issuerError: {} }, null)
+        .accounts(adminAccounts(attestationPda))
+        .remainingAccounts(await attestationAccounts(attestationPda))
+        .rpc();
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ revoked: {} }));
+    });
+
+    it("fails to suspend twice", async () => {
+      const { attestationPda } = await createAttestation();
+      await program.methods
+        .suspendAttestation({ other: {} })
+        .accounts(adminAccounts(attestationPda))
+        .rpc();
+
+      try {
+        await program.methods
+          .suspendAttestation({ other: {} })
+          .accounts(adminAccounts(attestationPda))
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("AttestationNotActive");
+      }
+    });
+
+    it("fails to reinstate an active attestation", async () => {
+      const { attestationPda } = await createAttestation();
+
+      try {
+        await program.methods
+          .reinstateAttestation()
+          .accounts(adminAccounts(attestationPda))
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("AttestationNotSuspended");
+      }
+    });
+
+    it("fails with wrong authority", async () => {
+      const fakeAuthority = Keypair.generate();
+      const { attestationPda } = await createAttestation();
+
+      try {
+        await program.methods
+          .suspendAttestation({ pendingDocumentation: {} })
+          .accounts({
+            state: findStatePda()[0],
+            attestation: attestationPda,
+            authority: fakeAuthority.publicKey,
+          })
+          .signers([fakeAuthority])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("Unauthorized");
+      }
+    });
+  });
+
+  // ============================================
+  // Close Attestation
+  // ============================================
+
+  describe("close_attestation", () => {
+    const [statePda] = findStatePda();
+
+    const setRetention = (seconds: number) =>
+      program.methods
+        .setRetentionPeriod(new anchor.BN(seconds))
+        .accounts({ state: statePda, authority: authority.publicKey })
+        .rpc();
+
+    const revoke = async (attestationPda: PublicKey) =>
+      program.methods
+        .revokeAttestation({ issuerError: {} }, null)
+        .accounts({
+          state: statePda,
+          attestation: attestationPda,
+          authority: authority.publicKey,
+        })
+        .remainingAccounts(await attestationAccounts(attestationPda))
+        .rpc();
+
+    // Open removal requests of contested wallets, each followed by its wallet
+    const removalAccounts = async (attestationPda: PublicKey) => {
+      const account = await program.account.attestation.fetch(attestationPda);
+      return account.wallets
+        .filter((_, i) => account.contestedBy & (1 << i))
+        .flatMap((wallet) => [
+          { pubkey: findRemovalPda(attestationPda, wallet)[0], isSigner: false, isWritable: true },
+          { pubkey: wallet, isSigner: false, isWritable: true },
+        ]);
+    };
+
+    const close = async (attestationPda: PublicKey) =>
+      program.methods
+        .closeAttestation()
+        .accounts({
+          state: statePda,
+          attestation: attestationPda,
+          tombstone: findTombstonePda(attestationPda)[0],
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([
+          ...(await attestationAccounts(attestationPda)),
+          ...(await removalAccounts(attestationPda)),
+        ])
+        .rpc();
+
+    after(async () => {
+      await setRetention(7 * 365 * 24 * 60 * 60);
+    });
+
+    it("rejects closing an active attestation", async () => {
+      await setRetention(0);
+      const { attestationPda } = await createAttestation();
+
+      try {
+        await close(attestationPda);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("AttestationNotClosable");
+      }
+    });
+
+    it("rejects closing within the retention period", async () => {
+      await setRetention(86400);
+      const { attestationPda } = await createAttestation();
+      await revoke(attestationPda);
+
+      try {
+        await close(attestationPda);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("RetentionPeriodActive");
+      }
+    });
+
+    it("closes a revoked attestation and leaves a tombstone", async () => {
+      await setRetention(0);
+      const { attestationPda, auditHash } = await createAttestation();
+      await revoke(attestationPda);
+
+      const rent = await provider.connection.getBalance(attestationPda);
+      expect(rent).to.be.greaterThan(0);
+
+      await close(attestationPda);
+
+      const closed = await provider.connection.getAccountInfo(attestationPda);
+      expect(closed).to.be.null;
+
+      const tombstone = await program.account.tombstone.fetch(
+        findTombstonePda(attestationPda)[0]
+      );
+      expect(tombstone.auditHash).to.deep.equal(auditHash);
+      expect(JSON.stringify(tombstone.finalStatus)).to.equal(
+        JSON.stringify({ revoked: {} })
+      );
+      expect(tombstone.closedAt.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("closes open removal requests and refunds the wallets", async () => {
+      await setRetention(0);
+      const owner = Keypair.generate();
+      await airdrop(owner.publicKey);
+      const { attestationPda } = await createAttestation({
+        wallets: [Keypair.generate().publicKey, owner.publicKey],
+      });
+      const [requestPda] = findRemovalPda(attestationPda, owner.publicKey);
+      await program.methods
+        .requestRemoval(makeAuditHash())
+        .accounts({
+          attestation: attestationPda,
+          removalRequest: requestPda,
+          wallet: owner.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+      await revoke(attestationPda);
+
+      const rent = await provider.connection.getBalance(requestPda);
+      const before = await provider.connection.getBalance(owner.publicKey);
+      await close(attestationPda);
+
+      expect(await provider.connection.getAccountInfo(requestPda)).to.be.null;
+      expect(await provider.connection.getBalance(owner.publicKey)).to.equal(before + rent);
+    });
+
+    it("never recreates a closed attestation's address", async () => {
+      await setRetention(0);
+      const { attestationPda, auditHash } = await createAttestation();
+      await revoke(attestationPda);
+      await close(attestationPda);
+
+      const reissued = await createAttestation({ auditHash });
+      expect(reissued.attestationPda.toBase58()).to.not.equal(attestationPda.toBase58());
+      await program.account.tombstone.fetch(findTombstonePda(attestationPda)[0]);
+    });
+
+    it("rejects a negative retention period", async () => {
+      try {
+        await setRetention(-1);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("InvalidRetentionPeriod");
+      }
+    });
+  });
+
+  // Accounts in the original layout cannot be created any more; the Rust unit
+  // tests cover reading them. Here, the legacy path must leave current ones alone.
+  describe("legacy attestations", () => {
+    const [statePda] = findStatePda();
+
+    it("refuses to revoke a current attestation through the legacy path", async () => {
+      const { attestationPda } = await createAttestation();
+
+      try {
+        await program.methods
+          .revokeLegacyAttestation({ superseded: {} })
+          .accounts({
+            state: statePda,
+            attestation: attestationPda,
+            authority: authority.publicKey,
+          })
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("NotLegacyAttestation");
+      }
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ active: {} }));
+    });
+
+    it("refuses to expire a current attestation through the legacy path", async () => {
+      const { attestationPda } = await createAttestation();
+
+      try {
+        await program.methods
+          .expireLegacyAttestation()
+          .accounts({ attestation: attestationPda })
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("NotLegacyAttestation");
+      }
+    });
+  });
+
+  // ============================================
+  // Wallet Consent
+  // ============================================
+
+  describe("acknowledge_attestation", () => {
+    const acknowledge = (attestationPda: PublicKey, wallet: Keypair) =>
+      program.methods
+        .acknowledgeAttestation()
+        .accounts({ attestation: attestationPda, wallet: wallet.publicKey })
+        .signers([wallet])
+        .rpc();
+
+    it("creates a consent-gated attestation as Pending", async () => {
+      const { attestationPda } = await createAttestation({ requireConsent: true });
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ pending: {} }));
+      expect(attestation.consentRequired).to.equal(true);
+      expect(attestation.acknowledged).to.equal(0);
+    });
+
+    it("activates once every listed wallet has acknowledged", async () => {
+      const owners = [Keypair.generate(), Keypair.generate()];
+      const { attestationPda } = await createAttestation({
+        wallets: owners.map((o) => o.publicKey),
+        requireConsent: true,
+      });
+
+      await acknowledge(attestationPda, owners[1]);
+      let attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.acknowledged).to.equal(0b10);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ pending: {} }));
+
+      await acknowledge(attestationPda, owners[0]);
+      attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.acknowledged).to.equal(0b11);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ active: {} }));
+    });
+
+    it("rejects a wallet that is not listed", async () => {
+      const { attestationPda } = await createAttestation({ requireConsent: true });
+
+      try {
+        await acknowledge(attestationPda, Keypair.generate());
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("WalletNotListed");
+      }
+    });
+
+    it("rejects a second acknowledgement from the same wallet", async () => {
+      const owners = [Keypair.generate(), Keypair.generate()];
+      const { attestationPda } = await createAttestation({
+        wallets: owners.map((o) => o.publicKey),
+        requireConsent: true,
+      });
+      await acknowledge(attestationPda, owners[0]);
+
+      try {
+        await acknowledge(attestationPda, owners[0]);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("AlreadyAcknowledged");
+      }
+    });
+
+    it("prevents the authority activating without full consent", async () => {
+      const { attestationPda } = await createAttestation({ requireConsent: true });
+
+      try {
+        await program.methods
+          .updateStatus({ active: {} })
+          .accounts({
+            state: findStatePda()[0],
+            attestation: attestationPda,
+            authority: authority.publicKey,
+          })
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("ConsentIncomplete");
+      }
+    });
+
+    // prefix || wallet || attestation || audit_hash || attestation_type (u8)
+    // || jurisdiction || tax_year (u16 LE) || end_tax_year (u16 LE)
+    const consentMessage = (
+      wallet: PublicKey,
+      attestation: PublicKey,
+      auditHash: number[],
+      attestationType: number,
+      jurisdiction: number[],
+      taxYear: number,
+      endTaxYear: number
+    ) => {
+      const years = Buffer.alloc(4);
+      years.writeUInt16LE(taxYear, 0);
+      years.writeUInt16LE(endTaxYear, 2);
+      return Buffer.concat([
+        Buffer.from("auditswarm:consent:v1"),
+        wallet.toBuffer(),
+        attestation.toBuffer(),
+        Buffer.from(auditHash),
+        Buffer.from([attestationType]),
+        Buffer.from(jurisdiction),
+        years,
+      ]);
+    };
+
+    // Consent to a TaxCompliance attestation for US 2025 at `attestation`
+    const signConsent = (
+      owner: Keypair,
+      attestation: PublicKey,
+      auditHash: number[],
+      { taxYear = 2025, endTaxYear = taxYear, attestationType = 0 } = {}
+    ) =>
+      Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: owner.secretKey,
+        message: consentMessage(
+          owner.publicKey,
+          attestation,
+          auditHash,
+          attestationType,
+          iso("US"),
+          taxYear,
+          endTaxYear
+        ),
+      });
+
+    it("activates at creation with signed consent from every wallet", async () => {
+      const owners = [Keypair.generate(), Keypair.generate()];
+      const auditHash = makeAuditHash();
+      const [nextPda] = await nextAttestationPda();
+      const { attestationPda } = await createAttestation({
+        auditHash,
+        wallets: owners.map((o) => o.publicKey),
+        requireConsent: true,
+        preInstructions: owners.map((o) => signConsent(o, nextPda, auditHash)),
+      });
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.acknowledged).to.equal(0b11);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ active: {} }));
+    });
+
+    it("ignores consent signed for a different tax year", async () => {
+      const owners = [Keypair.generate(), Keypair.generate()];
+      const auditHash = makeAuditHash();
+      const [nextPda] = await nextAttestationPda();
+      const { attestationPda } = await createAttestation({
+        auditHash,
+        wallets: owners.map((o) => o.publicKey),
+        requireConsent: true,
+        preInstructions: [
+          signConsent(owners[0], nextPda, auditHash),
+          signConsent(owners[1], nextPda, auditHash, { taxYear: 2024 }),
+        ],
+      });
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.acknowledged).to.equal(0b01);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ pending: {} }));
+
+      await acknowledge(attestationPda, owners[1]);
+      const updated = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(updated.status)).to.equal(JSON.stringify({ active: {} }));
+    });
+
+    it("only accepts consent signed for the whole tax year range", async () => {
+      const owners = [Keypair.generate(), Keypair.generate()];
+      const auditHash = makeAuditHash();
+      const [nextPda] = await nextAttestationPda();
+      const { attestationPda } = await createAttestation({
+        auditHash,
+        taxYear: 2023,
+        endTaxYear: 2025,
+        wallets: owners.map((o) => o.publicKey),
+        requireConsent: true,
+        preInstructions: [
+          signConsent(owners[0], nextPda, auditHash, { taxYear: 2023, endTaxYear: 2025 }),
+          // Consent to the first year only does not cover the range
+          signConsent(owners[1], nextPda, auditHash, { taxYear: 2023 }),
+        ],
+      });
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.acknowledged).to.equal(0b01);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ pending: {} }));
+    });
+
+    it("ignores consent signed for a different attestation type", async () => {
+      const owner = Keypair.generate();
+      const auditHash = makeAuditHash();
+      const [nextPda] = await nextAttestationPda();
+      const { attestationPda } = await createAttestation({
+        auditHash,
+        wallets: [owner.publicKey],
+        requireConsent: true,
+        // Signed for AuditComplete, used for TaxCompliance
+        preInstructions: [signConsent(owner, nextPda, auditHash, { attestationType: 1 })],
+      });
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.acknowledged).to.equal(0);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ pending: {} }));
+    });
+
+    it("ignores a consent replayed for another attestation", async () => {
+      const owner = Keypair.generate();
+      const auditHash = makeAuditHash();
+      const [nextPda] = await nextAttestationPda();
+      const consent = signConsent(owner, nextPda, auditHash);
+      const first = await createAttestation({
+        auditHash,
+        wallets: [owner.publicKey],
+        requireConsent: true,
+        preInstructions: [consent],
+      });
+      expect((await program.account.attestation.fetch(first.attestationPda)).acknowledged).to.equal(1);
+
+      // Free the wallet's slot, then reuse the signed bytes for identical terms
+      // under the issuer's next sequence
+      await program.methods
+        .revokeAttestation({ issuerError: {} }, null)
+        .accounts({
+          state: findStatePda()[0],
+          attestation: first.attestationPda,
+          authority: authority.publicKey,
+        })
+        .remainingAccounts(await attestationAccounts(first.attestationPda))
+        .rpc();
+      const second = await createAttestation({
+        auditHash,
+        wallets: [owner.publicKey],
+        requireConsent: true,
+        preInstructions: [consent],
+      });
+
+      const attestation = await program.account.attestation.fetch(second.attestationPda);
+      expect(attestation.acknowledged).to.equal(0);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ pending: {} }));
+    });
+  });
+
+  // ============================================
+  // Removal Requests
+  // ============================================
+
+  describe("request_removal", () => {
+    const requestRemoval = (attestationPda: PublicKey, wallet: Keypair) =>
+      program.methods
+        .requestRemoval(makeAuditHash())
+        .accounts({
+          attestation: attestationPda,
+          removalRequest: findRemovalPda(attestationPda, wallet.publicKey)[0],
+          wallet: wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([wallet])
+        .rpc();
+
+    const resolveRemoval = async (
+      attestationPda: PublicKey,
+      wallet: PublicKey,
+      resolution: any,
+      signer?: Keypair
+    ) => {
+      const builder = program.methods
+        .resolveRemoval(resolution, makeAuditHash())
+        .accounts({
+          attestation: attestationPda,
+          removalRequest: findRemovalPda(attestationPda, wallet)[0],
+          wallet,
+          authority: signer?.publicKey ?? authority.publicKey,
+        })
+        .remainingAccounts(await attestationAccounts(attestationPda));
+      if (signer) builder.signers([signer]);
+      return builder.rpc();
+    };
+
+    // Attestation listing a freshly funded wallet
+    const createDisputed = async () => {
+      const owner = Keypair.generate();
+      await airdrop(owner.publicKey);
+      const created = await createAttestation({
+        wallets: [Keypair.generate().publicKey, owner.publicKey],
+      });
+      await requestRemoval(created.attestationPda, owner);
+      return { ...created, owner };
+    };
+
+    it("flags the attestation as contested", async () => {
+      const { attestationPda, owner } = await createDisputed();
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.contestedBy).to.equal(0b10);
+
+      const request = await program.account.removalRequest.fetch(
+        findRemovalPda(attestationPda, owner.publicKey)[0]
+      );
+      expect(request.wallet.toBase58()).to.equal(owner.publicKey.toBase58());
+      expect(request.requestedAt.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("rejects a wallet that is not listed", async () => {
+      const stranger = Keypair.generate();
+      await airdrop(stranger.publicKey);
+      const { attestationPda } = await createAttestation();
+
+      try {
+        await requestRemoval(attestationPda, stranger);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("WalletNotListed");
+      }
+    });
+
+    it("lets the issuer reject the request, closing it", async () => {
+      const { attestationPda, owner } = await createDisputed();
+      const [requestPda] = findRemovalPda(attestationPda, owner.publicKey);
+      const rent = await provider.connection.getBalance(requestPda);
+      const before = await provider.connection.getBalance(owner.publicKey);
+
+      await resolveRemoval(attestationPda, owner.publicKey, { reject: {} });
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.contestedBy).to.equal(0);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ active: {} }));
+
+      expect(await provider.connection.getAccountInfo(requestPda)).to.be.null;
+      expect(await provider.connection.getBalance(owner.publicKey)).to.equal(before + rent);
+    });
+
+    it("lets a wallet dispute again after a rejection", async () => {
+      const { attestationPda, owner } = await createDisputed();
+      await resolveRemoval(attestationPda, owner.publicKey, { reject: {} });
+
+      await requestRemoval(attestationPda, owner);
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.contestedBy).to.equal(0b10);
+    });
+
+    it("lets the issuer revoke the attestation", async () => {
+      const { attestationPda, owner } = await createDisputed();
+
+      await resolveRemoval(attestationPda, owner.publicKey, { revoke: {} });
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ revoked: {} }));
+      expect(JSON.stringify(attestation.revocationReason)).to.equal(
+        JSON.stringify({ clientRequest: {} })
+      );
+    });
+
+    it("requires an amendment before resolving as amended", async () => {
+      const { attestationPda, owner } = await createDisputed();
+
+      try {
+        await resolveRemoval(attestationPda, owner.publicKey, { amend: {} });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("AttestationNotSuperseded");
+      }
+    });
+
+    it("rejects resolution by anyone but the issuer", async () => {
+      const { attestationPda, owner } = await createDisputed();
+
+      try {
+        await resolveRemoval(attestationPda, owner.publicKey, { reject: {} }, owner);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("Unauthorized");
+      }
+    });
+
+    it("cannot resolve the same request twice", async () => {
+      const { attestationPda, owner } = await createDisputed();
+      await resolveRemoval(attestationPda, owner.publicKey, { reject: {} });
+
+      try {
+        await resolveRemoval(attestationPda, owner.publicKey, { reject: {} });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("AccountNotInitialized");
+      }
+    });
+  });
+
+  // ============================================
+  // Pending Attestations
+  // ============================================
+
+  describe("activate_attestation", () => {
+    const activate = (attestationPda: PublicKey) =>
+      program.methods
+        .activateAttestation()
+        .accounts({
+          attestation: attestationPda,
+          issuer: findIssuerPda(authority.publicKey)[0],
+          authority: authority.publicKey,
+        })
+        .rpc();
+
+    it("creates an attestation in Pending and activates it", async () => {
+      const { attestationPda } = await createAttestation({ initialStatus: { pending: {} } });
+
+      let attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ pending: {} }));
+      expect(attestation.activationPending).to.equal(true);
+
+      await activate(attestationPda);
+
+      attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ active: {} }));
+      expect(attestation.activationPending).to.equal(false);
+    });
+
+    it("rejects activating an attestation that is not held", async () => {
+      const { attestationPda } = await createAttestation();
+
+      try {
+        await activate(attestationPda);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("AttestationNotPending");
+      }
+    });
+
+    it("stays Pending after consent until activated", async () => {
+      const owner = Keypair.generate();
+      const { attestationPda } = await createAttestation({
+        wallets: [owner.publicKey],
+        requireConsent: true,
+        initialStatus: { pending: {} },
+      });
+
+      // Consent alone does not release an attestation held for review
+      await program.methods
+        .acknowledgeAttestation()
+        .accounts({ attestation: attestationPda, wallet: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      let attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ pending: {} }));
+
+      await activate(attestationPda);
+      attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ active: {} }));
+    });
+
+    it("revokes a pending attestation", async () => {
+      const { attestationPda } = await createAttestation({ initialStatus: { pending: {} } });
+
+      await program.methods
+        .revokeAttestation({ issuerError: {} }, null)
+        .accounts({
+          state: findStatePda()[0],
+          attestation: attestationPda,
+          authority: authority.publicKey,
+        })
+        .remainingAccounts(await attestationAccounts(attestationPda))
+        .rpc();
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ revoked: {} }));
+    });
+
+    it("rejects any other initial status", async () => {
+      try {
+        await createAttestation({ initialStatus: { expired: {} } });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("InvalidInitialStatus");
+      }
+    });
+  });
+
+  // ============================================
+  // Merkle Wallet Sets
+  // ============================================
+
+  describe("create_merkle_attestation", () => {
+    const sha256 = (...parts: Buffer[]) =>
+      createHash("sha256").update(Buffer.concat(parts)).digest();
+
+    // Mirrors programs/attestation/src/merkle.rs
+    const leafHash = (wallet: PublicKey) => sha256(Buffer.from([0]), wallet.toBuffer());
+    const nodeHash = (a: Buffer, b: Buffer) =>
+      Buffer.compare(a, b) <= 0
+        ? sha256(Buffer.from([1]), a, b)
+        : sha256(Buffer.from([1]), b, a);
+
+    const buildTree = (wallets: PublicKey[]) => {
+      const levels: Buffer[][] = [wallets.map(leafHash)];
+      while (levels[levels.length - 1].length > 1) {
+        const level = levels[levels.length - 1];
+        const next: Buffer[] = [];
+        for (let i = 0; i < level.length; i += 2) {
+          next.push(i + 1 < level.length ? nodeHash(level[i], level[i + 1]) : level[i]);
+        }
+        levels.push(next);
+      }
+      const proof = (index: number) => {
+        const siblings: number[][] = [];
+        for (const level of levels.slice(0, -1)) {
+          if ((index ^ 1) < level.length) siblings.push([...level[index ^ 1]]);
+          index = Math.floor(index / 2);
+        }
+        return siblings;
+      };
+      return { root: [...levels[levels.length - 1][0]], proof };
+    };
+
+    const wallets = Array.from({ length: 37 }, () => Keypair.generate().publicKey);
+    const tree = buildTree(wallets);
+    let attestationPda: PublicKey;
+
+    const verify = (wallet: PublicKey, proof: number[][]) =>
+      program.methods
+        .verifyWalletInclusion(wallet, proof)
+        .accounts({ attestation: attestationPda })
+        .view();
+
+    const createMerkle = async (attestationType: any) => {
+      const [pda] = await nextAttestationPda();
+      await program.methods
+        .createMerkleAttestation(
+          iso("US"),
+          NO_SUBDIVISION,
+          attestationType,
+          2025,
+          2025,
+          { annual: {} },
+          utc(2025, 1),
+          utc(2026, 1),
+          makeAuditHash(),
+          new anchor.BN(Math.floor(Date.now() / 1000) + 86400 * 365),
+          tree.root,
+          wallets.length,
+          { active: {} }
+        )
+        .accounts({
+          state: findStatePda()[0],
+          attestation: pda,
+          issuer: findIssuerPda(authority.publicKey)[0],
+          jurisdictionConfig: findJurisdictionPda(iso("US"))[0],
+          schema: baseSchemaPda(attestationType),
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      return pda;
+    };
+
+    before(async () => {
+      // A fund-level review of a treasury too large to list
+      attestationPda = await createMerkle({ annualReview: {} });
+    });
+
+    it("refuses a Merkle root for TaxCompliance, which needs uniqueness slots", async () => {
+      try {
+        await createMerkle({ taxCompliance: {} });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("MerkleWalletsNotAllowed");
+      }
+    });
+
+    it("stores the root and leaf count instead of wallets", async () => {
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.walletRoot).to.deep.equal(tree.root);
+      expect(attestation.walletCount).to.equal(37);
+      expect(attestation.wallets.length).to.equal(0);
+    });
+
+    it("confirms a covered wallet with its proof", async () => {
+      expect(await verify(wallets[0], tree.proof(0))).to.equal(true);
+      expect(await verify(wallets[36], tree.proof(36))).to.equal(true);
+    });
+
+    it("rejects a wallet that is not covered", async () => {
+      expect(await verify(Keypair.generate().publicKey, tree.proof(0))).to.equal(false);
+      expect(await verify(wallets[1], tree.proof(0))).to.equal(false);
+    });
+
+    it("checks list attestations without a proof", async () => {
+      const listed = await createAttestation();
+      const check = (wallet: PublicKey) =>
+        program.methods
+          .verifyWalletInclusion(wallet, [])
+          .accounts({ attestation: listed.attestationPda })
+          .view();
+
+      expect(await check(listed.wallets[0])).to.equal(true);
+      expect(await check(Keypair.generate().publicKey)).to.equal(false);
+    });
+  });
+
+  // ============================================
+  // Wallet Changes
+  // ============================================
+
+  describe("add_wallets / remove_wallets", () => {
+    const changeWallets = async (
+      method: "addWallets" | "removeWallets",
+      attestationPda: PublicKey,
+      wallets: PublicKey[]
+    ) =>
+      program.methods[method](wallets)
+        .accounts({
+          attestation: attestationPda,
+          issuer: findIssuerPda(authority.publicKey)[0],
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          walletAccounts(
+            attestationPda,
+            wallets,
+            await program.account.attestation.fetch(attestationPda)
+          )
+        )
+        .rpc();
+
+    const accountSize = async (address: PublicKey) =>
+      (await provider.connection.getAccountInfo(address))!.data.length;
+
+    it("grows the account when wallets are added", async () => {
+      const { attestationPda, wallets } = await createAttestation();
+      const before = await accountSize(attestationPda);
+      const added = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+
+      await changeWallets("addWallets", attestationPda, added);
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.numWallets).to.equal(3);
+      expect(attestation.wallets.map((w) => w.toBase58())).to.deep.equal(
+        [...wallets, ...added].map((w) => w.toBase58())
+      );
+      expect(await accountSize(attestationPda)).to.equal(before + 64);
+    });
+
+    it("fails to add wallets past MAX_WALLET_SLOTS", async () => {
+      // Five years for two wallets already takes all ten slots
+      const { attestationPda } = await createAttestation({
+        attestationType: { annualReview: {} },
+        taxYear: 2020,
+        endTaxYear: 2024,
+        wallets: [Keypair.generate().publicKey, Keypair.generate().publicKey],
+      });
+
+      try {
+        await changeWallets("addWallets", attestationPda, [Keypair.generate().publicKey]);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("TooManySlots");
+      }
+    });
+
+    it("shrinks the account and refunds rent when wallets are removed", async () => {
+      const wallets = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+      const { attestationPda } = await createAttestation({ wallets });
+      const sizeBefore = await accountSize(attestationPda);
+      const rentBefore = await provider.connection.getBalance(attestationPda);
+
+      await changeWallets("removeWallets", attestationPda, [wallets[0]]);
+
+      const attestation = await program.account.attestation.fetch(attestationPda);
+      expect(attestation.numWallets).to.equal(1);
+      expect(attestation.wallets[0].toBase58()).to.equal(wallets[1].toBase58());
+      expect(await accountSize(attestationPda)).to.equal(sizeBefore - 32);
+      expect(await provider.connection.getBalance(attestationPda)).to.be.lessThan(rentBefore);
+    });
+
+    it("rejects removing a wallet that is not listed", async () => {
+      const { attestationPda } = await createAttestation();
+
+      try {
+        await changeWallets("removeWallets", attestationPda, [Keypair.generate().publicKey]);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("WalletNotListed");
+      }
+    });
+
+    it("rejects removing every wallet", async () => {
+      const { attestationPda, wallets } = await createAttestation();
+
+      try {
+        await changeWallets("removeWallets", attestationPda, wallets);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("InvalidWalletCount");
+      }
+    });
+
+    it("rejects adding a wallet that is already listed", async () => {
+      const { attestationPda, wallets } = await createAttestation();
+
+      try {
+        await changeWallets("addWallets", attestationPda, wallets);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("DuplicateWallet");
+      }
+    });
+
+    it("returns a consent-gated attestation to Pending until new wallets acknowledge", async () => {
+      const owner = Keypair.generate();
+      const newcomer = Keypair.generate();
+      const { attestationPda } = await createAttestation({
+        wallets: [owner.publicKey],
+        requireConsent: true,
+      });
+      const acknowledge = (wallet: Keypair) =>
+        program.methods
+          .acknowledgeAttestation()
+          .accounts({ attestation: attestationPda, wallet: wallet.publicKey })
+          .signers([wallet])
+          .rpc();
+      await acknowledge(owner);
+
+      await changeWallets("addWallets", attestationPda, [newcomer.publicKey]);
+      let attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ pending: {} }));
+      expect(attestation.acknowledged).to.equal(0b01);
+
+      await acknowledge(newcomer);
+      attestation = await program.account.attestation.fetch(attestationPda);
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ active: {} }));
+    });
+  });
+
+  // ============================================
+  // Wallet Links
+  // ============================================
+
+  describe("wallet links", () => {
+    const revoke = async (attestationPda: PublicKey) =>
+      program.methods
+        .revokeAttestation({ issuerError: {} }, null)
+        .accounts({
+          state: findStatePda()[0],
+          attestation: attestationPda,
+          authority: authority.publicKey,
+        })
+        .remainingAccounts(await attestationAccounts(attestationPda))
+        .rpc();
+
+    it("creates a link for every listed wallet", async () => {
+      const wallets = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+      const { attestationPda } = await createAttestation({ wallets });
+
+      for (const wallet of wallets) {
+        const link = await program.account.walletAttestationLink.fetch(
+          findWalletLinkPda(wallet, attestationPda)[0]
+        );
+        expect(link.wallet.toBase58()).to.equal(wallet.toBase58());
+        expect(link.attestation.toBase58()).to.equal(attestationPda.toBase58());
+        expect(link.linkedAt.toNumber()).to.be.greaterThan(0);
+        expect(link.revokedAt.toNumber()).to.equal(0);
+      }
+    });
+
+    it("finds every attestation of a wallet by its links", async () => {
+      const wallet = Keypair.generate().publicKey;
+      const first = await createAttestation({ wallets: [wallet] });
+      const second = await createAttestation({ wallets: [wallet], taxYear: 2024 });
+
+      const links = await program.account.walletAttestationLink.all([
+        { memcmp: { offset: 9, bytes: wallet.toBase58() } },
+      ]);
+      expect(links.map((l) => l.account.attestation.toBase58()).sort()).to.deep.equal(
+        [first.attestationPda, second.attestationPda].map((a) => a.toBase58()).sort()
+      );
+    });
+
+    it("records the revocation on each link", async () => {
+      const { attestationPda, wallets } = await createAttestation();
+      await revoke(attestationPda);
+
+      const link = await program.account.walletAttestationLink.fetch(
+        findWalletLinkPda(wallets[0], attestationPda)[0]
+      );
+      expect(link.revokedAt.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("fails to revoke without the wallet links", async () => {
+      const { attestationPda } = await createAttestation();
+
+      try {
+        await program.methods
+          .revokeAttestation({ issuerError: {} }, null)
+          .accounts({
+            state: findStatePda()[0],
+            attestation: attestationPda,
+            authority: authority.publicKey,
+          })
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("WalletLinkMismatch");
+      }
+    });
+
+    it("follows wallets as they are added and removed", async () => {
+      const { attestationPda, wallets } = await createAttestation();
+      const scope = await program.account.attestation.fetch(attestationPda);
+      const added = Keypair.generate().publicKey;
+      const accounts = {
+        attestation: attestationPda,
+        issuer: findIssuerPda(authority.publicKey)[0],
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      };
+
+      await program.methods
+        .addWallets([added])
+        .accounts(accounts)
+        .remainingAccounts(walletAccounts(attestationPda, [added], scope))
+        .rpc();
+      const addedLink = findWalletLinkPda(added, attestationPda)[0];
+      expect(await provider.connection.getAccountInfo(addedLink)).to.not.be.null;
+
+      await program.methods
+        .removeWallets([wallets[0]])
+        .accounts(accounts)
+        .remainingAccounts(walletAccounts(attestationPda, [wallets[0]], scope))
+        .rpc();
+      const removedLink = findWalletLinkPda(wallets[0], attestationPda)[0];
+      expect(await provider.connection.getAccountInfo(removedLink)).to.be.null;
+    });
+  });
+
+  // ============================================
+  // Uniqueness Slots
+  // ============================================
+
+  describe("uniqueness slots", () => {
+    const scope = { jurisdiction: iso("BR"), attestationType: { taxCompliance: {} }, taxYear: 2025 };
+
+    const slotHolder = async (wallet: PublicKey) =>
+      (await program.account.attestationSlot.fetch(findSlotPda(wallet, scope)[0])).attestation;
+
+    it("records the attestation holding each wallet's slot", async () => {
+      const wallet = Keypair.generate().publicKey;
+      const { attestationPda } = await createAttestation({ ...scope, wallets: [wallet] });
+
+      const slot = await program.account.attestationSlot.fetch(findSlotPda(wallet, scope)[0]);
+      expect(slot.wallet.toBase58()).to.equal(wallet.toBase58());
+      expect(slot.taxYear).to.equal(2025);
+      expect(slot.attestation.toBase58()).to.equal(attestationPda.toBase58());
+    });
+
+    it("gives each month of a tax year its own slot", async () => {
+      const wallet = Keypair.generate().publicKey;
+      const months = [1, 2].map((month) => ({
+        kind: { monthly: {} },
+        start: utc(2025, month),
+        end: utc(2025, month + 1),
+      }));
+
+      for (const period of months) {
+        await createAttestation({ ...scope, period, wallets: [wallet] });
+      }
+
+      const slot = await program.account.attestationSlot.fetch(
+        findSlotPda(wallet, { ...scope, periodKind: months[1].kind, periodStart: months[1].start })[0]
+      );
+      expect(slot.periodStart.toNumber()).to.equal(utc(2025, 2).toNumber());
+    });
+
+    it("refuses a second attestation for the same slot", async () => {
+      const wallet = Keypair.generate().publicKey;
+      await createAttestation({ ...scope, wallets: [wallet] });
+
+      try {
+        await createAttestation({ ...scope, wallets: [Keypair.generate().publicKey, wallet] });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("SlotOccupied");
+      }
+    });
+
+    it("allows another type for the same wallet and year", async () => {
+      const wallet = Keypair.generate().publicKey;
+      await createAttestation({ ...scope, wallets: [wallet] });
+      await createAttestation({ ...scope, attestationType: { auditComplete: {} }, wallets: [wallet] });
+    });
+
+    it("frees the slot when the holder is revoked", async () => {
+      const wallet = Keypair.generate().publicKey;
+      const first = await createAttestation({ ...scope, wallets: [wallet] });
+
+      await program.methods
+        .revokeAttestation({ issuerError: {} }, null)
+        .accounts({
+          state: findStatePda()[0],
+          attestation: first.attestationPda,
+          authority: authority.publicKey,
+        })
+        .remainingAccounts(await attestationAccounts(first.attestationPda))
+        .rpc();
+      expect((await slotHolder(wallet)).toBase58()).to.equal(PublicKey.default.toBase58());
+
+      const second = await createAttestation({ ...scope, wallets: [wallet] });
+      expect((await slotHolder(wallet)).toBase58()).to.equal(second.attestationPda.toBase58());
+    });
+
+    it("lets another attestation claim the slot once the holder is past expiry", async () => {
+      const wallet = Keypair.generate().publicKey;
+      // A non-renewable type, whose holder could not otherwise give the slot up
+      const auditScope = { ...scope, attestationType: { auditComplete: {} } };
+      await createAttestation({
+        ...auditScope,
+        wallets: [wallet],
+        expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 3),
+      });
+      await new Promise((resolve) => setTimeout(resolve, 5000));
+
+      const second = await createAttestation({ ...auditScope, wallets: [wallet] });
+      const slot = await program.account.attestationSlot.fetch(findSlotPda(wallet, auditScope)[0]);
+      expect(slot.attestation.toBase58()).to.equal(second.attestationPda.toBase58());
+      expect(slot.expiresAt.toNumber()).to.equal(second.expiresAt.toNumber());
+    });
+
+    it("hands slots to an amendment and frees those of dropped wallets", async () => {
+      const kept = Keypair.generate().publicKey;
+      const dropped = Keypair.generate().publicKey;
+      const original = await createAttestation({ ...scope, wallets: [kept, dropped] });
+
+      const auditHash = makeAuditHash();
+      const [amendedPda] = await nextAttestationPda();
+      await program.methods
+        .supersedeAttestation(
+          auditHash,
+          new anchor.BN(Math.floor(Date.now() / 1000) + 86400 * 365),
+          [kept]
+        )
+        .accounts({
+          state: findStatePda()[0],
+          previous: original.attestationPda,
+          attestation: amendedPda,
+          issuer: findIssuerPda(authority.publicKey)[0],
+          jurisdictionConfig: findJurisdictionPda(scope.jurisdiction)[0],
+          schema: baseSchemaPda(scope.attestationType),
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([
+          ...walletAccounts(amendedPda, [kept], scope),
+          { pubkey: findSlotPda(dropped, scope)[0], isSigner: false, isWritable: true },
+        ])
+        .rpc();
 
-      const stateAfter = await program.account.programState.fetch(statePda);
-      expect(stateAfter.attestationCount.toNumber()).to.equal(countBefore + 3);
+      expect((await slotHolder(kept)).toBase58()).to.equal(amendedPda.toBase58());
+      expect((await slotHolder(dropped)).toBase58()).to.equal(PublicKey.default.toBase58());
     });
   });
 
   // ============================================
-  // PDA Derivation
+  // Tax Year Ranges
   // ============================================
 
-  describe("PDA derivation", () => {
-    it("verifies PDA address matches expected derivation", async () => {
-      const auditHash = makeAuditHash();
-      const [expectedPda, expectedBump] = findAttestationPda(auditHash);
+  describe("tax year ranges", () => {
+    // Statute-of-limitations review of three years
+    const scope = {
+      jurisdiction: iso("US"),
+      attestationType: { annualReview: {} },
+      taxYear: 2021,
+      endTaxYear: 2023,
+    };
 
-      const { attestationPda } = await createAttestation({ auditHash });
+    const coversTaxYear = (attestation: PublicKey, taxYear: number) =>
+      program.methods.coversTaxYear(taxYear).accounts({ attestation }).view();
 
-      expect(attestationPda.toBase58()).to.equal(expectedPda.toBase58());
+    it("covers every year of its range", async () => {
+      const { attestationPda } = await createAttestation(scope);
 
       const attestation = await program.account.attestation.fetch(attestationPda);
-      expect(attestation.bump).to.equal(expectedBump);
+      expect(attestation.taxYear).to.equal(2021);
+      expect(attestation.endTaxYear).to.equal(2023);
+      expect(attestation.periodEnd.toNumber()).to.equal(utc(2024, 1).toNumber());
+
+      expect(await coversTaxYear(attestationPda, 2020)).to.equal(false);
+      expect(await coversTaxYear(attestationPda, 2021)).to.equal(true);
+      expect(await coversTaxYear(attestationPda, 2023)).to.equal(true);
+      expect(await coversTaxYear(attestationPda, 2024)).to.equal(false);
     });
 
-    it("verifies state PDA address", async () => {
-      const [expectedStatePda, expectedBump] = findStatePda();
-      const state = await program.account.programState.fetch(expectedStatePda);
-      expect(state.bump).to.equal(expectedBump);
+    it("claims a slot for each covered year", async () => {
+      const wallet = Keypair.generate().publicKey;
+      const { attestationPda } = await createAttestation({ ...scope, wallets: [wallet] });
+
+      for (const taxYear of [2021, 2022, 2023]) {
+        const slot = await program.account.attestationSlot.fetch(
+          findSlotPda(wallet, { ...scope, taxYear })[0]
+        );
+        expect(slot.taxYear).to.equal(taxYear);
+        expect(slot.attestation.toBase58()).to.equal(attestationPda.toBase58());
+      }
+    });
+
+    it("refuses a single-year attestation inside the range", async () => {
+      const wallet = Keypair.generate().publicKey;
+      await createAttestation({ ...scope, wallets: [wallet] });
+
+      try {
+        await createAttestation({
+          ...scope,
+          taxYear: 2022,
+          endTaxYear: 2022,
+          wallets: [wallet],
+        });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("SlotOccupied");
+      }
+    });
+
+    it("fails for a reversed or overlong range", async () => {
+      for (const [taxYear, endTaxYear] of [
+        [2023, 2021],
+        [2010, 2020],
+      ]) {
+        try {
+          await createAttestation({ ...scope, taxYear, endTaxYear });
+          expect.fail("should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.contain("InvalidTaxYearRange");
+        }
+      }
+    });
+
+    it("fails when wallets times covered years exceeds MAX_WALLET_SLOTS", async () => {
+      // Four wallets over three years would need twelve slots
+      const wallets = Array.from({ length: 4 }, () => Keypair.generate().publicKey);
+
+      try {
+        await createAttestation({ ...scope, wallets });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("TooManySlots");
+      }
+    });
+
+    it("fails for a quarter spanning several years", async () => {
+      try {
+        await createAttestation({
+          ...scope,
+          attestationType: { quarterlyReview: {} },
+          period: { kind: { quarterly: {} }, start: utc(2021, 1), end: utc(2021, 4) },
+        });
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("InvalidPeriod");
+      }
     });
   });
 
   // ============================================
-  // Update Status
+  // Multisig Authority
   // ============================================
 
-  describe("update_status", () => {
-    it("updates status from Active to Expired", async () => {
-      const { attestationPda, auditHash } = await createAttestation();
-      const [statePda] = findStatePda();
+  // Runs last: hands the program authority over to the multisig vault.
+  describe("multisig", () => {
+    const signers = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    const [multisigPda] = findMultisigPda();
+    const [vaultPda] = findVaultPda();
+    let attestationPda: PublicKey;
+    // Approved by a signer that a later update_multisig removes
+    let staleProposal: PublicKey;
 
-      await program.methods
-        .updateStatus({ expired: {} })
+    const approve = (proposalPda: PublicKey, signer: Keypair) =>
+      program.methods
+        .approveTransaction()
+        .accounts({ multisig: multisigPda, proposal: proposalPda, signer: signer.publicKey })
+        .signers([signer])
+        .rpc();
+
+    const cancel = (proposalPda: PublicKey, proposer: PublicKey, signer: Keypair) =>
+      program.methods
+        .cancelProposal()
         .accounts({
-          state: statePda,
-          attestation: attestationPda,
-          authority: authority.publicKey,
+          multisig: multisigPda,
+          proposal: proposalPda,
+          proposer,
+          signer: signer.publicKey,
         })
+        .signers([signer])
         .rpc();
 
-      const attestation = await program.account.attestation.fetch(attestationPda);
-      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ expired: {} }));
-      // revoked_at should remain 0 for expired status
-      expect(attestation.revokedAt.toNumber()).to.equal(0);
-    });
+    const revokeInstruction = async (attestation: PublicKey) =>
+      program.methods
+        .revokeAttestation({ issuerError: {} }, null)
+        .accounts({ state: findStatePda()[0], attestation, authority: vaultPda })
+        .remainingAccounts(await attestationAccounts(attestation))
+        .instruction();
 
-    it("updates status from Active to Revoked (sets revoked_at)", async () => {
-      const { attestationPda } = await createAttestation();
-      const [statePda] = findStatePda();
+    // Propose `ix` (built with the vault as authority) and return the proposal PDA
+    const propose = async (ix: anchor.web3.TransactionInstruction, proposer: Keypair) => {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+      const [proposalPda] = findProposalPda(multisig.proposalCount);
 
       await program.methods
-        .updateStatus({ revoked: {} })
+        .proposeTransaction(
+          ix.keys.map((k) => ({
+            pubkey: k.pubkey,
+            isSigner: k.isSigner,
+            isWritable: k.isWritable,
+          })),
+          ix.data
+        )
         .accounts({
-          state: statePda,
-          attestation: attestationPda,
-          authority: authority.publicKey,
+          multisig: multisigPda,
+          proposal: proposalPda,
+          proposer: proposer.publicKey,
+          systemProgram: SystemProgram.programId,
         })
+        .signers([proposer])
         .rpc();
 
-      const attestation = await program.account.attestation.fetch(attestationPda);
-      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ revoked: {} }));
-      expect(attestation.revokedAt.toNumber()).to.be.greaterThan(0);
+      return proposalPda;
+    };
+
+    const execute = async (
+      proposalPda: PublicKey,
+      ix: anchor.web3.TransactionInstruction,
+      executor: Keypair
+    ) =>
+      program.methods
+        .executeTransaction()
+        .accounts({
+          multisig: multisigPda,
+          proposal: proposalPda,
+          vault: vaultPda,
+          executor: executor.publicKey,
+          attestationProgram: program.programId,
+        })
+        .remainingAccounts(
+          ix.keys.map((k) => ({
+            pubkey: k.pubkey,
+            isSigner: false,
+            isWritable: k.isWritable,
+          }))
+        )
+        .signers([executor])
+        .rpc();
+
+    before(async () => {
+      for (const signer of signers) {
+        await airdrop(signer.publicKey);
+      }
+      ({ attestationPda } = await createAttestation());
     });
 
-    it("fails for invalid transition Active -> Pending", async () => {
-      const { attestationPda } = await createAttestation();
+    it("fails with threshold greater than signer count", async () => {
       const [statePda] = findStatePda();
-
       try {
         await program.methods
-          .updateStatus({ pending: {} })
+          .createMultisig(4, signers.map((s) => s.publicKey))
           .accounts({
             state: statePda,
-            attestation: attestationPda,
+            multisig: multisigPda,
+            vault: vaultPda,
             authority: authority.publicKey,
+            systemProgram: SystemProgram.programId,
           })
           .rpc();
         expect.fail("should have thrown");
       } catch (err: any) {
-        const errMsg = err.toString();
-        expect(errMsg).to.contain("InvalidStatusTransition");
+        expect(err.toString()).to.contain("InvalidThreshold");
       }
     });
 
-    it("fails for invalid transition Expired -> Active", async () => {
-      const { attestationPda } = await createAttestation();
+    it("fails with duplicate signers", async () => {
       const [statePda] = findStatePda();
-
-      // First transition to Expired
-      await program.methods
-        .updateStatus({ expired: {} })
-        .accounts({
-          state: statePda,
-          attestation: attestationPda,
-          authority: authority.publicKey,
-        })
-        .rpc();
-
-      // Try invalid transition Expired -> Active
       try {
         await program.methods
-          .updateStatus({ active: {} })
+          .createMultisig(2, [signers[0].publicKey, signers[0].publicKey])
           .accounts({
             state: statePda,
-            attestation: attestationPda,
+            multisig: multisigPda,
+            vault: vaultPda,
             authority: authority.publicKey,
+            systemProgram: SystemProgram.programId,
           })
           .rpc();
         expect.fail("should have thrown");
       } catch (err: any) {
-        const errMsg = err.toString();
-        expect(errMsg).to.contain("InvalidStatusTransition");
+        expect(err.toString()).to.contain("DuplicateSigner");
       }
     });
 
-    it("fails to update status on revoked attestation", async () => {
-      const { attestationPda } = await createAttestation();
+    it("creates a 2-of-3 multisig and hands over authority", async () => {
       const [statePda] = findStatePda();
 
-      // Revoke it first
+      // A transfer proposed by the single key must not survive the handover
+      const candidate = Keypair.generate();
+      await program.methods
+        .proposeAuthority(candidate.publicKey)
+        .accounts({ state: statePda, authority: authority.publicKey })
+        .rpc();
+
       await program.methods
-        .revokeAttestation()
+        .createMultisig(2, signers.map((s) => s.publicKey))
         .accounts({
           state: statePda,
-          attestation: attestationPda,
+          multisig: multisigPda,
+          vault: vaultPda,
           authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
         })
         .rpc();
 
-      // Try to update status on revoked attestation
+      const multisig = await program.account.multisig.fetch(multisigPda);
+      expect(multisig.threshold).to.equal(2);
+      expect(multisig.signers.map((k) => k.toBase58())).to.deep.equal(
+        signers.map((s) => s.publicKey.toBase58())
+      );
+      expect(multisig.proposalCount.toNumber()).to.equal(0);
+
+      const state = await program.account.programState.fetch(statePda);
+      expect(state.authority.toBase58()).to.equal(vaultPda.toBase58());
+      expect(state.pendingAuthority.toBase58()).to.equal(PublicKey.default.toBase58());
+
       try {
         await program.methods
-          .updateStatus({ expired: {} })
-          .accounts({
-            state: statePda,
-            attestation: attestationPda,
-            authority: authority.publicKey,
-          })
+          .acceptAuthority()
+          .accounts({ state: statePda, pendingAuthority: candidate.publicKey })
+          .signers([candidate])
           .rpc();
         expect.fail("should have thrown");
       } catch (err: any) {
-        const errMsg = err.toString();
-        expect(errMsg).to.contain("InvalidStatusTransition");
+        expect(err.toString()).to.contain("NoPendingAuthority");
       }
-    });
 
-    it("fails with wrong authority", async () => {
-      const fakeAuthority = Keypair.generate();
-      const sig = await provider.connection.requestAirdrop(
-        fakeAuthority.publicKey,
-        2 * anchor.web3.LAMPORTS_PER_SOL
-      );
-      await provider.connection.confirmTransaction(sig);
+      // The vault pays rent for accounts created through proposals
+      await airdrop(vaultPda);
+    });
 
-      const { attestationPda } = await createAttestation();
+    it("rejects the previous single authority", async () => {
       const [statePda] = findStatePda();
-
       try {
         await program.methods
-          .updateStatus({ expired: {} })
+          .revokeAttestation({ issuerError: {} }, null)
           .accounts({
             state: statePda,
             attestation: attestationPda,
-            authority: fakeAuthority.publicKey,
+            authority: authority.publicKey,
           })
-          .signers([fakeAuthority])
+          .remainingAccounts(await attestationAccounts(attestationPda))
           .rpc();
         expect.fail("should have thrown");
       } catch (err: any) {
-        const errMsg = err.toString();
-        expect(errMsg).to.contain("Unauthorized");
+        expect(err.toString()).to.contain("Unauthorized");
       }
     });
-  });
-
-  // ============================================
-  // Revoke Attestation
-  // ============================================
 
-  describe("revoke_attestation", () => {
-    it("revokes an active attestation", async () => {
-      const { attestationPda } = await createAttestation();
+    it("fails to propose from a non-member", async () => {
+      const stranger = Keypair.generate();
+      await airdrop(stranger.publicKey);
       const [statePda] = findStatePda();
+      const ix = await program.methods
+        .revokeAttestation({ issuerError: {} }, null)
+        .accounts({ state: statePda, attestation: attestationPda, authority: vaultPda })
+        .remainingAccounts(await attestationAccounts(attestationPda))
+        .instruction();
 
-      await program.methods
-        .revokeAttestation()
-        .accounts({
-          state: statePda,
-          attestation: attestationPda,
-          authority: authority.publicKey,
-        })
-        .rpc();
-
-      const attestation = await program.account.attestation.fetch(attestationPda);
-      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ revoked: {} }));
-      expect(attestation.revokedAt.toNumber()).to.be.greaterThan(0);
+      try {
+        await propose(ix, stranger);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("NotMultisigSigner");
+      }
     });
 
-    it("preserves wallet data after revocation", async () => {
-      const wallets = [
-        Keypair.generate().publicKey,
-        Keypair.generate().publicKey,
-      ];
-      const { attestationPda } = await createAttestation({ wallets });
+    it("revokes an attestation once the threshold is reached", async () => {
       const [statePda] = findStatePda();
+      const ix = await program.methods
+        .revokeAttestation({ issuerError: {} }, null)
+        .accounts({ state: statePda, attestation: attestationPda, authority: vaultPda })
+        .remainingAccounts(await attestationAccounts(attestationPda))
+        .instruction();
+
+      const proposalPda = await propose(ix, signers[0]);
+
+      // One approval (the proposer) is not enough
+      try {
+        await execute(proposalPda, ix, signers[0]);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("InsufficientApprovals");
+      }
 
       await program.methods
-        .revokeAttestation()
+        .approveTransaction()
         .accounts({
-          state: statePda,
-          attestation: attestationPda,
-          authority: authority.publicKey,
+          multisig: multisigPda,
+          proposal: proposalPda,
+          signer: signers[1].publicKey,
         })
+        .signers([signers[1]])
         .rpc();
 
+      await execute(proposalPda, ix, signers[2]);
+
       const attestation = await program.account.attestation.fetch(attestationPda);
-      expect(attestation.wallets.length).to.equal(2);
-      expect(attestation.wallets[0].toBase58()).to.equal(wallets[0].toBase58());
-      expect(attestation.wallets[1].toBase58()).to.equal(wallets[1].toBase58());
+      expect(JSON.stringify(attestation.status)).to.equal(JSON.stringify({ revoked: {} }));
+
+      const proposal = await program.account.proposal.fetch(proposalPda);
+      expect(proposal.executed).to.equal(true);
+      expect(proposal.approvals.length).to.equal(2);
+
+      // A proposal can only run once
+      try {
+        await execute(proposalPda, ix, signers[0]);
+        expect.fail("should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.contain("ProposalAlreadyExecuted");
+      }
     });
 
-    it("fails to revoke an already revoked attestation", async () => {
-      const { attestationPda } = await createAttestation();
+    it("fails to approve twice", async () => {
       const [statePda] = findStatePda();
+      const { attestationPda: other } = await createAttestation();
+      const ix = await program.methods
+        .revokeAttestation({ issuerError: {} }, null)
+        .accounts({ state: statePda, attestation: other, authority: vaultPda })
+        .remainingAccounts(await attestationAccounts(other))
+        .instruction();
+      const proposalPda = await propose(ix, signers[0]);
 
-      // Revoke once
-      await program.methods
-        .revokeAttestation()
-        .accounts({
-          state: statePda,
-          attestation: attestationPda,
-          authority: authority.publicKey,
-        })
-        .rpc();
-
-      // Try to revoke again
       try {
         await program.methods
-          .revokeAttestation()
+          .approveTransaction()
           .accounts({
-            state: statePda,
-            attestation: attestationPda,
-            authority: authority.publicKey,
+            multisig: multisigPda,
+            proposal: proposalPda,
+            signer: signers[0].publicKey,
           })
+          .signers([signers[0]])
           .rpc();
         expect.fail("should have thrown");
       } catch (err: any) {
-        const errMsg = err.toString();
-        expect(errMsg).to.contain("AttestationNotActive");
+        expect(err.toString()).to.contain("AlreadyApproved");
       }
     });
 
-    it("fails to revoke an expired attestation", async () => {
-      const { attestationPda } = await createAttestation();
+    it("fails when remaining accounts do not match the proposal", async () => {
       const [statePda] = findStatePda();
+      const ix = await program.methods
+        .revokeAttestation({ issuerError: {} }, null)
+        .accounts({ state: statePda, attestation: attestationPda, authority: vaultPda })
+        .remainingAccounts(await attestationAccounts(attestationPda))
+        .instruction();
+      const proposalPda = await propose(ix, signers[0]);
 
-      // Expire it first
       await program.methods
-        .updateStatus({ expired: {} })
+        .approveTransaction()
         .accounts({
-          state: statePda,
-          attestation: attestationPda,
-          authority: authority.publicKey,
+          multisig: multisigPda,
+          proposal: proposalPda,
+          signer: signers[1].publicKey,
         })
+        .signers([signers[1]])
         .rpc();
 
-      // Try to revoke
+      const tampered = { ...ix, keys: [...ix.keys] } as anchor.web3.TransactionInstruction;
+      tampered.keys[1] = { ...tampered.keys[1], pubkey: Keypair.generate().publicKey };
+
       try {
-        await program.methods
-          .revokeAttestation()
-          .accounts({
-            state: statePda,
-            attestation: attestationPda,
-            authority: authority.publicKey,
-          })
-          .rpc();
+        await execute(proposalPda, tampered, signers[0]);
         expect.fail("should have thrown");
       } catch (err: any) {
-        const errMsg = err.toString();
-        expect(errMsg).to.contain("AttestationNotActive");
+        expect(err.toString()).to.contain("ProposalAccountMismatch");
       }
     });
 
-    it("fails with wrong authority", async () => {
-      const fakeAuthority = Keypair.generate();
-      const sig = await provider.connection.requestAirdrop(
-        fakeAuthority.publicKey,
-        2 * anchor.web3.LAMPORTS_PER_SOL
-      );
-      await provider.connection.confirmTransaction(sig);
-
-      const { attestationPda } = await createAttestation();
-      const [statePda] = findStatePda();
+    it("lets only the proposer cancel an open proposal", async () => {
+      const { attestationPda: other } = await createAttestation();
+      const proposalPda = await propose(await revokeInstruction(other), signers[0]);
 
       try {
-        await program.methods
-          .revokeAttestation()
-          .accounts({
-            state: statePda,
-            attestation: attestationPda,
-            authority: fakeAuthority.publicKey,
-          })
-          .signers([fakeAuthority])
-          .rpc();
+        await cancel(proposalPda, signers[0].publicKey, signers[1]);
         expect.fail("should have thrown");
       } catch (err: any) {
-        const errMsg = err.toString();
-        expect(errMsg).to.contain("Unauthorized");
+        expect(err.toString()).to.contain("ProposalNotExpired");
       }
+
+      await cancel(proposalPda, signers[0].publicKey, signers[0]);
+      expect(await provider.connection.getAccountInfo(proposalPda)).to.be.null;
+    });
+
+    it("updates the signer set through a proposal", async () => {
+      const { attestationPda: other } = await createAttestation();
+      staleProposal = await propose(await revokeInstruction(other), signers[0]);
+      await approve(staleProposal, signers[1]);
+
+      const replacement = Keypair.generate();
+      const ix = await program.methods
+        .updateMultisig(1, [signers[0].publicKey, replacement.publicKey])
+        .accounts({ multisig: multisigPda, vault: vaultPda })
+        .instruction();
+      const proposalPda = await propose(ix, signers[0]);
+
+      await program.methods
+        .approveTransaction()
+        .accounts({
+          multisig: multisigPda,
+          proposal: proposalPda,
+          signer: signers[2].publicKey,
+        })
+        .signers([signers[2]])
+        .rpc();
+
+      await execute(proposalPda, ix, signers[0]);
+
+      const multisig = await program.account.multisig.fetch(multisigPda);
+      expect(multisig.threshold).to.equal(1);
+      expect(multisig.signers.map((k) => k.toBase58())).to.deep.equal([
+        signers[0].publicKey.toBase58(),
+        replacement.publicKey.toBase58(),
+      ]);
+
+      // Approvals of the removed signer are pruned on the next approval
+      await approve(staleProposal, replacement);
+      const proposal = await program.account.proposal.fetch(staleProposal);
+      expect(proposal.approvals.map((k) => k.toBase58())).to.deep.equal([
+        signers[0].publicKey.toBase58(),
+        replacement.publicKey.toBase58(),
+      ]);
     });
   });
 });